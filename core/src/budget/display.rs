@@ -1,4 +1,4 @@
-use super::resources::Budget;
+use super::resources::{Budget, BudgetLedger, LedgerCategory};
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -29,9 +29,10 @@ pub fn setup_budget_display(mut commands: Commands, asset_server: Res<AssetServe
 
 pub fn update_budget_display(
     budget: Res<Budget>,
+    ledger: Res<BudgetLedger>,
     mut query: Query<&mut Text, With<BudgetDisplayText>>,
 ) {
-    if !budget.is_changed() {
+    if !budget.is_changed() && !ledger.is_changed() {
         return;
     }
 
@@ -39,7 +40,22 @@ pub fn update_budget_display(
         return;
     };
 
-    text.0 = format!("Budget: ${}", format_money(budget.money));
+    let mut label = format!("Budget: ${}", format_money(budget.money));
+
+    // Keep the ledger lines in a stable order so the panel doesn't jump around
+    let mut entries: Vec<(&LedgerCategory, &i64)> = ledger.entries.iter().collect();
+    entries.sort_by_key(|(category, _)| category.label());
+    for (category, amount) in entries {
+        let sign = if *amount >= 0 { "+" } else { "-" };
+        label.push_str(&format!(
+            "\n{}: {}${}",
+            category.label(),
+            sign,
+            format_money(amount.abs())
+        ));
+    }
+
+    text.0 = label;
 }
 
 fn format_money(amount: i64) -> String {
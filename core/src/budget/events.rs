@@ -1,19 +1,47 @@
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::TilePos;
 
-use super::resources::BuildingType;
+use super::resources::{BuildingType, Footprint};
 
 #[derive(Message)]
 pub struct BuildingPlaced {
     pub building_type: BuildingType,
     pub tile_pos: TilePos,
+    /// Tiles covered from `tile_pos`, already rotated
+    pub footprint: Footprint,
 }
 
 #[derive(Message)]
 pub struct BuildingDemolished {
     pub building_type: BuildingType,
     pub tile_pos: TilePos,
+    pub footprint: Footprint,
+}
+
+/// Why a placement click did not turn into a building
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionFailureReason {
+    NotPlaceable,
+    Occupied,
+    Unaffordable,
+    NoRoadAccess,
+    Locked,
+}
+
+impl TransactionFailureReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransactionFailureReason::NotPlaceable => "Can't build here",
+            TransactionFailureReason::Occupied => "Tile is occupied",
+            TransactionFailureReason::Unaffordable => "Not enough money",
+            TransactionFailureReason::NoRoadAccess => "Needs an adjacent road",
+            TransactionFailureReason::Locked => "Not unlocked yet",
+        }
+    }
 }
 
 #[derive(Message)]
-pub struct TransactionFailed;
+pub struct TransactionFailed {
+    pub reason: TransactionFailureReason,
+    pub tile_pos: TilePos,
+}
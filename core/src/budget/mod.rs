@@ -1,23 +1,51 @@
 use bevy::prelude::*;
 
+use crate::menu::{GAME_EXIT, GameState, reset_resource};
+
 pub mod display;
 mod events;
 mod resources;
 pub mod spending;
 
-pub use events::{BuildingDemolished, BuildingPlaced, TransactionFailed};
-pub use resources::{Budget, BuildingType};
+pub use events::{BuildingDemolished, BuildingPlaced, TransactionFailed, TransactionFailureReason};
+pub use resources::{
+    Budget, BudgetLedger, BuildingType, DEFAULT_TAX_RATE, Footprint, LedgerCategory, Rotation,
+    TAX_RATE_STEP, TaxRates, TaxSector,
+};
 
 pub struct BudgetPlugin;
 
 impl Plugin for BudgetPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Budget>()
+            .init_resource::<BudgetLedger>()
+            .init_resource::<TaxRates>()
+            .init_resource::<spending::ShopTakings>()
             .add_message::<BuildingPlaced>()
             .add_message::<BuildingDemolished>()
             .add_message::<TransactionFailed>()
             .add_systems(Startup, display::setup_budget_display)
             .add_systems(Update, display::update_budget_display)
-            .add_systems(Update, spending::update_income_on_day_tick);
+            .add_systems(
+                Update,
+                (
+                    spending::update_income_on_day_tick,
+                    spending::pay_shop_takings_hourly,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+
+        for exit in GAME_EXIT {
+            app.add_systems(
+                exit,
+                (
+                    reset_resource::<Budget>,
+                    reset_resource::<BudgetLedger>,
+                    reset_resource::<TaxRates>,
+                    reset_resource::<spending::ShopTakings>,
+                ),
+            );
+        }
     }
 }
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+use std::collections::HashMap;
 
 #[derive(Resource)]
 pub struct Budget {
@@ -26,16 +28,136 @@ impl Budget {
     }
 }
 
+/// Tax rate the daily income figures were tuned for
+pub const DEFAULT_TAX_RATE: f32 = 0.09;
+/// How far one click on a tax button moves a rate
+pub const TAX_RATE_STEP: f32 = 0.01;
+/// Highest rate players may set for any sector
+pub const MAX_TAX_RATE: f32 = 0.2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxSector {
+    Residential,
+    Commercial,
+    Industrial,
+}
+
+/// Per-sector tax rates as a fraction of earnings. Higher rates bring in more
+/// money per worker but lower the demand for that kind of zone.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct TaxRates {
+    pub residential: f32,
+    pub commercial: f32,
+    pub industrial: f32,
+}
+
+impl Default for TaxRates {
+    fn default() -> Self {
+        Self {
+            residential: DEFAULT_TAX_RATE,
+            commercial: DEFAULT_TAX_RATE,
+            industrial: DEFAULT_TAX_RATE,
+        }
+    }
+}
+
+impl TaxRates {
+    pub fn rate(&self, sector: TaxSector) -> f32 {
+        match sector {
+            TaxSector::Residential => self.residential,
+            TaxSector::Commercial => self.commercial,
+            TaxSector::Industrial => self.industrial,
+        }
+    }
+
+    /// Move one sector's rate by `delta`, kept between 0% and `MAX_TAX_RATE` and
+    /// snapped to whole percents so repeated clicks don't drift
+    pub fn adjust(&mut self, sector: TaxSector, delta: f32) {
+        let rate = match sector {
+            TaxSector::Residential => &mut self.residential,
+            TaxSector::Commercial => &mut self.commercial,
+            TaxSector::Industrial => &mut self.industrial,
+        };
+        *rate = ((*rate + delta).clamp(0.0, MAX_TAX_RATE) * 100.0).round() / 100.0;
+    }
+}
+
+/// Income and expense lines booked outside of the daily tax/upkeep tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LedgerCategory {
+    TransitFares,
+    TransitUpkeep,
+    CityEvents,
+    GoodsExports,
+    GoodsImports,
+    MilestoneRewards,
+    Demolition,
+    LandPurchases,
+}
+
+impl LedgerCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LedgerCategory::TransitFares => "Transit fares",
+            LedgerCategory::TransitUpkeep => "Transit upkeep",
+            LedgerCategory::CityEvents => "City events",
+            LedgerCategory::GoodsExports => "Goods exports",
+            LedgerCategory::GoodsImports => "Goods imports",
+            LedgerCategory::MilestoneRewards => "Milestone rewards",
+            LedgerCategory::Demolition => "Demolition",
+            LedgerCategory::LandPurchases => "Land purchases",
+        }
+    }
+}
+
+/// Per-day breakdown of ledger entries, reset whenever a new day is booked
+#[derive(Resource, Default)]
+pub struct BudgetLedger {
+    pub day: u32,
+    pub entries: HashMap<LedgerCategory, i64>,
+}
+
+impl BudgetLedger {
+    /// Apply `amount` (positive for income, negative for expenses) to the budget and
+    /// remember it under `category` for the given day
+    pub fn book(&mut self, budget: &mut Budget, day: u32, category: LedgerCategory, amount: i64) {
+        if self.day != day {
+            self.day = day;
+            self.entries.clear();
+        }
+
+        budget.money += amount;
+        *self.entries.entry(category).or_default() += amount;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BuildingType {
     Residential,
     Commercial,
     Industry,
     Road,
     Decorative,
+    BusStop,
+    School,
+    Stadium,
 }
 
 impl BuildingType {
+    /// Name shown to the player
+    pub fn label(&self) -> &'static str {
+        match self {
+            BuildingType::Residential => "Residential",
+            BuildingType::Commercial => "Commercial",
+            BuildingType::Industry => "Industry",
+            BuildingType::Road => "Road",
+            BuildingType::Decorative => "Decorative",
+            BuildingType::BusStop => "Bus stop",
+            BuildingType::School => "School",
+            BuildingType::Stadium => "Stadium",
+        }
+    }
+
     // Construction costs
     pub fn cost(&self) -> i64 {
         match self {
@@ -44,6 +166,20 @@ impl BuildingType {
             BuildingType::Industry => 2000,
             BuildingType::Road => 50,
             BuildingType::Decorative => 500,
+            BuildingType::BusStop => 300,
+            BuildingType::School => 1500,
+            BuildingType::Stadium => 8000,
+        }
+    }
+
+    /// Tiles the building covers when placed without rotation
+    pub fn footprint(&self) -> Footprint {
+        match self {
+            BuildingType::Stadium => Footprint {
+                width: 3,
+                height: 2,
+            },
+            _ => Footprint::SINGLE,
         }
     }
 
@@ -54,7 +190,132 @@ impl BuildingType {
             4 => Some(BuildingType::Industry),
             5 => Some(BuildingType::Road),
             6 => Some(BuildingType::Decorative),
+            7 => Some(BuildingType::BusStop),
+            8 => Some(BuildingType::School),
+            9 => Some(BuildingType::Stadium),
             _ => None,
         }
     }
 }
+
+/// Which way a building faces; every step turns it a quarter clockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rotation {
+    #[default]
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Rotation {
+    pub fn next(self) -> Self {
+        match self {
+            Rotation::North => Rotation::East,
+            Rotation::East => Rotation::South,
+            Rotation::South => Rotation::West,
+            Rotation::West => Rotation::North,
+        }
+    }
+
+    pub fn quarter_turns(self) -> u32 {
+        match self {
+            Rotation::North => 0,
+            Rotation::East => 1,
+            Rotation::South => 2,
+            Rotation::West => 3,
+        }
+    }
+
+    /// Clockwise angle in radians, for turning sprites
+    pub fn angle(self) -> f32 {
+        -(self.quarter_turns() as f32) * std::f32::consts::FRAC_PI_2
+    }
+}
+
+/// Size of a building in tiles. A building's `tile_pos` is the bottom-left
+/// tile of its footprint and the rest extends up and to the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Footprint {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Footprint {
+    pub const SINGLE: Footprint = Footprint {
+        width: 1,
+        height: 1,
+    };
+
+    /// The footprint after turning the building; quarter turns swap its sides
+    pub fn rotated(self, rotation: Rotation) -> Self {
+        if rotation.quarter_turns() % 2 == 1 {
+            Footprint {
+                width: self.height,
+                height: self.width,
+            }
+        } else {
+            self
+        }
+    }
+
+    pub fn is_single(&self) -> bool {
+        *self == Footprint::SINGLE
+    }
+
+    /// Every tile covered when the footprint starts at `origin`
+    pub fn tiles(self, origin: TilePos) -> impl Iterator<Item = TilePos> {
+        (origin.x..origin.x + self.width)
+            .flat_map(move |x| (origin.y..origin.y + self.height).map(move |y| TilePos { x, y }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tax_rates_stay_in_range() {
+        let mut taxes = TaxRates::default();
+
+        taxes.adjust(TaxSector::Commercial, TAX_RATE_STEP);
+        assert_eq!(taxes.rate(TaxSector::Commercial), 0.10);
+        assert_eq!(taxes.rate(TaxSector::Residential), DEFAULT_TAX_RATE);
+
+        for _ in 0..30 {
+            taxes.adjust(TaxSector::Industrial, TAX_RATE_STEP);
+            taxes.adjust(TaxSector::Residential, -TAX_RATE_STEP);
+        }
+        assert_eq!(taxes.industrial, MAX_TAX_RATE);
+        assert_eq!(taxes.residential, 0.0);
+    }
+
+    #[test]
+    fn rotating_a_footprint_swaps_its_sides() {
+        let stadium = BuildingType::Stadium.footprint();
+
+        assert_eq!(stadium.rotated(Rotation::South), stadium);
+        assert_eq!(
+            stadium.rotated(Rotation::East),
+            Footprint {
+                width: 2,
+                height: 3
+            }
+        );
+        assert_eq!(Rotation::West.next(), Rotation::North);
+    }
+
+    #[test]
+    fn footprint_tiles_extend_up_and_right_from_the_origin() {
+        let tiles: Vec<_> = BuildingType::Stadium
+            .footprint()
+            .tiles(TilePos { x: 4, y: 7 })
+            .collect();
+
+        assert_eq!(tiles.len(), 6);
+        assert!(tiles.contains(&TilePos { x: 4, y: 7 }));
+        assert!(tiles.contains(&TilePos { x: 6, y: 8 }));
+        assert!(!tiles.contains(&TilePos { x: 7, y: 7 }));
+        assert_eq!(Footprint::SINGLE.tiles(TilePos { x: 1, y: 1 }).count(), 1);
+    }
+}
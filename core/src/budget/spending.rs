@@ -1,9 +1,14 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::city::resources::{CityInfrastructure, CityPopulation, CityServices};
+use crate::city::goods::GoodsFlow;
+use crate::city::resources::{CityInfrastructure, CityPopulation, CityServices, Demographics};
+use crate::notifications::Notify;
 use crate::time::GameClock;
+use crate::time::day_cycle::{hours_since, sales_in_hour};
+use crate::time::weather::WeatherState;
 
-use super::resources::Budget;
+use super::resources::{Budget, BuildingType, DEFAULT_TAX_RATE, TaxRates};
 
 // income per productive worker per day (tuned for a snappier early game)
 const WORKER_TAX_PER_DAY: i64 = 8;
@@ -18,39 +23,93 @@ const RES_UPKEEP_PER_BUILDING: i64 = 0;
 const COM_UPKEEP_PER_BUILDING: i64 = 2;
 const IND_UPKEEP_PER_BUILDING: i64 = 10;
 const DECORATIVE_UPKEEP_PER_BUILDING: i64 = 15;
+const SCHOOL_UPKEEP_PER_BUILDING: i64 = 12;
+const STADIUM_UPKEEP_PER_BUILDING: i64 = 60;
 
 const NEGATIVE_BALANCE_PENALTY_DAYS: u32 = 3;
 const NEGATIVE_BALANCE_HAPPINESS_PENALTY: f32 = 0.01;
 const HEALTHY_RESERVE_THRESHOLD: i64 = 100_000;
 const HEALTHY_RESERVE_HAPPINESS_BONUS: f32 = 0.005;
 
+/// Fixed daily upkeep of a single building; bus stops are paid for by their lines
+pub fn daily_upkeep(building_type: BuildingType) -> i64 {
+    match building_type {
+        BuildingType::Residential => RES_UPKEEP_PER_BUILDING,
+        BuildingType::Commercial => COM_UPKEEP_PER_BUILDING,
+        BuildingType::Industry => IND_UPKEEP_PER_BUILDING,
+        BuildingType::Road => ROAD_UPKEEP_PER_TILE,
+        BuildingType::Decorative => DECORATIVE_UPKEEP_PER_BUILDING,
+        BuildingType::BusStop => 0,
+        BuildingType::School => SCHOOL_UPKEEP_PER_BUILDING,
+        BuildingType::Stadium => STADIUM_UPKEEP_PER_BUILDING,
+    }
+}
+
+/// What the city's shops are expected to pay in tax today. It is worked out on the
+/// daily tick but paid as the sales happen, mostly in the evening.
+#[derive(Resource, Default, Debug)]
+pub struct ShopTakings {
+    pub daily: i64,
+}
+
+#[derive(Default)]
+pub struct IncomeTickState {
+    last_income_day: u32,
+    negative_streak: u32,
+}
+
+#[derive(SystemParam)]
+pub struct IncomeCityInputs<'w> {
+    population: ResMut<'w, CityPopulation>,
+    services: Res<'w, CityServices>,
+    demographics: Res<'w, Demographics>,
+    infra: Res<'w, CityInfrastructure>,
+    tax_rates: Res<'w, TaxRates>,
+    goods: Res<'w, GoodsFlow>,
+    weather: Res<'w, WeatherState>,
+    shop_takings: ResMut<'w, ShopTakings>,
+}
+
 /// derive periodic income/upkeep and modify `Budget` once per in‑game day
 pub fn update_income_on_day_tick(
     clock: Res<GameClock>,
-    mut population: ResMut<CityPopulation>,
-    services: Res<CityServices>,
-    infra: Res<CityInfrastructure>,
+    city: IncomeCityInputs,
     mut budget: ResMut<Budget>,
-    mut last_income_day: Local<u32>,
-    mut negative_streak: Local<u32>,
+    mut notify_writer: MessageWriter<Notify>,
+    mut state: Local<IncomeTickState>,
 ) {
-    if *last_income_day == clock.day {
+    if state.last_income_day == clock.day {
         return;
     }
-    *last_income_day = clock.day;
+    state.last_income_day = clock.day;
+
+    let IncomeCityInputs {
+        mut population,
+        services,
+        demographics,
+        infra,
+        tax_rates,
+        goods,
+        weather,
+        mut shop_takings,
+    } = city;
 
-    let pop = population.population.max(0);
     let jobs = services.job_capacity.max(0);
 
-    let employed = pop.min(jobs);
+    // only working-age citizens with the right schooling can fill jobs
+    let employed = demographics.workforce().min(jobs);
 
     let productive_workers =
         ((employed as f32) * population.happiness.clamp(0.0, 1.0)).round() as i64;
 
-    let income_from_workers = productive_workers * WORKER_TAX_PER_DAY;
+    let income_from_workers = (productive_workers as f32
+        * WORKER_TAX_PER_DAY as f32
+        * (tax_rates.residential / DEFAULT_TAX_RATE))
+        .round() as i64;
 
     // income sharing between industry and commercial with efficiency loss for underutilized buildings
     let mut corp_income: f32 = 0.0;
+    let mut commercial_income: f32 = 0.0;
     if services.job_capacity > 0 {
         let total_jobs = services.job_capacity as f32;
         let industry_job_cap = infra.industry_job_capacity as f32;
@@ -81,8 +140,11 @@ pub fn update_income_on_day_tick(
             1.0
         };
 
-        let industry_efficiency = (industry_staffing_ratio * 1.2).min(1.0);
-        let commercial_efficiency = (commercial_staffing_ratio * 1.2).min(1.0);
+        // factories only earn on goods that found a buyer, shops only sell what was delivered
+        let industry_efficiency =
+            (industry_staffing_ratio * 1.2).min(1.0) * goods.factory_sales_ratio();
+        let commercial_efficiency =
+            (commercial_staffing_ratio * 1.2).min(1.0) * goods.shop_supply_ratio();
 
         // Apply efficiency multiplier to productive workers (happiness-adjusted)
         let industry_productive =
@@ -90,39 +152,59 @@ pub fn update_income_on_day_tick(
         let commercial_productive =
             (commercial_workers * population.happiness.clamp(0.0, 1.0)) * commercial_efficiency;
 
-        corp_income += industry_productive * INDUSTRY_PROFIT_PER_WORKER;
-        corp_income += commercial_productive * COMMERCIAL_PROFIT_PER_WORKER;
+        corp_income += industry_productive
+            * INDUSTRY_PROFIT_PER_WORKER
+            * (tax_rates.industrial / DEFAULT_TAX_RATE);
+        commercial_income = commercial_productive
+            * COMMERCIAL_PROFIT_PER_WORKER
+            * (tax_rates.commercial / DEFAULT_TAX_RATE);
     }
     let income_from_corporations = corp_income.round() as i64;
+    shop_takings.daily = commercial_income.round() as i64;
 
     let total_income = income_from_workers + income_from_corporations;
 
-    let road_upkeep = infra.road_count * ROAD_UPKEEP_PER_TILE;
-    let residential_upkeep = infra.residential_count * RES_UPKEEP_PER_BUILDING;
-    let commercial_upkeep = infra.commercial_count * COM_UPKEEP_PER_BUILDING;
-    let industry_upkeep = infra.industry_count * IND_UPKEEP_PER_BUILDING;
-    let decorative_upkeep = infra.decorative_count * DECORATIVE_UPKEEP_PER_BUILDING;
-
-    let upkeep =
-        road_upkeep + residential_upkeep + commercial_upkeep + industry_upkeep + decorative_upkeep;
+    let road_upkeep = infra.road_count
+        * (daily_upkeep(BuildingType::Road) + weather.today.road_upkeep_per_tile());
+    let residential_upkeep = infra.residential_count * daily_upkeep(BuildingType::Residential);
+    let commercial_upkeep = infra.commercial_count * daily_upkeep(BuildingType::Commercial);
+    let industry_upkeep = infra.industry_count * daily_upkeep(BuildingType::Industry);
+    let decorative_upkeep = infra.decorative_count * daily_upkeep(BuildingType::Decorative);
+    let school_upkeep = infra.school_count * daily_upkeep(BuildingType::School);
+    let stadium_upkeep = infra.stadium_count * daily_upkeep(BuildingType::Stadium);
+
+    let upkeep = road_upkeep
+        + residential_upkeep
+        + commercial_upkeep
+        + industry_upkeep
+        + decorative_upkeep
+        + school_upkeep
+        + stadium_upkeep;
 
     let net = total_income - upkeep;
     budget.money += net;
 
     // track negative streak for soft happiness penalties when running deficits
     if budget.money < 0 {
-        *negative_streak += 1;
+        state.negative_streak += 1;
     } else {
-        *negative_streak = 0;
+        state.negative_streak = 0;
+    }
+
+    if state.negative_streak == NEGATIVE_BALANCE_PENALTY_DAYS {
+        notify_writer.write(Notify::warning(format!(
+            "The budget has been in deficit for {} days, citizens are getting unhappy",
+            state.negative_streak
+        )));
     }
 
-    if *negative_streak >= NEGATIVE_BALANCE_PENALTY_DAYS {
+    if state.negative_streak >= NEGATIVE_BALANCE_PENALTY_DAYS {
         let old = population.happiness;
         population.happiness =
             (population.happiness - NEGATIVE_BALANCE_HAPPINESS_PENALTY).clamp(0.0, 1.0);
         info!(
             "Happiness decreased from {:.3} to {:.3} due to running a budget deficit for {} days",
-            old, population.happiness, *negative_streak
+            old, population.happiness, state.negative_streak
         );
     }
 
@@ -140,3 +222,15 @@ pub fn update_income_on_day_tick(
         );
     }
 }
+
+/// Book the shops' tax hour by hour as they make their sales
+pub fn pay_shop_takings_hourly(
+    clock: Res<GameClock>,
+    shop_takings: Res<ShopTakings>,
+    mut budget: ResMut<Budget>,
+    mut last_hour: Local<Option<u64>>,
+) {
+    for hour in hours_since(&mut last_hour, &clock) {
+        budget.money += sales_in_hour(shop_takings.daily, hour);
+    }
+}
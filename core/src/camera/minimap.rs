@@ -0,0 +1,223 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::RelativeCursorPosition;
+use bevy_ecs_tilemap::prelude::{TileStorage, TilemapGridSize, TilemapSize, TilemapType};
+
+use super::CameraController;
+use crate::budget::BuildingType;
+use crate::map::{
+    PlacedBuilding, TileState, UiClickBlocker, grid_point_to_world, world_to_grid_point,
+};
+
+/// Side of the minimap on screen; the map image is stretched to fit
+pub const MINIMAP_SIZE: f32 = 160.0;
+const MINIMAP_BORDER: f32 = 2.0;
+
+const UNOWNED_COLOR: Color = Color::srgb(0.12, 0.2, 0.12);
+const PLACEABLE_COLOR: Color = Color::srgb(0.3, 0.5, 0.28);
+const TERRAIN_COLOR: Color = Color::srgb(0.35, 0.35, 0.38);
+const ABANDONED_COLOR: Color = Color::srgb(0.4, 0.28, 0.2);
+
+/// Corner overview of the whole map, one pixel per tile
+#[derive(Component)]
+pub struct Minimap;
+
+/// Outline on the minimap of what the main camera currently sees
+#[derive(Component)]
+pub struct MinimapViewport;
+
+fn building_color(building_type: BuildingType) -> Color {
+    match building_type {
+        BuildingType::Residential => Color::srgb(0.35, 0.6, 0.95),
+        BuildingType::Commercial => Color::srgb(0.3, 0.85, 0.4),
+        BuildingType::Industry => Color::srgb(0.95, 0.8, 0.3),
+        BuildingType::Road => Color::srgb(0.75, 0.75, 0.75),
+        BuildingType::Decorative => Color::srgb(0.95, 0.5, 0.85),
+        BuildingType::BusStop => Color::srgb(1.0, 0.6, 0.1),
+        BuildingType::School => Color::srgb(0.9, 0.35, 0.3),
+        BuildingType::Stadium => Color::srgb(0.55, 0.9, 0.6),
+    }
+}
+
+fn tile_color(state: TileState, building_q: &Query<&PlacedBuilding>) -> Color {
+    match state {
+        TileState::Empty => UNOWNED_COLOR,
+        TileState::Placeable => PLACEABLE_COLOR,
+        TileState::Terrain => TERRAIN_COLOR,
+        TileState::Abandoned(_) => ABANDONED_COLOR,
+        TileState::Occupied(entity) => building_q.get(entity).map_or(PLACEABLE_COLOR, |building| {
+            building_color(building.building_type)
+        }),
+    }
+}
+
+fn blank_image(width: u32, height: u32) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // keep tiles as crisp squares when the image is stretched
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+pub fn setup_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = images.add(blank_image(1, 1));
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                width: Val::Px(MINIMAP_SIZE),
+                height: Val::Px(MINIMAP_SIZE),
+                border: UiRect::all(Val::Px(MINIMAP_BORDER)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BorderColor::all(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            ImageNode::new(image),
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+            Minimap,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::WHITE),
+                MinimapViewport,
+            ));
+        });
+}
+
+/// Repaint the minimap whenever a tile changes hands, is built on or is cleared.
+/// A new map's tiles count as changed, so the image is resized to fit it then.
+pub fn redraw_minimap(
+    changed_q: Query<(), Changed<TileState>>,
+    map_q: Query<(&TileStorage, &TilemapSize)>,
+    tile_q: Query<&TileState>,
+    building_q: Query<&PlacedBuilding>,
+    minimap_q: Query<&ImageNode, With<Minimap>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if changed_q.is_empty() {
+        return;
+    }
+    let Some((tile_storage, map_size)) = map_q.iter().next() else {
+        return;
+    };
+    let Ok(node) = minimap_q.single() else {
+        return;
+    };
+    let Some(image) = images.get_mut(&node.image) else {
+        return;
+    };
+
+    if image.width() != map_size.x || image.height() != map_size.y {
+        *image = blank_image(map_size.x, map_size.y);
+    }
+
+    for (tile_pos, tile_entity) in tile_storage.iter().enumerate() {
+        let Some(state) = tile_entity.and_then(|entity| tile_q.get(entity).ok()) else {
+            continue;
+        };
+        let x = tile_pos as u32 % map_size.x;
+        let y = tile_pos as u32 / map_size.x;
+        // image rows run top to bottom, tile rows bottom to top
+        let _ = image.set_color_at(x, map_size.y - 1 - y, tile_color(*state, &building_q));
+    }
+}
+
+/// Outline the part of the map the main camera can see. On an isometric map the view
+/// covers a slanted patch of tiles, so the outline is the box around it.
+pub fn update_minimap_viewport(
+    camera_q: Query<(&Transform, &Projection), With<CameraController>>,
+    map_q: Query<
+        (&TilemapSize, &TilemapGridSize, &TilemapType, &Transform),
+        Without<CameraController>,
+    >,
+    mut viewport_q: Query<&mut Node, With<MinimapViewport>>,
+) {
+    let Ok((camera, projection)) = camera_q.single() else {
+        return;
+    };
+    let Projection::Orthographic(ortho) = projection else {
+        return;
+    };
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    let center = camera.translation.truncate();
+    let (view_min, view_max) = (center + ortho.area.min, center + ortho.area.max);
+    let corners = [
+        view_min,
+        Vec2::new(view_max.x, view_min.y),
+        view_max,
+        Vec2::new(view_min.x, view_max.y),
+    ]
+    .map(|corner| world_to_grid_point(corner, map_size, grid_size, map_type, map_transform));
+    let min = corners.iter().copied().fold(Vec2::INFINITY, Vec2::min);
+    let max = corners.iter().copied().fold(Vec2::NEG_INFINITY, Vec2::max);
+
+    let (width, height) = (map_size.x as f32, map_size.y as f32);
+    for mut node in viewport_q.iter_mut() {
+        node.left = Val::Percent(min.x / width * 100.0);
+        node.top = Val::Percent((height - max.y) / height * 100.0);
+        node.width = Val::Percent((max.x - min.x) / width * 100.0);
+        node.height = Val::Percent((max.y - min.y) / height * 100.0);
+    }
+}
+
+/// Click or drag on the minimap to move the main camera over that spot
+pub fn navigate_with_minimap(
+    minimap_q: Query<(&Interaction, &RelativeCursorPosition), With<Minimap>>,
+    map_q: Query<
+        (&TilemapSize, &TilemapGridSize, &TilemapType, &Transform),
+        Without<CameraController>,
+    >,
+    mut camera_q: Query<&mut Transform, With<CameraController>>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    let Ok((interaction, cursor)) = minimap_q.single() else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    ui_click_blocker.just_clicked_ui = true;
+
+    let Some(normalized) = cursor.normalized else {
+        return;
+    };
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    // (0, 0) is the middle of the minimap and +y points down it
+    let normalized = normalized.clamp(Vec2::splat(-0.5), Vec2::splat(0.5));
+    let point = Vec2::new(
+        (normalized.x + 0.5) * map_size.x as f32,
+        (0.5 - normalized.y) * map_size.y as f32,
+    );
+    let target = grid_point_to_world(point, map_size, grid_size, map_type, map_transform);
+
+    for mut transform in camera_q.iter_mut() {
+        transform.translation.x = target.x;
+        transform.translation.y = target.y;
+    }
+}
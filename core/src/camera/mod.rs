@@ -1,4 +1,23 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy::window::PrimaryWindow;
+use bevy_ecs_tilemap::prelude::{TilePos, TilemapGridSize, TilemapSize, TilemapType};
+
+use crate::map::{grid_point_to_world, tile_center_to_world};
+use crate::menu::GameState;
+use crate::settings::{Action, ActionInput, GameSettings};
+
+mod minimap;
+
+pub use minimap::MINIMAP_SIZE;
+
+/// How close to the window edge, in pixels, the cursor has to be to pan the camera
+const EDGE_PAN_MARGIN: f32 = 12.0;
+/// Zoom change for one notch of the scroll wheel, as a fraction of the current zoom
+const WHEEL_ZOOM_STEP: f32 = 0.1;
+/// Pixels of smooth (touchpad) scrolling that count as one wheel notch
+const PIXELS_PER_WHEEL_NOTCH: f32 = 50.0;
 
 #[derive(Component)]
 pub struct CameraController {
@@ -19,33 +38,88 @@ impl Default for CameraController {
     }
 }
 
+/// Request to centre the camera on a map tile, e.g. from the notification log
+#[derive(Message)]
+pub struct FocusCameraOnTile {
+    pub tile_pos: TilePos,
+}
+
 #[allow(dead_code)]
 pub struct CameraControllerPlugin;
 
 impl Plugin for CameraControllerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (camera_movement, camera_zoom, reset_camera));
+        app.add_message::<FocusCameraOnTile>()
+            .add_systems(Startup, minimap::setup_minimap)
+            .add_systems(
+                Update,
+                (
+                    (
+                        camera_movement,
+                        camera_drag_pan,
+                        camera_zoom,
+                        reset_camera,
+                        focus_camera_on_tile,
+                        minimap::navigate_with_minimap,
+                        clamp_camera_to_map,
+                    )
+                        .chain(),
+                    minimap::redraw_minimap,
+                    minimap::update_minimap_viewport.after(clamp_camera_to_map),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Direction to pan when the cursor rests against an edge of the window; zero
+/// elsewhere. Window coordinates grow downwards, world coordinates upwards.
+fn edge_pan_direction(cursor: Vec2, window_size: Vec2) -> Vec2 {
+    let mut direction = Vec2::ZERO;
+    if cursor.x <= EDGE_PAN_MARGIN {
+        direction.x -= 1.0;
+    }
+    if cursor.x >= window_size.x - EDGE_PAN_MARGIN {
+        direction.x += 1.0;
+    }
+    if cursor.y <= EDGE_PAN_MARGIN {
+        direction.y += 1.0;
     }
+    if cursor.y >= window_size.y - EDGE_PAN_MARGIN {
+        direction.y -= 1.0;
+    }
+    direction
 }
 
 fn camera_movement(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
+    settings: Res<GameSettings>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
     mut camera_q: Query<(&mut Transform, &CameraController), With<Camera>>,
 ) {
+    let edge_direction = match window_q.single() {
+        Ok(window) if settings.edge_panning && window.focused => {
+            window.cursor_position().map_or(Vec2::ZERO, |cursor| {
+                edge_pan_direction(cursor, window.size())
+            })
+        }
+        _ => Vec2::ZERO,
+    };
+
     for (mut transform, controller) in camera_q.iter_mut() {
-        let mut direction = Vec3::ZERO;
+        let mut direction = edge_direction.extend(0.0);
 
-        if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
+        if actions.pressed(Action::CameraUp) {
             direction.y += 1.0;
         }
-        if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
+        if actions.pressed(Action::CameraDown) {
             direction.y -= 1.0;
         }
-        if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
+        if actions.pressed(Action::CameraLeft) {
             direction.x -= 1.0;
         }
-        if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
+        if actions.pressed(Action::CameraRight) {
             direction.x += 1.0;
         }
 
@@ -56,36 +130,109 @@ fn camera_movement(
     }
 }
 
+/// Hold the middle mouse button and drag to pull the map along with the cursor
+fn camera_drag_pan(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    mut camera_q: Query<(&mut Transform, &Projection), With<CameraController>>,
+    mut last_cursor: Local<Option<Vec2>>,
+) {
+    let cursor = window_q
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    if !mouse.pressed(MouseButton::Middle) {
+        *last_cursor = None;
+        return;
+    }
+
+    if let (Some(previous), Some(cursor)) = (*last_cursor, cursor) {
+        // one window pixel covers `scale` world units; window y grows downwards
+        let delta = cursor - previous;
+        for (mut transform, projection) in camera_q.iter_mut() {
+            let scale = match projection {
+                Projection::Orthographic(ortho) => ortho.scale,
+                _ => 1.0,
+            };
+            transform.translation.x -= delta.x * scale;
+            transform.translation.y += delta.y * scale;
+        }
+    }
+    *last_cursor = cursor;
+}
+
+/// Camera position after zooming from `old_scale` to `new_scale` so that the world
+/// point `anchor` stays under the same spot on screen
+fn zoom_about(camera: Vec2, anchor: Vec2, old_scale: f32, new_scale: f32) -> Vec2 {
+    anchor + (camera - anchor) * (new_scale / old_scale)
+}
+
+/// Zoom with the keys around the middle of the screen, or with the scroll wheel
+/// towards whatever is under the cursor. Scrolling over a panel scrolls the panel.
 fn camera_zoom(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut camera_q: Query<(&mut Projection, &CameraController)>,
+    actions: ActionInput,
+    mut wheel_reader: MessageReader<MouseWheel>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    panel_q: Query<&RelativeCursorPosition>,
+    mut camera_q: Query<(&mut Transform, &mut Projection, &CameraController)>,
 ) {
-    for (mut projection, controller) in camera_q.iter_mut() {
-        let mut zoom_delta = 0.0;
+    let notches: f32 = wheel_reader
+        .read()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / PIXELS_PER_WHEEL_NOTCH,
+        })
+        .sum();
+    let over_panel = panel_q.iter().any(RelativeCursorPosition::cursor_over);
+    let window = window_q.single().ok();
+
+    for (mut transform, mut projection, controller) in camera_q.iter_mut() {
+        let Projection::Orthographic(ortho) = projection.as_mut() else {
+            continue;
+        };
 
-        if keyboard.pressed(KeyCode::Equal) || keyboard.pressed(KeyCode::NumpadAdd) {
+        let mut zoom_delta = 0.0;
+        if actions.pressed(Action::ZoomIn) {
             zoom_delta -= 1.0;
         }
-        if keyboard.pressed(KeyCode::Minus) || keyboard.pressed(KeyCode::NumpadSubtract) {
+        if actions.pressed(Action::ZoomOut) {
             zoom_delta += 1.0;
         }
-
-        if zoom_delta != 0.0
-            && let Projection::Orthographic(ortho) = projection.as_mut()
-        {
+        if zoom_delta != 0.0 {
             let zoom_change = zoom_delta * controller.zoom_speed * time.delta_secs();
             ortho.scale =
                 (ortho.scale + zoom_change).clamp(controller.min_zoom, controller.max_zoom);
         }
+
+        if notches == 0.0 || over_panel {
+            continue;
+        }
+        let Some((window, cursor)) =
+            window.and_then(|window| Some((window, window.cursor_position()?)))
+        else {
+            continue;
+        };
+
+        let old_scale = ortho.scale;
+        ortho.scale = (old_scale * (1.0 - WHEEL_ZOOM_STEP).powf(notches))
+            .clamp(controller.min_zoom, controller.max_zoom);
+
+        // world point under the cursor, window y grows downwards
+        let camera = transform.translation.truncate();
+        let offset = (cursor - window.size() / 2.0) * Vec2::new(1.0, -1.0);
+        let anchor = camera + offset * old_scale;
+        let target = zoom_about(camera, anchor, old_scale, ortho.scale);
+        transform.translation.x = target.x;
+        transform.translation.y = target.y;
     }
 }
 
 fn reset_camera(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut camera_q: Query<(&mut Transform, &mut Projection), With<CameraController>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Home) {
+    if actions.just_pressed(Action::ResetCamera) {
         for (mut transform, mut projection) in camera_q.iter_mut() {
             transform.translation = Vec3::ZERO;
 
@@ -97,3 +244,130 @@ fn reset_camera(
         }
     }
 }
+
+fn focus_camera_on_tile(
+    mut focus_reader: MessageReader<FocusCameraOnTile>,
+    map_q: Query<
+        (&TilemapSize, &TilemapGridSize, &TilemapType, &Transform),
+        Without<CameraController>,
+    >,
+    mut camera_q: Query<&mut Transform, With<CameraController>>,
+) {
+    let Some(request) = focus_reader.read().last() else {
+        return;
+    };
+
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    let target = tile_center_to_world(
+        &request.tile_pos,
+        map_size,
+        grid_size,
+        map_type,
+        map_transform,
+    );
+
+    for mut transform in camera_q.iter_mut() {
+        transform.translation.x = target.x;
+        transform.translation.y = target.y;
+    }
+}
+
+/// Keep the camera's centre where the map stays in view: while the map is bigger than
+/// the view it fills the screen, and once the view is bigger the whole map stays on it.
+/// `map_min..map_max` and `half_view` are along one axis.
+fn clamp_axis(center: f32, map_min: f32, map_max: f32, half_view: f32) -> f32 {
+    let (a, b) = (map_min + half_view, map_max - half_view);
+    center.clamp(a.min(b), a.max(b))
+}
+
+/// Stop the camera from leaving the map, whatever moved it this frame. On an isometric
+/// map this uses the box around the diamond.
+fn clamp_camera_to_map(
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    map_q: Query<
+        (&TilemapSize, &TilemapGridSize, &TilemapType, &Transform),
+        Without<CameraController>,
+    >,
+    mut camera_q: Query<(&mut Transform, &Projection), With<CameraController>>,
+) {
+    let Ok(window) = window_q.single() else {
+        return;
+    };
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    let (width, height) = (map_size.x as f32, map_size.y as f32);
+    let corners = [
+        Vec2::ZERO,
+        Vec2::new(width, 0.0),
+        Vec2::new(0.0, height),
+        Vec2::new(width, height),
+    ]
+    .map(|corner| {
+        grid_point_to_world(corner, map_size, grid_size, map_type, map_transform).truncate()
+    });
+    let map_min = corners.iter().copied().fold(Vec2::INFINITY, Vec2::min);
+    let map_max = corners.iter().copied().fold(Vec2::NEG_INFINITY, Vec2::max);
+
+    for (mut transform, projection) in camera_q.iter_mut() {
+        let Projection::Orthographic(ortho) = projection else {
+            continue;
+        };
+        let half_view = window.size() * ortho.scale / 2.0;
+        let translation = &mut transform.translation;
+        translation.x = clamp_axis(translation.x, map_min.x, map_max.x, half_view.x);
+        translation.y = clamp_axis(translation.y, map_min.y, map_max.y, half_view.y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zooming_keeps_the_point_under_the_cursor_in_place() {
+        let camera = Vec2::new(100.0, -40.0);
+        let anchor = Vec2::new(300.0, 60.0);
+        let offset = (anchor - camera) / 2.0;
+
+        let zoomed = zoom_about(camera, anchor, 2.0, 0.5);
+        assert!((zoomed + offset * 0.5).abs_diff_eq(anchor, 1e-4));
+        assert_eq!(zoom_about(camera, camera, 2.0, 0.5), camera);
+    }
+
+    #[test]
+    fn the_map_stays_in_view_at_any_zoom() {
+        // map wider than the view: the view can't run past either edge
+        assert_eq!(clamp_axis(5_000.0, -1_000.0, 1_000.0, 400.0), 600.0);
+        assert_eq!(clamp_axis(-5_000.0, -1_000.0, 1_000.0, 400.0), -600.0);
+        assert_eq!(clamp_axis(100.0, -1_000.0, 1_000.0, 400.0), 100.0);
+        // view wider than the map: the map can't slide off either side
+        assert_eq!(clamp_axis(5_000.0, -1_000.0, 1_000.0, 1_500.0), 500.0);
+        assert_eq!(clamp_axis(-5_000.0, -1_000.0, 1_000.0, 1_500.0), -500.0);
+    }
+
+    #[test]
+    fn only_the_window_edges_pan_the_camera() {
+        let window = Vec2::new(1280.0, 720.0);
+        assert_eq!(
+            edge_pan_direction(Vec2::new(640.0, 360.0), window),
+            Vec2::ZERO
+        );
+        assert_eq!(
+            edge_pan_direction(Vec2::new(0.0, 360.0), window),
+            Vec2::NEG_X
+        );
+        assert_eq!(
+            edge_pan_direction(Vec2::new(1279.0, 0.0), window),
+            Vec2::new(1.0, 1.0)
+        );
+        assert_eq!(
+            edge_pan_direction(Vec2::new(640.0, 719.0), window),
+            Vec2::NEG_Y
+        );
+    }
+}
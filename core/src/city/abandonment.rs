@@ -0,0 +1,251 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+
+use crate::budget::BuildingType;
+use crate::map::{Derelict, PlacedBuilding};
+use crate::spatial::SpatialGrid;
+use crate::time::GameClock;
+
+use super::resources::{CityPopulation, is_accessible};
+
+/// Radius (in tiles) used for entertainment and pollution around a building
+const LOCAL_RADIUS: i32 = 3;
+/// How far citizens look for a workplace, or businesses for workers
+const COMMUTE_SEARCH_RADIUS: i32 = 8;
+
+/// Buildings scoring at least this are flagged as at risk
+pub const AT_RISK_SCORE: f32 = 0.4;
+/// Warnings appear once happiness falls this low, a little above the
+/// abandonment threshold so players get a few days of notice
+const AT_RISK_HAPPINESS: f32 = 0.8;
+/// Below this happiness flagged buildings start being abandoned
+pub const ABANDON_HAPPINESS: f32 = 0.7;
+/// A building must stay flagged this many days in a row before it can be abandoned
+pub const AT_RISK_WARNING_DAYS: u32 = 3;
+const _: () = assert!(AT_RISK_HAPPINESS > ABANDON_HAPPINESS);
+
+/// Local factors that make a building more or less likely to be abandoned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalConditions {
+    pub road_access: bool,
+    /// Chebyshev distance to the nearest workplace (for homes) or home (for workplaces)
+    pub commute_distance: Option<i32>,
+    pub entertainment: u32,
+    /// Industry buildings close by
+    pub pollution: u32,
+    pub age_days: u32,
+}
+
+impl LocalConditions {
+    pub fn survey(building: &PlacedBuilding, spatial_grid: &SpatialGrid, day: u32) -> Self {
+        let pos = &building.tile_pos;
+
+        let commute_partners: &[BuildingType] = match building.building_type {
+            BuildingType::Residential => &[BuildingType::Commercial, BuildingType::Industry],
+            _ => &[BuildingType::Residential],
+        };
+        let commute_distance = commute_partners
+            .iter()
+            .flat_map(|partner| {
+                spatial_grid.buildings_of_type_in_radius(pos, COMMUTE_SEARCH_RADIUS, *partner)
+            })
+            .filter(|other| other != pos)
+            .map(|other| chebyshev_distance(pos, &other))
+            .min();
+
+        let count_nearby = |building_type: BuildingType| {
+            spatial_grid
+                .buildings_of_type_in_radius(pos, LOCAL_RADIUS, building_type)
+                .iter()
+                .filter(|other| *other != pos)
+                .count() as u32
+        };
+
+        Self {
+            road_access: is_accessible(pos, spatial_grid),
+            commute_distance,
+            entertainment: count_nearby(BuildingType::Commercial)
+                + count_nearby(BuildingType::Decorative),
+            pollution: count_nearby(BuildingType::Industry),
+            age_days: day.saturating_sub(building.placed_day),
+        }
+    }
+
+    /// Higher is worse; roughly 0.0 for a well-served building up to ~1.3
+    pub fn risk_score(&self, building_type: BuildingType) -> f32 {
+        let mut score = 0.0;
+
+        if !self.road_access {
+            score += 0.35;
+        }
+
+        score += match self.commute_distance {
+            Some(distance) => 0.03 * distance as f32,
+            None => 0.3,
+        };
+
+        if building_type != BuildingType::Industry {
+            score += (0.2 - 0.05 * self.entertainment as f32).max(0.0);
+            score += (0.08 * self.pollution as f32).min(0.3);
+        }
+
+        // older buildings wear out; new ones get the benefit of the doubt
+        score += (self.age_days as f32 / 30.0).min(1.0) * 0.15;
+
+        score
+    }
+}
+
+fn chebyshev_distance(a: &TilePos, b: &TilePos) -> i32 {
+    let dx = (a.x as i32 - b.x as i32).abs();
+    let dy = (a.y as i32 - b.y as i32).abs();
+    dx.max(dy)
+}
+
+/// Marks a live building likely to be abandoned soon
+#[derive(Component, Debug)]
+pub struct AtRisk {
+    pub score: f32,
+    /// Day the building was first flagged in the current unbroken run of warnings
+    pub since_day: u32,
+}
+
+impl AtRisk {
+    /// Only buildings that have been warned about for long enough may be abandoned
+    pub fn warning_served(&self, day: u32) -> bool {
+        day.saturating_sub(self.since_day) >= AT_RISK_WARNING_DAYS
+    }
+}
+
+/// Once per day, flag the residential and job buildings that would be
+/// abandoned first if the city stays unhappy
+pub fn flag_buildings_at_risk(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    population: Res<CityPopulation>,
+    spatial_grid: Res<SpatialGrid>,
+    buildings_q: Query<(Entity, &PlacedBuilding, Option<&AtRisk>), Without<Derelict>>,
+    derelict_flags_q: Query<Entity, (With<AtRisk>, With<Derelict>)>,
+    mut last_processed_day: Local<u32>,
+) {
+    if *last_processed_day == clock.day {
+        return;
+    }
+    *last_processed_day = clock.day;
+
+    for entity in derelict_flags_q.iter() {
+        commands.entity(entity).remove::<AtRisk>();
+    }
+
+    let warnings_on = population.population > 0 && population.happiness < AT_RISK_HAPPINESS;
+
+    for (entity, building, flagged) in buildings_q.iter() {
+        let candidate = matches!(
+            building.building_type,
+            BuildingType::Residential | BuildingType::Commercial | BuildingType::Industry
+        );
+
+        let score = LocalConditions::survey(building, &spatial_grid, clock.day)
+            .risk_score(building.building_type);
+
+        if warnings_on && candidate && score >= AT_RISK_SCORE {
+            // keep the original day so the warning period isn't restarted
+            let since_day = flagged.map_or(clock.day, |flag| flag.since_day);
+            commands.entity(entity).insert(AtRisk { score, since_day });
+        } else if flagged.is_some() {
+            commands.entity(entity).remove::<AtRisk>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::{Footprint, Rotation};
+
+    fn conditions() -> LocalConditions {
+        LocalConditions {
+            road_access: true,
+            commute_distance: Some(2),
+            entertainment: 4,
+            pollution: 0,
+            age_days: 0,
+        }
+    }
+
+    #[test]
+    fn missing_road_access_raises_risk() {
+        let served = conditions();
+        let isolated = LocalConditions {
+            road_access: false,
+            ..served
+        };
+
+        assert!(
+            isolated.risk_score(BuildingType::Residential)
+                > served.risk_score(BuildingType::Residential)
+        );
+    }
+
+    #[test]
+    fn pollution_only_bothers_non_industry() {
+        let clean = conditions();
+        let polluted = LocalConditions {
+            pollution: 3,
+            ..clean
+        };
+
+        assert!(
+            polluted.risk_score(BuildingType::Residential)
+                > clean.risk_score(BuildingType::Residential)
+        );
+        assert_eq!(
+            polluted.risk_score(BuildingType::Industry),
+            clean.risk_score(BuildingType::Industry)
+        );
+    }
+
+    #[test]
+    fn abandonment_waits_out_the_warning() {
+        let flag = AtRisk {
+            score: 0.9,
+            since_day: 10,
+        };
+
+        assert!(!flag.warning_served(10));
+        assert!(!flag.warning_served(10 + AT_RISK_WARNING_DAYS - 1));
+        assert!(flag.warning_served(10 + AT_RISK_WARNING_DAYS));
+    }
+
+    #[test]
+    fn survey_measures_commute_and_pollution() {
+        let mut grid = SpatialGrid::default();
+        let home = PlacedBuilding {
+            building_type: BuildingType::Residential,
+            variant: 0,
+            tile_pos: TilePos { x: 10, y: 10 },
+            rotation: Rotation::North,
+            placed_day: 2,
+            placed_hour: 30,
+        };
+        grid.insert(home.tile_pos, Footprint::SINGLE, BuildingType::Residential);
+        grid.insert(
+            TilePos { x: 12, y: 11 },
+            Footprint::SINGLE,
+            BuildingType::Industry,
+        );
+        grid.insert(
+            TilePos { x: 10, y: 9 },
+            Footprint::SINGLE,
+            BuildingType::Road,
+        );
+
+        let survey = LocalConditions::survey(&home, &grid, 12);
+
+        assert!(survey.road_access);
+        assert_eq!(survey.commute_distance, Some(2));
+        assert_eq!(survey.pollution, 1);
+        assert_eq!(survey.entertainment, 0);
+        assert_eq!(survey.age_days, 10);
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::{TilePos, TilemapGridSize, TilemapSize, TilemapType};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::budget::BuildingType;
+use crate::map::tile_center_to_world;
+use crate::notifications::Notify;
+use crate::spatial::SpatialGrid;
+use crate::time::GameClock;
+
+use super::resources::ROAD_NEIGHBOR_RADIUS;
+
+const HIGHWAY_COLOR: Color = Color::srgb(0.35, 0.35, 0.4);
+const HIGHWAY_SPRITE_SIZE: f32 = 80.0;
+
+/// Highway entry points on the map edge. Roads that touch one link the
+/// city to the outside world for migrants and trade.
+#[derive(Resource, Debug, Default)]
+pub struct OutsideConnections {
+    pub points: Vec<TilePos>,
+    /// Goods trucks using the highways yesterday
+    pub truck_trips: i64,
+    /// Citizens who arrived or left by highway yesterday
+    pub migrant_trips: i64,
+}
+
+impl OutsideConnections {
+    /// One highway in the middle of every map edge
+    pub fn at_edge_midpoints(map_size: &TilemapSize) -> Self {
+        let (mid_x, mid_y) = (map_size.x / 2, map_size.y / 2);
+        Self {
+            points: vec![
+                TilePos { x: mid_x, y: 0 },
+                TilePos {
+                    x: mid_x,
+                    y: map_size.y - 1,
+                },
+                TilePos { x: 0, y: mid_y },
+                TilePos {
+                    x: map_size.x - 1,
+                    y: mid_y,
+                },
+            ],
+            ..default()
+        }
+    }
+
+    pub fn is_connection(&self, pos: &TilePos) -> bool {
+        self.points.contains(pos)
+    }
+
+    pub fn highway_trips(&self) -> i64 {
+        self.truck_trips + self.migrant_trips
+    }
+}
+
+/// Roads split into connected pieces, using 4-neighbour adjacency.
+/// Rebuilt once per day.
+#[derive(Resource, Debug, Default)]
+pub struct RoadNetwork {
+    component_of: HashMap<TilePos, usize>,
+    /// Components with a road next to a highway entry point
+    outside: HashSet<usize>,
+}
+
+impl RoadNetwork {
+    pub fn build(spatial_grid: &SpatialGrid, connections: &OutsideConnections) -> Self {
+        let mut network = Self::default();
+        let mut next_component = 0;
+
+        for start in spatial_grid.buildings_of_type(BuildingType::Road) {
+            if network.component_of.contains_key(&start) {
+                continue;
+            }
+
+            let component = next_component;
+            next_component += 1;
+
+            let mut queue = VecDeque::from([start]);
+            network.component_of.insert(start, component);
+
+            while let Some(pos) = queue.pop_front() {
+                for neighbor in road_neighbors(&pos) {
+                    if connections.is_connection(&neighbor) {
+                        network.outside.insert(component);
+                    } else if spatial_grid.has_road_at(&neighbor)
+                        && !network.component_of.contains_key(&neighbor)
+                    {
+                        network.component_of.insert(neighbor, component);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        network
+    }
+
+    /// The network piece a building at `pos` loads onto: the one with the nearest road
+    pub fn component_near(&self, pos: &TilePos, spatial_grid: &SpatialGrid) -> Option<usize> {
+        spatial_grid
+            .buildings_of_type_in_radius(pos, ROAD_NEIGHBOR_RADIUS, BuildingType::Road)
+            .into_iter()
+            .min_by_key(|road| {
+                let dx = (road.x as i32 - pos.x as i32).abs();
+                let dy = (road.y as i32 - pos.y as i32).abs();
+                (dx.max(dy), road.x, road.y)
+            })
+            .and_then(|road| self.component_of.get(&road).copied())
+    }
+
+    pub fn reaches_outside(&self, component: usize) -> bool {
+        self.outside.contains(&component)
+    }
+
+    /// Whether any road links the city to a highway
+    pub fn is_connected(&self) -> bool {
+        !self.outside.is_empty()
+    }
+}
+
+fn road_neighbors(pos: &TilePos) -> impl Iterator<Item = TilePos> {
+    let (x, y) = (pos.x as i64, pos.y as i64);
+    [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+        .into_iter()
+        .filter(|(x, y)| *x >= 0 && *y >= 0)
+        .map(|(x, y)| TilePos {
+            x: x as u32,
+            y: y as u32,
+        })
+}
+
+/// Once per in‑game day, work out which roads reach a highway and remind the
+/// player when homes are waiting for one
+pub fn update_road_network(
+    clock: Res<GameClock>,
+    spatial_grid: Res<SpatialGrid>,
+    connections: Res<OutsideConnections>,
+    mut network: ResMut<RoadNetwork>,
+    mut notify_writer: MessageWriter<Notify>,
+    mut last_processed_day: Local<u32>,
+    mut warned: Local<bool>,
+) {
+    if *last_processed_day == clock.day {
+        return;
+    }
+    *last_processed_day = clock.day;
+
+    *network = RoadNetwork::build(&spatial_grid, &connections);
+
+    if network.is_connected() {
+        *warned = false;
+    } else if !*warned
+        && !spatial_grid
+            .buildings_of_type(BuildingType::Residential)
+            .is_empty()
+    {
+        *warned = true;
+        let mut notify = Notify::warning(
+            "Nobody can move in until a road reaches a highway (HWY) at the map edge",
+        );
+        if let Some(point) = connections.points.first() {
+            notify = notify.at(*point);
+        }
+        notify_writer.write(notify);
+    }
+}
+
+/// Sign on a highway entry point
+#[derive(Component)]
+pub struct HighwayMarker;
+
+/// Draw a marker on every highway entry point once a new map is built
+pub fn spawn_highway_markers(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    connections: Res<OutsideConnections>,
+    tilemap_q: Query<
+        (&TilemapSize, &TilemapGridSize, &TilemapType, &Transform),
+        Added<TilemapSize>,
+    >,
+) {
+    let Some((map_size, grid_size, map_type, map_transform)) = tilemap_q.iter().next() else {
+        return;
+    };
+
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    for point in &connections.points {
+        let world_pos = tile_center_to_world(point, map_size, grid_size, map_type, map_transform);
+
+        commands
+            .spawn((
+                Sprite::from_color(HIGHWAY_COLOR, Vec2::splat(HIGHWAY_SPRITE_SIZE)),
+                Transform::from_xyz(world_pos.x, world_pos.y, 5.0),
+                HighwayMarker,
+            ))
+            .with_child((
+                Text2d::new("HWY"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Transform::from_xyz(0.0, 0.0, 1.0),
+            ));
+    }
+}
+
+pub fn clear_highway_markers(
+    mut commands: Commands,
+    markers_q: Query<Entity, With<HighwayMarker>>,
+) {
+    for entity in markers_q.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::Footprint;
+
+    fn tile(x: u32, y: u32) -> TilePos {
+        TilePos { x, y }
+    }
+
+    #[test]
+    fn highways_sit_in_the_middle_of_each_edge() {
+        let connections = OutsideConnections::at_edge_midpoints(&TilemapSize { x: 32, y: 32 });
+
+        assert_eq!(connections.points.len(), 4);
+        assert!(connections.is_connection(&tile(16, 0)));
+        assert!(connections.is_connection(&tile(31, 16)));
+    }
+
+    #[test]
+    fn only_roads_touching_a_highway_reach_outside() {
+        let connections = OutsideConnections {
+            points: vec![tile(16, 0)],
+            ..default()
+        };
+        let mut grid = SpatialGrid::default();
+        for y in 1..4 {
+            grid.insert(tile(16, y), Footprint::SINGLE, BuildingType::Road);
+        }
+        grid.insert(tile(10, 10), Footprint::SINGLE, BuildingType::Road);
+        grid.insert(tile(11, 11), Footprint::SINGLE, BuildingType::Road);
+
+        let network = RoadNetwork::build(&grid, &connections);
+
+        let linked = network.component_of[&tile(16, 3)];
+        assert_eq!(network.component_of[&tile(16, 1)], linked);
+        assert!(network.reaches_outside(linked));
+        assert!(network.is_connected());
+        // diagonal roads don't connect
+        assert_ne!(
+            network.component_of[&tile(10, 10)],
+            network.component_of[&tile(11, 11)]
+        );
+        assert!(!network.reaches_outside(network.component_of[&tile(10, 10)]));
+    }
+}
@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+
+use crate::time::GameClock;
+
+use super::resources::{CityInfrastructure, CityPopulation, Demographics};
+
+/// How new arrivals (and the very first citizens) split across age groups
+const MIGRANT_CHILD_SHARE: f32 = 0.2;
+const MIGRANT_WORKER_SHARE: f32 = 0.7;
+const MIGRANT_RETIREE_SHARE: f32 = 0.1;
+/// Share of working-age migrants who arrive with schooling
+const MIGRANT_EDUCATION: f32 = 0.3;
+
+/// Daily births per worker at neutral happiness
+const BIRTH_RATE: f32 = 0.004;
+/// Children come of age after about two months, workers retire after eight
+const DAYS_AS_CHILD: f32 = 60.0;
+const DAYS_AS_WORKER: f32 = 240.0;
+/// Daily death rates at perfect health
+const RETIREE_DEATH_RATE: f32 = 0.01;
+const WORKER_DEATH_RATE: f32 = 0.0005;
+
+/// Children a single school can teach at once
+pub const SCHOOL_SEATS: f32 = 40.0;
+/// Health lost when every building next to homes is a factory
+const MAX_POLLUTION_HEALTH_PENALTY: f32 = 0.3;
+
+/// City-wide inputs for one day of demographic change
+#[derive(Debug, Clone, Copy)]
+pub struct DailyConditions {
+    pub happiness: f32,
+    pub school_seats: f32,
+    /// Industry share of homes plus factories, 0.0 to 1.0
+    pub pollution: f32,
+}
+
+/// Advance the cohorts by one day. `population` is the head count after
+/// migration; the new total including births and deaths is returned.
+pub fn advance_day(
+    demographics: &mut Demographics,
+    population: i64,
+    conditions: DailyConditions,
+) -> i64 {
+    apply_migration(demographics, population - demographics.total());
+
+    let target_health = (0.8 + 0.2 * conditions.happiness
+        - MAX_POLLUTION_HEALTH_PENALTY * conditions.pollution)
+        .clamp(0.0, 1.0);
+    demographics.health += (target_health - demographics.health) * 0.2;
+
+    let births = demographics.workers * BIRTH_RATE * (0.5 + conditions.happiness);
+
+    let graduates = demographics.children / DAYS_AS_CHILD;
+    let retiring = demographics.workers / DAYS_AS_WORKER;
+    let schooled_share = if demographics.children > 0.0 {
+        (conditions.school_seats / demographics.children).min(1.0)
+    } else {
+        0.0
+    };
+
+    let frailty = 1.5 - demographics.health;
+    let retiree_deaths = demographics.retirees * RETIREE_DEATH_RATE * frailty;
+    let worker_deaths = demographics.workers * WORKER_DEATH_RATE * frailty;
+
+    let remaining_workers = (demographics.workers - retiring - worker_deaths).max(0.0);
+    let new_workers = remaining_workers + graduates;
+    if new_workers > 0.0 {
+        demographics.education =
+            (demographics.education * remaining_workers + schooled_share * graduates) / new_workers;
+    }
+
+    demographics.children += births - graduates;
+    demographics.workers = new_workers;
+    demographics.retirees = (demographics.retirees + retiring - retiree_deaths).max(0.0);
+
+    demographics.total()
+}
+
+/// Spread arrivals across age groups, or remove leavers proportionally
+fn apply_migration(demographics: &mut Demographics, delta: i64) {
+    if delta > 0 {
+        let arrivals = delta as f32;
+        let new_workers = arrivals * MIGRANT_WORKER_SHARE;
+        demographics.education = (demographics.education * demographics.workers
+            + MIGRANT_EDUCATION * new_workers)
+            / (demographics.workers + new_workers);
+
+        demographics.children += arrivals * MIGRANT_CHILD_SHARE;
+        demographics.workers += new_workers;
+        demographics.retirees += arrivals * MIGRANT_RETIREE_SHARE;
+    } else if delta < 0 {
+        let total = demographics.children + demographics.workers + demographics.retirees;
+        let keep = if total > 0.0 {
+            ((total + delta as f32) / total).max(0.0)
+        } else {
+            0.0
+        };
+
+        demographics.children *= keep;
+        demographics.workers *= keep;
+        demographics.retirees *= keep;
+    }
+}
+
+/// Once per in‑game day, after migration, age the population and apply births and deaths
+pub fn update_demographics(
+    clock: Res<GameClock>,
+    infra: Res<CityInfrastructure>,
+    mut population: ResMut<CityPopulation>,
+    mut demographics: ResMut<Demographics>,
+    mut last_processed_day: Local<u32>,
+) {
+    if *last_processed_day == clock.day {
+        return;
+    }
+    *last_processed_day = clock.day;
+
+    let polluting = infra.industry_count.max(0) as f32;
+    let exposed = polluting + infra.residential_count.max(0) as f32;
+    let conditions = DailyConditions {
+        happiness: population.happiness,
+        school_seats: infra.school_count.max(0) as f32 * SCHOOL_SEATS,
+        pollution: if exposed > 0.0 {
+            polluting / exposed
+        } else {
+            0.0
+        },
+    };
+
+    let old_pop = population.population.max(0);
+    let new_pop = advance_day(&mut demographics, old_pop, conditions);
+
+    if new_pop != old_pop {
+        info!(
+            "Births and deaths changed population from {} to {} (children {:.0}, workers {:.0}, retirees {:.0}, education {:.2}, health {:.2})",
+            old_pop,
+            new_pop,
+            demographics.children,
+            demographics.workers,
+            demographics.retirees,
+            demographics.education,
+            demographics.health
+        );
+    }
+    population.population = new_pop;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditions() -> DailyConditions {
+        DailyConditions {
+            happiness: 0.8,
+            school_seats: 0.0,
+            pollution: 0.0,
+        }
+    }
+
+    /// One day with no migration
+    fn live_day(demographics: &mut Demographics, conditions: DailyConditions) {
+        let population = demographics.total();
+        advance_day(demographics, population, conditions);
+    }
+
+    #[test]
+    fn arrivals_are_split_across_age_groups() {
+        let mut demographics = Demographics::default();
+
+        apply_migration(&mut demographics, 100);
+
+        assert_eq!(demographics.total(), 100);
+        assert!(demographics.workers > demographics.children);
+        assert!(demographics.children > demographics.retirees);
+    }
+
+    #[test]
+    fn leavers_are_removed_proportionally() {
+        let mut demographics = Demographics::default();
+        apply_migration(&mut demographics, 100);
+
+        apply_migration(&mut demographics, -50);
+
+        assert_eq!(demographics.total(), 50);
+        assert!((demographics.workers - 35.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn schools_raise_education() {
+        let mut unschooled = Demographics::default();
+        apply_migration(&mut unschooled, 200);
+        let mut schooled = unschooled.clone();
+
+        for _ in 0..60 {
+            live_day(&mut unschooled, conditions());
+            live_day(
+                &mut schooled,
+                DailyConditions {
+                    school_seats: SCHOOL_SEATS,
+                    ..conditions()
+                },
+            );
+        }
+
+        assert!(schooled.education > unschooled.education);
+        assert!(schooled.workforce() > unschooled.workforce());
+    }
+
+    #[test]
+    fn pollution_lowers_health_and_raises_deaths() {
+        let mut clean = Demographics::default();
+        apply_migration(&mut clean, 500);
+        let mut polluted = clean.clone();
+
+        for _ in 0..30 {
+            live_day(&mut clean, conditions());
+            live_day(
+                &mut polluted,
+                DailyConditions {
+                    pollution: 1.0,
+                    ..conditions()
+                },
+            );
+        }
+
+        assert!(polluted.health < clean.health);
+        assert!(polluted.retirees < clean.retirees);
+    }
+}
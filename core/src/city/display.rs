@@ -1,27 +1,36 @@
 use bevy::prelude::*;
 
-use super::resources::{CityPopulation, CityServices};
+use super::abandonment::AtRisk;
+use super::goods::GoodsFlow;
+use super::rci::RciDemand;
+use super::resources::{CityPopulation, CityServices, Demographics};
+use crate::budget::{TAX_RATE_STEP, TaxRates, TaxSector};
+use crate::camera::MINIMAP_SIZE;
+use crate::map::UiClickBlocker;
 
 #[derive(Component)]
 pub enum CityStatKind {
     Pop,
+    AgeGroups,
+    Education,
     Housing,
     Jobs,
     Happy,
     HousingDemand,
     JobDemand,
     EntertainmentDemand,
+    GoodsDemand,
 }
 
 pub fn setup_city_stats_display(mut commands: Commands, asset_server: Res<AssetServer>) {
     let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
 
-    // Root container: two cards laid out horizontally
+    // Root container: two cards laid out horizontally, right of the minimap
     commands
         .spawn((Node {
             position_type: PositionType::Absolute,
             bottom: Val::Px(10.0),
-            left: Val::Px(10.0),
+            left: Val::Px(MINIMAP_SIZE + 26.0),
             flex_direction: FlexDirection::Row,
             column_gap: Val::Px(16.0),
             ..default()
@@ -53,6 +62,8 @@ pub fn setup_city_stats_display(mut commands: Commands, asset_server: Res<AssetS
                     // Stats as a vertical column
                     for (label, kind) in [
                         ("Population: 0", CityStatKind::Pop),
+                        ("Kids/Work/Old: 0/0/0", CityStatKind::AgeGroups),
+                        ("Educated: 30%", CityStatKind::Education),
                         ("Housing: 0", CityStatKind::Housing),
                         ("Jobs: 0", CityStatKind::Jobs),
                         ("Happiness: 1.00", CityStatKind::Happy),
@@ -97,6 +108,7 @@ pub fn setup_city_stats_display(mut commands: Commands, asset_server: Res<AssetS
                         ("Housing: 0", CityStatKind::HousingDemand),
                         ("Jobs: 0", CityStatKind::JobDemand),
                         ("Entertainment: 0", CityStatKind::EntertainmentDemand),
+                        ("Goods: 0", CityStatKind::GoodsDemand),
                     ] {
                         card.spawn((
                             Text::new(label),
@@ -110,15 +122,131 @@ pub fn setup_city_stats_display(mut commands: Commands, asset_server: Res<AssetS
                         ));
                     }
                 });
+
+            // Zone demand meter
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(8.0)),
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::End,
+                        column_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|card| {
+                    for (label, zone) in [
+                        ("R", RciZone::Residential),
+                        ("C", RciZone::Commercial),
+                        ("I", RciZone::Industrial),
+                    ] {
+                        card.spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(4.0),
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column
+                                .spawn((
+                                    Node {
+                                        width: Val::Px(RCI_BAR_WIDTH),
+                                        height: Val::Px(RCI_BAR_HALF_HEIGHT * 2.0),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.1)),
+                                ))
+                                .with_children(|track| {
+                                    track.spawn((
+                                        Node {
+                                            position_type: PositionType::Absolute,
+                                            width: Val::Percent(100.0),
+                                            top: Val::Px(RCI_BAR_HALF_HEIGHT),
+                                            height: Val::Px(0.0),
+                                            ..default()
+                                        },
+                                        BackgroundColor(zone.color()),
+                                        RciBar(zone),
+                                    ));
+                                });
+
+                            column.spawn((
+                                Text::new(label),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(zone.color()),
+                            ));
+
+                            column.spawn((
+                                Text::new(tax_label(&TaxRates::default(), zone)),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 12.0,
+                                    ..default()
+                                },
+                                TextColor(Color::WHITE),
+                                TaxRateLabel(zone),
+                            ));
+
+                            column
+                                .spawn(Node {
+                                    flex_direction: FlexDirection::Row,
+                                    column_gap: Val::Px(2.0),
+                                    ..default()
+                                })
+                                .with_children(|buttons| {
+                                    for (sign, delta) in
+                                        [("-", -TAX_RATE_STEP), ("+", TAX_RATE_STEP)]
+                                    {
+                                        buttons
+                                            .spawn((
+                                                Button,
+                                                Node {
+                                                    padding: UiRect::axes(
+                                                        Val::Px(4.0),
+                                                        Val::Px(1.0),
+                                                    ),
+                                                    justify_content: JustifyContent::Center,
+                                                    ..default()
+                                                },
+                                                BackgroundColor(Color::srgba(0.8, 0.8, 0.8, 1.0)),
+                                                TaxButton { zone, delta },
+                                            ))
+                                            .with_children(|button| {
+                                                button.spawn((
+                                                    Text::new(sign),
+                                                    TextFont {
+                                                        font: font.clone(),
+                                                        font_size: 12.0,
+                                                        ..default()
+                                                    },
+                                                    TextColor(Color::BLACK),
+                                                ));
+                                            });
+                                    }
+                                });
+                        });
+                    }
+                });
         });
 }
 
 pub fn update_city_stats_display(
     population: Res<CityPopulation>,
     services: Res<CityServices>,
+    demographics: Res<Demographics>,
+    goods: Res<GoodsFlow>,
     mut stats_q: Query<(&mut Text, &mut TextColor, &CityStatKind)>,
 ) {
-    if !population.is_changed() && !services.is_changed() {
+    if !population.is_changed()
+        && !services.is_changed()
+        && !demographics.is_changed()
+        && !goods.is_changed()
+    {
         return;
     }
 
@@ -129,6 +257,15 @@ pub fn update_city_stats_display(
             CityStatKind::Pop => {
                 text.0 = format!("Population: {}", population.population);
             }
+            CityStatKind::AgeGroups => {
+                text.0 = format!(
+                    "Kids/Work/Old: {:.0}/{:.0}/{:.0}",
+                    demographics.children, demographics.workers, demographics.retirees
+                );
+            }
+            CityStatKind::Education => {
+                text.0 = format!("Educated: {:.0}%", demographics.education * 100.0);
+            }
             CityStatKind::Housing => {
                 text.0 = format!("Housing: {}", services.housing_capacity);
             }
@@ -174,6 +311,156 @@ pub fn update_city_stats_display(
                     TextColor(Color::WHITE)
                 };
             }
+            CityStatKind::GoodsDemand => {
+                let missing = (goods.needed - goods.delivered - goods.imported).max(0.0);
+                text.0 = format!("Goods: {:.0}", missing);
+                *color = if goods.shop_supply_ratio() < 0.5 {
+                    TextColor(Color::srgb(1.0, 0.3, 0.3))
+                } else if goods.shop_supply_ratio() < 0.9 {
+                    TextColor(Color::srgb(1.0, 0.8, 0.3))
+                } else {
+                    TextColor(Color::WHITE)
+                };
+            }
         }
     }
 }
+
+/// Height of an RCI bar at full demand in either direction
+const RCI_BAR_HALF_HEIGHT: f32 = 40.0;
+const RCI_BAR_WIDTH: f32 = 14.0;
+
+#[derive(Clone, Copy)]
+pub enum RciZone {
+    Residential,
+    Commercial,
+    Industrial,
+}
+
+impl RciZone {
+    fn color(&self) -> Color {
+        match self {
+            RciZone::Residential => Color::srgb(0.3, 0.85, 0.3),
+            RciZone::Commercial => Color::srgb(0.3, 0.55, 1.0),
+            RciZone::Industrial => Color::srgb(1.0, 0.8, 0.2),
+        }
+    }
+
+    fn demand(&self, demand: &RciDemand) -> f32 {
+        match self {
+            RciZone::Residential => demand.residential,
+            RciZone::Commercial => demand.commercial,
+            RciZone::Industrial => demand.industrial,
+        }
+    }
+
+    fn tax_sector(&self) -> TaxSector {
+        match self {
+            RciZone::Residential => TaxSector::Residential,
+            RciZone::Commercial => TaxSector::Commercial,
+            RciZone::Industrial => TaxSector::Industrial,
+        }
+    }
+}
+
+/// Bar in the RCI meter; grows up from the middle line for positive demand
+/// and down for negative demand
+#[derive(Component)]
+pub struct RciBar(RciZone);
+
+pub fn update_rci_meter(demand: Res<RciDemand>, mut bars_q: Query<(&mut Node, &RciBar)>) {
+    if !demand.is_changed() {
+        return;
+    }
+
+    for (mut node, bar) in bars_q.iter_mut() {
+        let value = bar.0.demand(&demand).clamp(-1.0, 1.0);
+        let height = value.abs() * RCI_BAR_HALF_HEIGHT;
+
+        node.height = Val::Px(height);
+        node.top = if value >= 0.0 {
+            Val::Px(RCI_BAR_HALF_HEIGHT - height)
+        } else {
+            Val::Px(RCI_BAR_HALF_HEIGHT)
+        };
+    }
+}
+
+/// Tax rate shown under a zone's RCI bar
+#[derive(Component)]
+pub struct TaxRateLabel(RciZone);
+
+/// Raises or lowers a zone's tax rate by `delta` when clicked
+#[derive(Component)]
+pub struct TaxButton {
+    zone: RciZone,
+    delta: f32,
+}
+
+fn tax_label(taxes: &TaxRates, zone: RciZone) -> String {
+    format!("{:.0}%", taxes.rate(zone.tax_sector()) * 100.0)
+}
+
+pub fn handle_tax_buttons(
+    interaction_q: Query<(&Interaction, &TaxButton), Changed<Interaction>>,
+    mut taxes: ResMut<TaxRates>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    for (interaction, button) in interaction_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        ui_click_blocker.just_clicked_ui = true;
+        taxes.adjust(button.zone.tax_sector(), button.delta);
+        info!("Tax rates now {:?}", *taxes);
+    }
+}
+
+pub fn update_tax_labels(taxes: Res<TaxRates>, mut labels_q: Query<(&mut Text, &TaxRateLabel)>) {
+    if !taxes.is_changed() {
+        return;
+    }
+
+    for (mut text, label) in labels_q.iter_mut() {
+        text.0 = tax_label(&taxes, label.0);
+    }
+}
+
+const AT_RISK_ICON_COLOR: Color = Color::srgb(1.0, 0.8, 0.1);
+/// Height above the building centre where the warning icon floats
+const AT_RISK_ICON_OFFSET: f32 = 36.0;
+
+#[derive(Component)]
+pub struct AtRiskIcon;
+
+/// Float a "!" over buildings flagged `AtRisk` and drop it once the flag goes
+pub fn update_at_risk_icons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    flagged_q: Query<Entity, Added<AtRisk>>,
+    mut unflagged: RemovedComponents<AtRisk>,
+    icons_q: Query<(Entity, &ChildOf), With<AtRiskIcon>>,
+) {
+    for building in unflagged.read() {
+        for (icon, child_of) in icons_q.iter() {
+            if child_of.parent() == building {
+                commands.entity(icon).despawn();
+            }
+        }
+    }
+
+    for building in flagged_q.iter() {
+        commands.entity(building).with_child((
+            Text2d::new("!"),
+            TextFont {
+                font: asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf"),
+                font_size: 28.0,
+                ..default()
+            },
+            TextColor(AT_RISK_ICON_COLOR),
+            Transform::from_xyz(0.0, AT_RISK_ICON_OFFSET, 1.0),
+            AtRiskIcon,
+        ));
+    }
+}
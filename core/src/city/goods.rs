@@ -0,0 +1,197 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::budget::{Budget, BudgetLedger, BuildingType, LedgerCategory};
+use crate::spatial::SpatialGrid;
+use crate::time::GameClock;
+
+use super::connections::{OutsideConnections, RoadNetwork};
+use super::resources::{CityServices, Demographics};
+
+/// Goods a fully staffed factory makes per day
+pub const GOODS_PER_FACTORY: f32 = 15.0;
+/// Goods a shop sells per day when it is fully stocked
+pub const GOODS_PER_SHOP: f32 = 15.0;
+
+/// Export duty the city collects per good shipped out of town
+const EXPORT_DUTY_PER_GOOD: f32 = 1.0;
+/// Price the city pays per good brought in to cover a shortage
+const IMPORT_PRICE_PER_GOOD: f32 = 3.0;
+/// Goods one truck carries through a highway
+const GOODS_PER_TRUCK: f32 = 5.0;
+
+/// Yesterday's movement of goods from factories to shops
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct GoodsFlow {
+    pub produced: f32,
+    pub needed: f32,
+    /// Goods trucked from a factory to a shop on the same road network
+    pub delivered: f32,
+    pub exported: f32,
+    pub imported: f32,
+}
+
+impl GoodsFlow {
+    /// How well stocked the shops were, 0.0 to 1.0
+    pub fn shop_supply_ratio(&self) -> f32 {
+        if self.needed > 0.0 {
+            ((self.delivered + self.imported) / self.needed).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Share of factory output that found a buyer, 0.0 to 1.0
+    pub fn factory_sales_ratio(&self) -> f32 {
+        if self.produced > 0.0 {
+            ((self.delivered + self.exported) / self.produced).min(1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Match factory output with shop demand on each road network piece.
+/// Leftovers are exported and gaps imported where the piece reaches a highway;
+/// buildings without road access neither ship nor receive anything.
+pub fn route_goods(
+    factories: &[(Option<usize>, f32)],
+    shops: &[(Option<usize>, f32)],
+    reaches_outside: impl Fn(usize) -> bool,
+) -> GoodsFlow {
+    let mut flow = GoodsFlow::default();
+    let mut per_component: HashMap<usize, (f32, f32)> = HashMap::new();
+
+    for (component, output) in factories {
+        flow.produced += output;
+        if let Some(component) = component {
+            per_component.entry(*component).or_default().0 += output;
+        }
+    }
+    for (component, need) in shops {
+        flow.needed += need;
+        if let Some(component) = component {
+            per_component.entry(*component).or_default().1 += need;
+        }
+    }
+
+    for (component, (supply, need)) in per_component {
+        let delivered = supply.min(need);
+        flow.delivered += delivered;
+
+        if reaches_outside(component) {
+            flow.exported += supply - delivered;
+            flow.imported += need - delivered;
+        }
+    }
+
+    flow
+}
+
+#[derive(SystemParam)]
+pub struct GoodsInputs<'w> {
+    clock: Res<'w, GameClock>,
+    services: Res<'w, CityServices>,
+    demographics: Res<'w, Demographics>,
+    spatial_grid: Res<'w, SpatialGrid>,
+    network: Res<'w, RoadNetwork>,
+    connections: ResMut<'w, OutsideConnections>,
+    flow: ResMut<'w, GoodsFlow>,
+    budget: ResMut<'w, Budget>,
+    ledger: ResMut<'w, BudgetLedger>,
+}
+
+/// Once per in‑game day, produce goods in factories, truck them to shops over the
+/// roads and trade the difference with the outside world
+pub fn update_goods_on_day_tick(mut inputs: GoodsInputs, mut last_processed_day: Local<u32>) {
+    let day = inputs.clock.day;
+    if *last_processed_day == day {
+        return;
+    }
+    *last_processed_day = day;
+
+    // factories run at the same staffing level the income tick assumes
+    let jobs = inputs.services.job_capacity.max(0);
+    let staffing = if jobs > 0 {
+        inputs.demographics.workforce().min(jobs) as f32 / jobs as f32
+    } else {
+        0.0
+    };
+
+    let attach = |building_type: BuildingType, amount: f32| -> Vec<(Option<usize>, f32)> {
+        inputs
+            .spatial_grid
+            .buildings_of_type(building_type)
+            .iter()
+            .map(|pos| {
+                let component = inputs.network.component_near(pos, &inputs.spatial_grid);
+                (component, amount)
+            })
+            .collect()
+    };
+    let factories = attach(BuildingType::Industry, GOODS_PER_FACTORY * staffing);
+    let shops = attach(BuildingType::Commercial, GOODS_PER_SHOP);
+
+    let flow = route_goods(&factories, &shops, |component| {
+        inputs.network.reaches_outside(component)
+    });
+
+    let export_duty = (flow.exported * EXPORT_DUTY_PER_GOOD).round() as i64;
+    let import_cost = (flow.imported * IMPORT_PRICE_PER_GOOD).round() as i64;
+
+    let GoodsInputs { budget, ledger, .. } = &mut inputs;
+    if export_duty > 0 {
+        ledger.book(budget, day, LedgerCategory::GoodsExports, export_duty);
+    }
+    if import_cost > 0 {
+        ledger.book(budget, day, LedgerCategory::GoodsImports, -import_cost);
+    }
+
+    if flow != *inputs.flow {
+        info!(
+            "Goods: produced {:.0}, needed {:.0}, delivered {:.0}, exported {:.0}, imported {:.0}",
+            flow.produced, flow.needed, flow.delivered, flow.exported, flow.imported
+        );
+    }
+    *inputs.flow = flow;
+    inputs.connections.truck_trips =
+        ((flow.exported + flow.imported) / GOODS_PER_TRUCK).ceil() as i64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goods_only_move_within_a_network() {
+        let flow = route_goods(&[(Some(0), 15.0)], &[(Some(1), 15.0)], |_| false);
+
+        assert_eq!(flow.delivered, 0.0);
+        assert_eq!(flow.shop_supply_ratio(), 0.0);
+        assert_eq!(flow.factory_sales_ratio(), 0.0);
+    }
+
+    #[test]
+    fn edge_networks_trade_surplus_and_shortage() {
+        let flow = route_goods(
+            &[(Some(0), 30.0), (Some(1), 5.0)],
+            &[(Some(0), 15.0), (Some(1), 15.0)],
+            |_| true,
+        );
+
+        assert_eq!(flow.delivered, 20.0);
+        assert_eq!(flow.exported, 15.0);
+        assert_eq!(flow.imported, 10.0);
+        assert_eq!(flow.shop_supply_ratio(), 1.0);
+        assert_eq!(flow.factory_sales_ratio(), 1.0);
+    }
+
+    #[test]
+    fn buildings_without_roads_are_cut_off() {
+        let flow = route_goods(&[(None, 15.0)], &[(None, 15.0)], |_| true);
+
+        assert_eq!(flow.produced, 15.0);
+        assert_eq!(flow.delivered + flow.exported + flow.imported, 0.0);
+    }
+}
@@ -1,5 +1,12 @@
+mod abandonment;
+mod connections;
+mod demographics;
 mod display;
+pub mod goods;
+pub mod rci;
 pub mod resources;
 mod systems;
 
+pub use abandonment::AtRisk;
+pub use connections::OutsideConnections;
 pub use systems::SimulationPlugin;
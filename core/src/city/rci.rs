@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+
+use crate::budget::{DEFAULT_TAX_RATE, TaxRates};
+
+use super::goods::GoodsFlow;
+use super::resources::{CityInfrastructure, CityPopulation, CityServices, Demographics};
+
+/// Shop jobs a city wants per citizen before commercial demand levels off
+const SHOP_JOBS_PER_CITIZEN: f32 = 0.15;
+/// Demand lost for every point of tax above the default rate
+const TAX_SENSITIVITY: f32 = 5.0;
+
+/// Residential, commercial and industrial zone demand, each from -1.0
+/// ("stop building this") to 1.0 ("build this now")
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct RciDemand {
+    pub residential: f32,
+    pub commercial: f32,
+    pub industrial: f32,
+}
+
+/// Everything the demand model looks at, gathered from the city resources
+#[derive(Debug, Clone, Copy)]
+pub struct RciInputs {
+    pub population: i64,
+    pub happiness: f32,
+    pub housing_capacity: i64,
+    pub workforce: i64,
+    pub commercial_jobs: i64,
+    pub industry_jobs: i64,
+    pub goods: GoodsFlow,
+    pub taxes: TaxRates,
+}
+
+impl RciInputs {
+    /// Share of the workforce without a job; negative when jobs go unfilled
+    pub fn unemployment(&self) -> f32 {
+        if self.workforce <= 0 {
+            return 0.0;
+        }
+        let jobs = (self.commercial_jobs + self.industry_jobs).max(0);
+        (self.workforce - jobs) as f32 / self.workforce as f32
+    }
+
+    /// From -1.0 (shops have no goods) to 1.0 (factories have no buyers)
+    pub fn goods_balance(&self) -> f32 {
+        let goods = &self.goods;
+        let unsold = goods.produced - goods.delivered - goods.exported;
+        let missing = goods.needed - goods.delivered - goods.imported;
+        let scale = goods.produced.max(goods.needed);
+        if scale > 0.0 {
+            ((unsold - missing) / scale).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+fn tax_penalty(rate: f32) -> f32 {
+    (rate - DEFAULT_TAX_RATE) * TAX_SENSITIVITY
+}
+
+pub fn rci_demand(inputs: &RciInputs) -> RciDemand {
+    let unemployment = inputs.unemployment().clamp(-1.0, 1.0);
+    let goods = inputs.goods_balance();
+
+    let vacancy = if inputs.housing_capacity > 0 {
+        (inputs.housing_capacity - inputs.population.max(0)) as f32 / inputs.housing_capacity as f32
+    } else {
+        // nowhere to live at all
+        -0.5
+    };
+    let residential = -0.8 * vacancy - 0.8 * unemployment + 0.6 * (inputs.happiness - 0.5)
+        - tax_penalty(inputs.taxes.residential);
+
+    let wanted_shop_jobs = inputs.population.max(0) as f32 * SHOP_JOBS_PER_CITIZEN;
+    let shop_gap = if wanted_shop_jobs > 0.0 {
+        (wanted_shop_jobs - inputs.commercial_jobs.max(0) as f32) / wanted_shop_jobs
+    } else {
+        0.0
+    };
+    let commercial = 0.6 * shop_gap.max(-1.0) + 0.4 * unemployment + 0.3 * goods.min(0.0)
+        - tax_penalty(inputs.taxes.commercial);
+
+    let industrial = 0.6 * unemployment - 0.5 * goods - tax_penalty(inputs.taxes.industrial);
+
+    RciDemand {
+        residential: residential.clamp(-1.0, 1.0),
+        commercial: commercial.clamp(-1.0, 1.0),
+        industrial: industrial.clamp(-1.0, 1.0),
+    }
+}
+
+pub fn update_rci_demand(
+    population: Res<CityPopulation>,
+    services: Res<CityServices>,
+    infra: Res<CityInfrastructure>,
+    goods: Res<GoodsFlow>,
+    demographics: Res<Demographics>,
+    taxes: Res<TaxRates>,
+    mut demand: ResMut<RciDemand>,
+) {
+    let next = rci_demand(&RciInputs {
+        population: population.population,
+        happiness: population.happiness,
+        housing_capacity: services.housing_capacity,
+        workforce: demographics.workforce(),
+        commercial_jobs: infra.commercial_job_capacity,
+        industry_jobs: infra.industry_job_capacity,
+        goods: *goods,
+        taxes: *taxes,
+    });
+
+    // avoid tripping change detection every frame
+    demand.set_if_neq(next);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::{TAX_RATE_STEP, TaxSector};
+
+    fn inputs() -> RciInputs {
+        RciInputs {
+            population: 100,
+            happiness: 0.8,
+            housing_capacity: 100,
+            workforce: 60,
+            commercial_jobs: 15,
+            industry_jobs: 45,
+            goods: GoodsFlow::default(),
+            taxes: TaxRates::default(),
+        }
+    }
+
+    #[test]
+    fn empty_city_wants_homes_first() {
+        let demand = rci_demand(&RciInputs {
+            population: 0,
+            happiness: 1.0,
+            housing_capacity: 0,
+            workforce: 0,
+            commercial_jobs: 0,
+            industry_jobs: 0,
+            goods: GoodsFlow::default(),
+            taxes: TaxRates::default(),
+        });
+
+        assert!(demand.residential > 0.5);
+        assert!(demand.residential > demand.commercial);
+        assert!(demand.residential > demand.industrial);
+    }
+
+    #[test]
+    fn unemployment_pushes_jobs_over_homes() {
+        let jobless = rci_demand(&RciInputs {
+            commercial_jobs: 5,
+            industry_jobs: 10,
+            ..inputs()
+        });
+        let balanced = rci_demand(&inputs());
+
+        assert!(jobless.commercial > balanced.commercial);
+        assert!(jobless.industrial > balanced.industrial);
+        assert!(jobless.residential < balanced.residential);
+    }
+
+    #[test]
+    fn goods_shortage_favours_industry() {
+        let short = rci_demand(&RciInputs {
+            goods: GoodsFlow {
+                produced: 15.0,
+                needed: 45.0,
+                delivered: 15.0,
+                ..GoodsFlow::default()
+            },
+            ..inputs()
+        });
+
+        assert!(short.industrial > short.commercial);
+    }
+
+    #[test]
+    fn higher_taxes_lower_demand() {
+        let taxed = rci_demand(&RciInputs {
+            taxes: TaxRates {
+                residential: 0.2,
+                ..TaxRates::default()
+            },
+            ..inputs()
+        });
+
+        assert!(taxed.residential < rci_demand(&inputs()).residential);
+    }
+
+    #[test]
+    fn tax_controls_move_demand() {
+        let mut taxes = TaxRates::default();
+        taxes.adjust(TaxSector::Industrial, 3.0 * TAX_RATE_STEP);
+        taxes.adjust(TaxSector::Commercial, -3.0 * TAX_RATE_STEP);
+        let adjusted = rci_demand(&RciInputs { taxes, ..inputs() });
+        let balanced = rci_demand(&inputs());
+
+        assert!(adjusted.industrial < balanced.industrial);
+        assert!(adjusted.commercial > balanced.commercial);
+        assert_eq!(adjusted.residential, balanced.residential);
+    }
+}
@@ -1,11 +1,14 @@
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::TilePos;
+use std::collections::HashMap;
 
 use crate::budget::{BuildingDemolished, BuildingPlaced, BuildingType};
 use crate::spatial::SpatialGrid;
 
 const RESIDENTIAL_NEIGHBOR_RADIUS: i32 = 3;
-const ROAD_NEIGHBOR_RADIUS: i32 = 4;
+pub const ROAD_NEIGHBOR_RADIUS: i32 = 4;
+const LAND_VALUE_RADIUS: i32 = 3;
+const DERELICT_LAND_VALUE_PENALTY: f32 = 0.25;
 
 #[derive(Resource, Debug)]
 pub struct CityPopulation {
@@ -24,6 +27,48 @@ impl Default for CityPopulation {
     }
 }
 
+/// Share of working-age citizens who can take a job without any schooling
+const UNSKILLED_WORKFORCE_SHARE: f32 = 0.6;
+
+/// Breakdown of `CityPopulation` into age groups. Cohorts are fractional so
+/// slow daily rates (births, aging) accumulate instead of rounding away.
+#[derive(Resource, Debug, Clone)]
+pub struct Demographics {
+    pub children: f32,
+    pub workers: f32,
+    pub retirees: f32,
+    /// Share of workers with enough schooling for skilled jobs, 0.0 to 1.0
+    pub education: f32,
+    /// from 0.0 as "everyone is sick" to 1.0 as "everyone is healthy"
+    pub health: f32,
+}
+
+impl Default for Demographics {
+    fn default() -> Self {
+        Self {
+            children: 0.0,
+            workers: 0.0,
+            retirees: 0.0,
+            education: 0.3,
+            health: 1.0,
+        }
+    }
+}
+
+impl Demographics {
+    pub fn total(&self) -> i64 {
+        (self.children + self.workers + self.retirees).round() as i64
+    }
+
+    /// Working-age citizens able to fill a job; uneducated workers only
+    /// qualify for part of the market
+    pub fn workforce(&self) -> i64 {
+        let qualified =
+            UNSKILLED_WORKFORCE_SHARE + (1.0 - UNSKILLED_WORKFORCE_SHARE) * self.education;
+        (self.workers * qualified).round() as i64
+    }
+}
+
 /// Describes how much housing, jobs and entertainment the city provides
 #[derive(Resource, Debug, Default)]
 pub struct CityServices {
@@ -44,17 +89,59 @@ pub struct CityInfrastructure {
     pub industry_count: i64,
     pub road_count: i64,
     pub decorative_count: i64,
+    pub bus_stop_count: i64,
+    pub school_count: i64,
+    pub stadium_count: i64,
 
     pub industry_job_capacity: i64,
     pub commercial_job_capacity: i64,
 }
 
+impl CityServices {
+    /// Add what one building provides (`change` 1) or take it away again (`change` -1)
+    pub fn count_building(&mut self, building_type: BuildingType, change: i64) {
+        let contrib = building_contribution(building_type);
+        self.housing_capacity += change * contrib.housing;
+        self.job_capacity += change * contrib.jobs;
+        self.entertainment_capacity += change * contrib.entertainment;
+    }
+}
+
+impl CityInfrastructure {
+    /// Count one building in (`change` 1) or out (`change` -1) of the city
+    pub fn count_building(&mut self, building_type: BuildingType, change: i64) {
+        let contrib = building_contribution(building_type);
+        match building_type {
+            BuildingType::Residential => self.residential_count += change,
+            BuildingType::Commercial => {
+                self.commercial_count += change;
+                self.commercial_job_capacity += change * contrib.jobs;
+            }
+            BuildingType::Industry => {
+                self.industry_count += change;
+                self.industry_job_capacity += change * contrib.jobs;
+            }
+            BuildingType::Road => self.road_count += change,
+            BuildingType::Decorative => self.decorative_count += change,
+            BuildingType::BusStop => self.bus_stop_count += change,
+            BuildingType::School => self.school_count += change,
+            BuildingType::Stadium => self.stadium_count += change,
+        }
+    }
+}
+
 pub struct BuildingContribution {
     pub housing: i64,
     pub jobs: i64,
     pub entertainment: i64,
 }
 
+/// Happiness change each building caused when it was placed, keyed by tile
+#[derive(Resource, Debug, Default)]
+pub struct PlacementImpacts {
+    pub by_tile: HashMap<TilePos, f32>,
+}
+
 pub fn building_contribution(building_type: BuildingType) -> BuildingContribution {
     match building_type {
         BuildingType::Residential => BuildingContribution {
@@ -82,20 +169,56 @@ pub fn building_contribution(building_type: BuildingType) -> BuildingContributio
             jobs: 0,
             entertainment: 20,
         },
+        BuildingType::BusStop => BuildingContribution {
+            housing: 0,
+            jobs: 0,
+            entertainment: 0,
+        },
+        BuildingType::School => BuildingContribution {
+            housing: 0,
+            jobs: 4,
+            entertainment: 0,
+        },
+        BuildingType::Stadium => BuildingContribution {
+            housing: 0,
+            jobs: 12,
+            entertainment: 150,
+        },
     }
 }
 
-fn count_nearby_residential(center: &TilePos, spatial_grid: &SpatialGrid) -> u32 {
+pub fn count_nearby_residential(center: &TilePos, spatial_grid: &SpatialGrid) -> u32 {
     spatial_grid.count_residential_in_radius(center, RESIDENTIAL_NEIGHBOR_RADIUS)
 }
 
+/// Relative desirability of a tile, 1.0 being an ordinary lot. Road access and
+/// neighbours raise it, derelict lots nearby drag it down.
+pub fn land_value(center: &TilePos, spatial_grid: &SpatialGrid) -> f32 {
+    let mut value = 1.0;
+
+    if is_accessible(center, spatial_grid) {
+        value += 0.2;
+    }
+
+    let neighbours = spatial_grid.count_residential_in_radius(center, LAND_VALUE_RADIUS);
+    value += 0.05 * neighbours.min(8) as f32;
+
+    let derelicts = spatial_grid.count_derelicts_in_radius(center, LAND_VALUE_RADIUS);
+    value -= DERELICT_LAND_VALUE_PENALTY * derelicts as f32;
+
+    value.clamp(0.0, 2.0)
+}
+
 pub fn apply_demolition_happiness(
     mut population: ResMut<CityPopulation>,
     services: Res<CityServices>,
     spatial_grid: Res<SpatialGrid>,
+    mut impacts: ResMut<PlacementImpacts>,
     mut demolished_reader: MessageReader<BuildingDemolished>,
 ) {
     for event in demolished_reader.read() {
+        impacts.by_tile.remove(&event.tile_pos);
+
         let nearby_residential = count_nearby_residential(&event.tile_pos, &spatial_grid);
 
         let pop = population.population.max(1) as f32;
@@ -119,6 +242,9 @@ pub fn apply_demolition_happiness(
             }
             BuildingType::Road => 0.0,
             BuildingType::Decorative => 0.0,
+            BuildingType::BusStop => 0.0,
+            BuildingType::School => -0.01 * nearby_residential as f32,
+            BuildingType::Stadium => -0.01 * nearby_residential as f32,
         };
 
         delta = delta.clamp(-0.05, 0.05);
@@ -134,59 +260,85 @@ pub fn apply_demolition_happiness(
     }
 }
 
-fn is_accessible(center: &TilePos, spatial_grid: &SpatialGrid) -> bool {
+pub fn is_accessible(center: &TilePos, spatial_grid: &SpatialGrid) -> bool {
     spatial_grid.has_road_in_radius(center, ROAD_NEIGHBOR_RADIUS)
 }
 
+/// Happiness change placing `building_type` at `tile_pos` causes, given the
+/// current city state. Shared by the placement system and the hover tooltip.
+pub fn placement_happiness_delta(
+    building_type: BuildingType,
+    tile_pos: &TilePos,
+    population: &CityPopulation,
+    services: &CityServices,
+    spatial_grid: &SpatialGrid,
+) -> f32 {
+    // a new road gives its own tile road access
+    if building_type != BuildingType::Road && !is_accessible(tile_pos, spatial_grid) {
+        return 0.0;
+    }
+
+    let nearby_residential = count_nearby_residential(tile_pos, spatial_grid);
+
+    let pop = population.population.max(1) as f32;
+    let housing_need = (services.housing_demand.max(0) as f32 / pop).clamp(0.0, 1.0);
+    let job_need = (services.job_demand.max(0) as f32 / pop).clamp(0.0, 1.0);
+
+    let delta = match building_type {
+        BuildingType::Residential => {
+            let base = 0.01 * (nearby_residential as f32 + 1.0);
+            base * housing_need
+        }
+        BuildingType::Commercial => {
+            let base = 0.015 * nearby_residential as f32;
+            base * job_need
+        }
+        BuildingType::Industry => {
+            let contrib = building_contribution(building_type);
+            let jobs_gained = contrib.jobs as f32;
+
+            let positive = 0.01 * job_need * (jobs_gained / 10.0);
+            let negative = -0.005 * nearby_residential as f32;
+            let isolation_penalty = if nearby_residential == 0 { -0.01 } else { 0.0 };
+
+            positive + negative + isolation_penalty
+        }
+        BuildingType::Road => {
+            let base = 0.003 * nearby_residential as f32;
+            base * housing_need
+        }
+        BuildingType::Decorative => 0.01 * nearby_residential as f32,
+        BuildingType::BusStop => 0.004 * nearby_residential as f32,
+        BuildingType::School => 0.008 * nearby_residential as f32,
+        // crowds and floodlights are a mixed blessing for the houses next door
+        BuildingType::Stadium => 0.01 - 0.002 * nearby_residential as f32,
+    };
+
+    delta.clamp(-0.05, 0.05)
+}
+
 pub fn apply_placement_happiness(
     mut population: ResMut<CityPopulation>,
     services: Res<CityServices>,
     spatial_grid: Res<SpatialGrid>,
+    mut impacts: ResMut<PlacementImpacts>,
     mut placed_reader: MessageReader<BuildingPlaced>,
 ) {
     for event in placed_reader.read() {
-        if !is_accessible(&event.tile_pos, &spatial_grid) {
-            continue;
-        }
+        let delta = placement_happiness_delta(
+            event.building_type,
+            &event.tile_pos,
+            &population,
+            &services,
+            &spatial_grid,
+        );
 
-        let nearby_residential = count_nearby_residential(&event.tile_pos, &spatial_grid);
-
-        let pop = population.population.max(1) as f32;
-        let housing_need = (services.housing_demand.max(0) as f32 / pop).clamp(0.0, 1.0);
-        let job_need = (services.job_demand.max(0) as f32 / pop).clamp(0.0, 1.0);
-
-        let mut delta = match event.building_type {
-            BuildingType::Residential => {
-                let base = 0.01 * (nearby_residential as f32 + 1.0);
-                base * housing_need
-            }
-            BuildingType::Commercial => {
-                let base = 0.015 * nearby_residential as f32;
-                base * job_need
-            }
-            BuildingType::Industry => {
-                let contrib = building_contribution(event.building_type);
-                let jobs_gained = contrib.jobs as f32;
-
-                let positive = 0.01 * job_need * (jobs_gained / 10.0);
-                let negative = -0.005 * nearby_residential as f32;
-                let isolation_penalty = if nearby_residential == 0 { -0.01 } else { 0.0 };
-
-                positive + negative + isolation_penalty
-            }
-            BuildingType::Road => {
-                let base = 0.003 * nearby_residential as f32;
-                base * housing_need
-            }
-            BuildingType::Decorative => 0.01 * nearby_residential as f32,
-        };
-
-        delta = delta.clamp(-0.05, 0.05);
+        impacts.by_tile.insert(event.tile_pos, delta);
         if delta != 0.0 {
             if delta < 0.0 {
                 info!(
-                    "Happiness decreased by {:.3} due to placement of {:?} at {:?} (nearby_residential={})",
-                    delta, event.building_type, event.tile_pos, nearby_residential
+                    "Happiness decreased by {:.3} due to placement of {:?} at {:?}",
+                    delta, event.building_type, event.tile_pos
                 );
             }
             population.happiness = (population.happiness + delta).clamp(0.0, 1.0);
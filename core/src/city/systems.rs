@@ -1,20 +1,34 @@
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use bevy_ecs_tilemap::prelude::{TileStorage, TileTextureIndex, TilemapSize};
+use bevy_ecs_tilemap::prelude::{TileStorage, TilemapSize};
 
+use crate::budget::spending::update_income_on_day_tick;
 use crate::budget::{BuildingDemolished, BuildingPlaced, BuildingType};
-use crate::map::{
-    ABANDONED_TEXTURE_INDEX, CommercialBuilding, IndustryBuilding, ResidentialBuilding,
-};
+use crate::map::{Derelict, PlacedBuilding, TileState, abandon_building, restore_building};
+use crate::menu::{GAME_EXIT, GameState, reset_resource};
+use crate::notifications::Notify;
 use crate::spatial::{
     SpatialGrid, sync_spatial_grid_on_demolition, sync_spatial_grid_on_placement,
 };
 use crate::time::GameClock;
+use crate::time::weather::WeatherState;
+use crate::transit::RoadTraffic;
 
-use super::display::{setup_city_stats_display, update_city_stats_display};
+use super::abandonment::{ABANDON_HAPPINESS, AtRisk, flag_buildings_at_risk};
+use super::connections::{
+    OutsideConnections, RoadNetwork, clear_highway_markers, spawn_highway_markers,
+    update_road_network,
+};
+use super::demographics::update_demographics;
+use super::display::{
+    handle_tax_buttons, setup_city_stats_display, update_at_risk_icons, update_city_stats_display,
+    update_rci_meter, update_tax_labels,
+};
+use super::goods::{GoodsFlow, update_goods_on_day_tick};
+use super::rci::{RciDemand, update_rci_demand};
 use super::resources::{
-    CityInfrastructure, CityPopulation, CityServices, apply_demolition_happiness,
-    apply_placement_happiness, building_contribution,
+    CityInfrastructure, CityPopulation, CityServices, Demographics, PlacementImpacts,
+    apply_demolition_happiness, apply_placement_happiness, building_contribution, land_value,
 };
 
 pub struct SimulationPlugin;
@@ -24,7 +38,13 @@ impl Plugin for SimulationPlugin {
         app.init_resource::<CityPopulation>()
             .init_resource::<CityServices>()
             .init_resource::<CityInfrastructure>()
+            .init_resource::<Demographics>()
+            .init_resource::<RciDemand>()
+            .init_resource::<GoodsFlow>()
+            .init_resource::<OutsideConnections>()
+            .init_resource::<RoadNetwork>()
             .init_resource::<SpatialGrid>()
+            .init_resource::<PlacementImpacts>()
             .add_systems(Startup, setup_city_stats_display)
             .add_systems(
                 Update,
@@ -34,23 +54,86 @@ impl Plugin for SimulationPlugin {
                     update_capacities_from_building_events,
                     update_infrastructure_from_building_events,
                 )
-                    .chain(),
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (update_road_network, update_population, update_demographics)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(Update, spawn_highway_markers)
+            .add_systems(
+                Update,
+                (update_demands, update_happiness_from_demands).run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                update_goods_on_day_tick
+                    .after(update_demographics)
+                    .before(update_income_on_day_tick)
+                    .run_if(in_state(GameState::InGame)),
             )
-            .add_systems(Update, update_population)
-            .add_systems(Update, (update_demands, update_happiness_from_demands))
             .add_systems(
                 Update,
-                apply_abandonment.after(update_happiness_from_demands),
+                update_rci_demand
+                    .after(update_demands)
+                    .run_if(in_state(GameState::InGame)),
             )
             .add_systems(
                 Update,
-                apply_placement_happiness.after(update_happiness_from_demands),
+                (apply_abandonment, flag_buildings_at_risk)
+                    .chain()
+                    .after(update_happiness_from_demands)
+                    .run_if(in_state(GameState::InGame)),
             )
             .add_systems(
                 Update,
-                apply_demolition_happiness.after(apply_placement_happiness),
+                apply_placement_happiness
+                    .after(update_happiness_from_demands)
+                    .run_if(in_state(GameState::InGame)),
             )
-            .add_systems(Update, update_city_stats_display);
+            .add_systems(
+                Update,
+                apply_demolition_happiness
+                    .after(apply_placement_happiness)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                update_derelict_lots
+                    .after(update_happiness_from_demands)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(Update, update_city_stats_display)
+            .add_systems(Update, update_rci_meter.after(update_rci_demand))
+            .add_systems(
+                Update,
+                (handle_tax_buttons, update_tax_labels)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(PostUpdate, update_at_risk_icons);
+
+        for exit in GAME_EXIT {
+            app.add_systems(
+                exit,
+                (
+                    clear_highway_markers,
+                    reset_resource::<CityPopulation>,
+                    reset_resource::<CityServices>,
+                    reset_resource::<CityInfrastructure>,
+                    reset_resource::<Demographics>,
+                    reset_resource::<RciDemand>,
+                    reset_resource::<GoodsFlow>,
+                    reset_resource::<OutsideConnections>,
+                    reset_resource::<RoadNetwork>,
+                    reset_resource::<SpatialGrid>,
+                    reset_resource::<PlacementImpacts>,
+                ),
+            );
+        }
     }
 }
 
@@ -61,17 +144,11 @@ pub fn update_capacities_from_building_events(
     mut demolished_reader: MessageReader<BuildingDemolished>,
 ) {
     for event in placed_reader.read() {
-        let contrib = building_contribution(event.building_type);
-        services.housing_capacity += contrib.housing;
-        services.job_capacity += contrib.jobs;
-        services.entertainment_capacity += contrib.entertainment;
+        services.count_building(event.building_type, 1);
     }
 
     for event in demolished_reader.read() {
-        let contrib = building_contribution(event.building_type);
-        services.housing_capacity -= contrib.housing;
-        services.job_capacity -= contrib.jobs;
-        services.entertainment_capacity -= contrib.entertainment;
+        services.count_building(event.building_type, -1);
     }
 
     services.housing_capacity = services.housing_capacity.max(0);
@@ -85,49 +162,11 @@ pub fn update_infrastructure_from_building_events(
     mut demolished_reader: MessageReader<BuildingDemolished>,
 ) {
     for event in placed_reader.read() {
-        let contrib = building_contribution(event.building_type);
-        match event.building_type {
-            BuildingType::Residential => {
-                infra.residential_count += 1;
-            }
-            BuildingType::Commercial => {
-                infra.commercial_count += 1;
-                infra.commercial_job_capacity += contrib.jobs;
-            }
-            BuildingType::Industry => {
-                infra.industry_count += 1;
-                infra.industry_job_capacity += contrib.jobs;
-            }
-            BuildingType::Road => {
-                infra.road_count += 1;
-            }
-            BuildingType::Decorative => {
-                infra.decorative_count += 1;
-            }
-        }
+        infra.count_building(event.building_type, 1);
     }
 
     for event in demolished_reader.read() {
-        let contrib = building_contribution(event.building_type);
-        match event.building_type {
-            BuildingType::Residential => {
-                infra.residential_count -= 1;
-            }
-            BuildingType::Commercial => {
-                infra.commercial_count -= 1;
-                infra.commercial_job_capacity -= contrib.jobs;
-            }
-            BuildingType::Industry => {
-                infra.industry_count -= 1;
-                infra.industry_job_capacity -= contrib.jobs;
-            }
-            BuildingType::Road => {
-                infra.road_count -= 1;
-            }
-            BuildingType::Decorative => {
-                infra.decorative_count -= 1;
-            }
-        }
+        infra.count_building(event.building_type, -1);
     }
 
     infra.residential_count = infra.residential_count.max(0);
@@ -135,6 +174,9 @@ pub fn update_infrastructure_from_building_events(
     infra.industry_count = infra.industry_count.max(0);
     infra.road_count = infra.road_count.max(0);
     infra.decorative_count = infra.decorative_count.max(0);
+    infra.bus_stop_count = infra.bus_stop_count.max(0);
+    infra.school_count = infra.school_count.max(0);
+    infra.stadium_count = infra.stadium_count.max(0);
     infra.industry_job_capacity = infra.industry_job_capacity.max(0);
     infra.commercial_job_capacity = infra.commercial_job_capacity.max(0);
 }
@@ -144,9 +186,15 @@ fn calculate_population_target(
     current_population: i64,
     happiness: f32,
     job_capacity: i64,
+    highway_connected: bool,
 ) -> i64 {
     let base_target = housing_capacity.max(0);
 
+    // newcomers arrive by highway; without one the city can only shrink
+    if !highway_connected {
+        return base_target.min(current_population);
+    }
+
     if happiness < 0.7 || current_population == 0 {
         return base_target;
     }
@@ -175,6 +223,8 @@ fn calculate_population_target(
 pub fn update_population(
     clock: Res<GameClock>,
     services: Res<CityServices>,
+    network: Res<RoadNetwork>,
+    mut connections: ResMut<OutsideConnections>,
     mut population: ResMut<CityPopulation>,
     mut last_processed_day: Local<u32>,
 ) {
@@ -191,6 +241,7 @@ pub fn update_population(
         current_pop,
         population.happiness,
         services.job_capacity,
+        network.is_connected(),
     );
 
     let max_population = if housing_cap == 0 {
@@ -201,6 +252,7 @@ pub fn update_population(
 
     let diff = max_population - current_pop;
 
+    connections.migrant_trips = 0;
     if diff == 0 {
         return;
     }
@@ -221,6 +273,9 @@ pub fn update_population(
     let old_pop = current_pop;
     population.population += step;
     population.population = population.population.max(0);
+    if network.is_connected() {
+        connections.migrant_trips = (population.population - old_pop).abs();
+    }
 
     let immigration_driven = target_population > housing_cap;
     if immigration_driven {
@@ -242,7 +297,13 @@ pub fn update_population(
     }
 }
 
-pub fn update_demands(mut services: ResMut<CityServices>, population: Res<CityPopulation>) {
+pub fn update_demands(
+    mut services: ResMut<CityServices>,
+    population: Res<CityPopulation>,
+    demographics: Res<Demographics>,
+    infra: Res<CityInfrastructure>,
+    weather: Res<WeatherState>,
+) {
     let pop = population.population.max(0);
 
     let mut happy_growth_bonus: i64 = 0;
@@ -252,8 +313,21 @@ pub fn update_demands(mut services: ResMut<CityServices>, population: Res<CityPo
     }
 
     services.housing_demand = (pop + happy_growth_bonus - services.housing_capacity).max(0);
-    services.job_demand = (pop - services.job_capacity).max(0);
-    services.entertainment_demand = (pop - services.entertainment_capacity).max(0);
+    // children and retirees don't look for work
+    services.job_demand = (demographics.workers.round() as i64 - services.job_capacity).max(0);
+    // parks and stadiums lose visitors to the season and the weather
+    let outdoor_loss: f32 = [
+        (BuildingType::Decorative, infra.decorative_count),
+        (BuildingType::Stadium, infra.stadium_count),
+    ]
+    .into_iter()
+    .map(|(building_type, count)| {
+        let entertainment = (count * building_contribution(building_type).entertainment) as f32;
+        entertainment * (1.0 - weather.entertainment_factor(building_type))
+    })
+    .sum();
+    let entertainment = services.entertainment_capacity - outdoor_loss.round() as i64;
+    services.entertainment_demand = (pop - entertainment).max(0);
 }
 
 /// Recompute happiness from current demands once per in‑game day.
@@ -263,6 +337,7 @@ pub fn update_demands(mut services: ResMut<CityServices>, population: Res<CityPo
 pub fn update_happiness_from_demands(
     clock: Res<GameClock>,
     services: Res<CityServices>,
+    traffic: Res<RoadTraffic>,
     mut population: ResMut<CityPopulation>,
     mut last_processed_day: Local<u32>,
 ) {
@@ -291,8 +366,18 @@ pub fn update_happiness_from_demands(
         0.0
     };
 
-    // Weight shortages; housing and jobs hurt more than entertainment.
-    let mut pressure = 0.8 * housing_pressure + 0.6 * job_pressure + 0.25 * entertainment_shortfall;
+    // drivers stuck in jams; bus riders are spared
+    let traffic_pressure = if pop > 0 {
+        traffic.delayed_share() * (traffic.car_commuters as f32 / pop_f).min(1.0)
+    } else {
+        0.0
+    };
+
+    // Weight shortages; housing and jobs hurt more than entertainment and traffic.
+    let mut pressure = 0.8 * housing_pressure
+        + 0.6 * job_pressure
+        + 0.25 * entertainment_shortfall
+        + 0.3 * traffic_pressure;
     // Cap extreme pressure so it doesn't explode numerically
     if pressure > 1.5 {
         pressure = 1.5;
@@ -304,31 +389,31 @@ pub fn update_happiness_from_demands(
 
     if new_happiness + 1e-4 < old_happiness {
         info!(
-            "Happiness decreased from {:.3} to {:.3} due to service pressures: housing={:.3}, jobs={:.3}, entertainment_shortfall={:.3}",
-            old_happiness, new_happiness, housing_pressure, job_pressure, entertainment_shortfall
+            "Happiness decreased from {:.3} to {:.3} due to service pressures: housing={:.3}, jobs={:.3}, entertainment_shortfall={:.3}, traffic={:.3}",
+            old_happiness,
+            new_happiness,
+            housing_pressure,
+            job_pressure,
+            entertainment_shortfall,
+            traffic_pressure
         );
     }
 }
 
-type AbandonmentBuildingQuery<'w, 's> = Query<
-    'w,
-    's,
-    (
-        Entity,
-        Option<&'static ResidentialBuilding>,
-        Option<&'static CommercialBuilding>,
-        Option<&'static IndustryBuilding>,
-    ),
->;
+type AbandonmentBuildingQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static PlacedBuilding, &'static AtRisk), Without<Derelict>>;
 
 #[derive(SystemParam)]
 pub struct AbandonmentInputs<'w, 's> {
     clock: Res<'w, GameClock>,
     population: Res<'w, CityPopulation>,
     services: Res<'w, CityServices>,
+    demographics: Res<'w, Demographics>,
     tile_storage_q: Query<'w, 's, (&'static TileStorage, &'static TilemapSize)>,
-    tile_texture_q: Query<'w, 's, &'static mut TileTextureIndex>,
+    tile_state_q: Query<'w, 's, &'static mut TileState>,
+    spatial_grid: ResMut<'w, SpatialGrid>,
     demolished_writer: MessageWriter<'w, BuildingDemolished>,
+    notify_writer: MessageWriter<'w, Notify>,
     commands: Commands<'w, 's>,
     building_sprites_q: AbandonmentBuildingQuery<'w, 's>,
 }
@@ -336,10 +421,9 @@ pub struct AbandonmentInputs<'w, 's> {
 /// Periodically abandon buildings based on happiness and service pressures
 pub fn apply_abandonment(mut inputs: AbandonmentInputs, mut last_abandonment_day: Local<u32>) {
     const ABANDONMENT_INTERVAL_DAYS: u32 = 3;
-    // If people are reasonably happy skip abandonment
-    const MIN_HAPPINESS_FOR_ABANDON: f32 = 0.7;
 
-    if inputs.clock.day < *last_abandonment_day + ABANDONMENT_INTERVAL_DAYS {
+    // measured both ways: the next city may start on an earlier day than the last one
+    if inputs.clock.day.abs_diff(*last_abandonment_day) < ABANDONMENT_INTERVAL_DAYS {
         return;
     }
     *last_abandonment_day = inputs.clock.day;
@@ -354,7 +438,8 @@ pub fn apply_abandonment(mut inputs: AbandonmentInputs, mut last_abandonment_day
     if pop == 0 {
         return;
     }
-    if inputs.population.happiness >= MIN_HAPPINESS_FOR_ABANDON {
+    // If people are reasonably happy skip abandonment
+    if inputs.population.happiness >= ABANDON_HAPPINESS {
         return;
     }
 
@@ -362,8 +447,7 @@ pub fn apply_abandonment(mut inputs: AbandonmentInputs, mut last_abandonment_day
 
     let job_cap = inputs.services.job_capacity.max(0);
     let jobs_f = job_cap as f32;
-    let pop_f = pop as f32;
-    let employed = pop_f.min(jobs_f);
+    let employed = inputs.demographics.workforce().min(job_cap) as f32;
     // Unhappy workers effectively "quit", reducing staffing
     let effective_workers = employed * inputs.population.happiness.clamp(0.0, 1.0);
     let staffing_ratio = if jobs_f > 0.0 {
@@ -390,28 +474,48 @@ pub fn apply_abandonment(mut inputs: AbandonmentInputs, mut last_abandonment_day
     let residential_to_abandon: usize = if housing_shortage { 1 } else { 0 };
     let job_capacity_to_remove: i64 = if job_understaffed { 1 } else { 0 };
 
+    // only buildings flagged long enough can go, worst local conditions first
+    let mut candidates: Vec<(Entity, PlacedBuilding, f32)> = inputs
+        .building_sprites_q
+        .iter()
+        .filter(|(_, _, at_risk)| at_risk.warning_served(inputs.clock.day))
+        .map(|(entity, building, at_risk)| (entity, *building, at_risk.score))
+        .collect();
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
     if residential_to_abandon > 0 {
         let mut remaining = residential_to_abandon;
 
-        for (entity, residential, _commercial, _industry) in inputs.building_sprites_q.iter() {
+        for (entity, building, score) in &candidates {
             if remaining == 0 {
                 break;
             }
 
-            if let Some(building) = residential {
+            if building.building_type == BuildingType::Residential {
                 let pos = building.tile_pos;
-                if let Some(tile_entity) = tile_storage.get(&pos)
-                    && let Ok(mut texture) = inputs.tile_texture_q.get_mut(tile_entity)
-                {
-                    texture.0 = ABANDONED_TEXTURE_INDEX;
-                    info!("Abandoned residential at {:?}", pos);
-                    inputs.demolished_writer.write(BuildingDemolished {
-                        building_type: BuildingType::Residential,
-                        tile_pos: pos,
-                    });
-                    inputs.commands.entity(entity).despawn();
-                    remaining -= 1;
-                }
+                abandon_building(
+                    &mut inputs.commands,
+                    &mut inputs.spatial_grid,
+                    tile_storage,
+                    &mut inputs.tile_state_q,
+                    *entity,
+                    building,
+                    inputs.clock.day,
+                );
+                info!("Abandoned residential at {:?} (risk {:.2})", pos, score);
+                inputs.notify_writer.write(
+                    Notify::warning(format!(
+                        "Residential building at ({}, {}) was abandoned",
+                        pos.x, pos.y
+                    ))
+                    .at(pos),
+                );
+                inputs.demolished_writer.write(BuildingDemolished {
+                    building_type: BuildingType::Residential,
+                    tile_pos: pos,
+                    footprint: building.footprint(),
+                });
+                remaining -= 1;
             }
         }
     }
@@ -420,17 +524,16 @@ pub fn apply_abandonment(mut inputs: AbandonmentInputs, mut last_abandonment_day
     if job_capacity_to_remove > 0 {
         let mut remaining_jobs = job_capacity_to_remove;
 
-        for (entity, _residential, commercial, industry) in inputs.building_sprites_q.iter() {
+        for (entity, building, score) in &candidates {
             if remaining_jobs <= 0 {
                 break;
             }
 
-            let (pos, btype) = if let Some(b) = commercial {
-                (b.tile_pos, BuildingType::Commercial)
-            } else if let Some(b) = industry {
-                (b.tile_pos, BuildingType::Industry)
-            } else {
-                continue;
+            let (pos, btype) = match building.building_type {
+                BuildingType::Commercial | BuildingType::Industry => {
+                    (building.tile_pos, building.building_type)
+                }
+                _ => continue,
             };
 
             let contrib = building_contribution(btype);
@@ -438,18 +541,147 @@ pub fn apply_abandonment(mut inputs: AbandonmentInputs, mut last_abandonment_day
                 continue;
             }
 
-            if let Some(tile_entity) = tile_storage.get(&pos)
-                && let Ok(mut texture) = inputs.tile_texture_q.get_mut(tile_entity)
-            {
-                texture.0 = ABANDONED_TEXTURE_INDEX;
-                info!("Abandoned {:?} at {:?}", btype, pos);
-                inputs.demolished_writer.write(BuildingDemolished {
-                    building_type: btype,
-                    tile_pos: pos,
-                });
-                inputs.commands.entity(entity).despawn();
-                remaining_jobs -= contrib.jobs;
-            }
+            abandon_building(
+                &mut inputs.commands,
+                &mut inputs.spatial_grid,
+                tile_storage,
+                &mut inputs.tile_state_q,
+                *entity,
+                building,
+                inputs.clock.day,
+            );
+            info!("Abandoned {:?} at {:?} (risk {:.2})", btype, pos, score);
+            inputs.notify_writer.write(
+                Notify::warning(format!(
+                    "{} building at ({}, {}) was abandoned",
+                    btype.label(),
+                    pos.x,
+                    pos.y
+                ))
+                .at(pos),
+            );
+            inputs.demolished_writer.write(BuildingDemolished {
+                building_type: btype,
+                tile_pos: pos,
+                footprint: building.footprint(),
+            });
+            remaining_jobs -= contrib.jobs;
         }
     }
 }
+
+/// Days a lot stays derelict before it can be reoccupied
+const DERELICT_RECOVERY_DELAY_DAYS: u32 = 5;
+const MIN_HAPPINESS_FOR_RECOVERY: f32 = 0.75;
+const MIN_LAND_VALUE_FOR_RECOVERY: f32 = 0.8;
+/// Residents within this radius of a derelict lot are bothered by it
+const DERELICT_BLIGHT_RADIUS: i32 = 3;
+const DERELICT_BLIGHT_PER_LOT: f32 = 0.003;
+const MAX_DERELICT_BLIGHT_PER_DAY: f32 = 0.03;
+
+#[derive(SystemParam)]
+pub struct DerelictInputs<'w, 's> {
+    clock: Res<'w, GameClock>,
+    population: ResMut<'w, CityPopulation>,
+    services: Res<'w, CityServices>,
+    spatial_grid: ResMut<'w, SpatialGrid>,
+    tile_storage_q: Query<'w, 's, &'static TileStorage>,
+    tile_state_q: Query<'w, 's, &'static mut TileState>,
+    derelicts_q: Query<'w, 's, (Entity, &'static Derelict, &'static PlacedBuilding)>,
+    placed_writer: MessageWriter<'w, BuildingPlaced>,
+    notify_writer: MessageWriter<'w, Notify>,
+    commands: Commands<'w, 's>,
+}
+
+/// Once per day derelict lots weigh on nearby residents, and at most one lot
+/// whose neighbourhood has improved is moved back into
+pub fn update_derelict_lots(mut inputs: DerelictInputs, mut last_processed_day: Local<u32>) {
+    if *last_processed_day == inputs.clock.day {
+        return;
+    }
+    *last_processed_day = inputs.clock.day;
+
+    let mut derelicts: Vec<(Entity, u32, PlacedBuilding)> = inputs
+        .derelicts_q
+        .iter()
+        .map(|(entity, derelict, building)| (entity, derelict.since_day, *building))
+        .collect();
+    if derelicts.is_empty() {
+        return;
+    }
+    // oldest lots first, so recovery order doesn't depend on entity ids
+    derelicts.sort_by_key(|(_, since_day, building)| {
+        (*since_day, building.tile_pos.x, building.tile_pos.y)
+    });
+
+    let blighted = derelicts
+        .iter()
+        .filter(|(_, _, building)| {
+            inputs
+                .spatial_grid
+                .count_residential_in_radius(&building.tile_pos, DERELICT_BLIGHT_RADIUS)
+                > 0
+        })
+        .count();
+    if blighted > 0 {
+        let penalty = (DERELICT_BLIGHT_PER_LOT * blighted as f32).min(MAX_DERELICT_BLIGHT_PER_DAY);
+        inputs.population.happiness = (inputs.population.happiness - penalty).clamp(0.0, 1.0);
+        info!(
+            "Happiness decreased by {:.3} due to {} derelict lots next to homes",
+            penalty, blighted
+        );
+    }
+
+    if inputs.population.happiness < MIN_HAPPINESS_FOR_RECOVERY {
+        return;
+    }
+
+    let Some(tile_storage) = inputs.tile_storage_q.iter().next() else {
+        return;
+    };
+
+    for (entity, since_day, building) in derelicts {
+        if inputs.clock.day < since_day + DERELICT_RECOVERY_DELAY_DAYS {
+            continue;
+        }
+
+        let wanted = match building.building_type {
+            BuildingType::Residential => inputs.services.housing_demand > 0,
+            BuildingType::Commercial | BuildingType::Industry => inputs.services.job_demand > 0,
+            _ => false,
+        };
+        if !wanted
+            || land_value(&building.tile_pos, &inputs.spatial_grid) < MIN_LAND_VALUE_FOR_RECOVERY
+        {
+            continue;
+        }
+
+        restore_building(
+            &mut inputs.commands,
+            &mut inputs.spatial_grid,
+            tile_storage,
+            &mut inputs.tile_state_q,
+            entity,
+            &building,
+        );
+        inputs.placed_writer.write(BuildingPlaced {
+            building_type: building.building_type,
+            tile_pos: building.tile_pos,
+            footprint: building.footprint(),
+        });
+        info!(
+            "Derelict {:?} at {:?} was reoccupied",
+            building.building_type, building.tile_pos
+        );
+        inputs.notify_writer.write(
+            Notify::info(format!(
+                "Derelict {} building at ({}, {}) was moved back into",
+                building.building_type.label(),
+                building.tile_pos.x,
+                building.tile_pos.y
+            ))
+            .at(building.tile_pos),
+        );
+        break;
+    }
+}
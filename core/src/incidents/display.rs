@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+
+use crate::map::UiClickBlocker;
+use crate::notifications::Notify;
+use crate::settings::GameSettings;
+
+use super::events::{IncidentEnded, IncidentStarted};
+use super::resources::{IncidentKind, IncidentScheduler};
+
+#[derive(Component)]
+pub struct IncidentFeedText;
+
+#[derive(Component)]
+pub struct IncidentToggleButton;
+
+#[derive(Component)]
+pub struct IncidentToggleLabel;
+
+pub fn setup_incident_display(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(140.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|card| {
+            card.spawn((
+                Text::new("City events"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                IncidentFeedText,
+            ));
+
+            card.spawn((
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.8, 0.8, 0.8, 1.0)),
+                IncidentToggleButton,
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new("Random events: ON"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::BLACK),
+                    IncidentToggleLabel,
+                ));
+            });
+        });
+}
+
+pub fn handle_incident_toggle_button(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<IncidentToggleButton>)>,
+    mut label_q: Query<&mut Text, With<IncidentToggleLabel>>,
+    mut settings: ResMut<GameSettings>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    for interaction in interaction_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        ui_click_blocker.just_clicked_ui = true;
+        settings.random_events = !settings.random_events;
+        info!(
+            "Random city events {}",
+            if settings.random_events {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+    }
+
+    // the main menu settings can flip this too
+    if settings.is_changed() {
+        for mut text in label_q.iter_mut() {
+            text.0 = if settings.random_events {
+                "Random events: ON".to_string()
+            } else {
+                "Random events: OFF".to_string()
+            };
+        }
+    }
+}
+
+/// Forward incident start/end messages to the notification feed
+pub fn announce_incidents(
+    mut started_reader: MessageReader<IncidentStarted>,
+    mut ended_reader: MessageReader<IncidentEnded>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    for event in started_reader.read() {
+        let notify = match event.kind {
+            IncidentKind::Fire => Notify::critical(format!("{} broke out!", event.kind.label())),
+            IncidentKind::Flood | IncidentKind::Recession => {
+                Notify::warning(format!("{} started", event.kind.label()))
+            }
+            IncidentKind::EconomicBoom | IncidentKind::Festival => {
+                Notify::info(format!("{} started", event.kind.label()))
+            }
+        };
+
+        notify_writer.write(match event.tile_pos {
+            Some(pos) => notify.at(pos),
+            None => notify,
+        });
+    }
+
+    for event in ended_reader.read() {
+        notify_writer.write(Notify::info(format!("{} is over", event.kind.label())));
+    }
+}
+
+/// List the incidents currently in progress
+pub fn update_active_incidents_text(
+    scheduler: Option<Res<IncidentScheduler>>,
+    mut query: Query<&mut Text, With<IncidentFeedText>>,
+) {
+    let Some(scheduler) = scheduler else {
+        return;
+    };
+    if !scheduler.is_changed() {
+        return;
+    }
+
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+
+    let mut label = String::from("City events");
+    if scheduler.active.is_empty() {
+        label.push_str("\nAll quiet");
+    }
+    for incident in &scheduler.active {
+        let location = incident
+            .tile_pos
+            .map(|pos| format!(" at ({}, {})", pos.x, pos.y))
+            .unwrap_or_default();
+        label.push_str(&format!(
+            "\n> {}{} (day {}-{})",
+            incident.kind.label(),
+            location,
+            incident.started_day,
+            incident.ends_day
+        ));
+    }
+
+    text.0 = label;
+}
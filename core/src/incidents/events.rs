@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+
+use super::resources::IncidentKind;
+
+#[derive(Message)]
+pub struct IncidentStarted {
+    pub kind: IncidentKind,
+    pub tile_pos: Option<TilePos>,
+}
+
+#[derive(Message)]
+pub struct IncidentEnded {
+    pub kind: IncidentKind,
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::menu::{GAME_EXIT, GameState};
+
+mod display;
+mod events;
+mod resources;
+mod systems;
+
+pub use events::{IncidentEnded, IncidentStarted};
+
+pub struct IncidentPlugin;
+
+impl Plugin for IncidentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<IncidentStarted>()
+            .add_message::<IncidentEnded>()
+            .add_systems(
+                Startup,
+                (
+                    systems::setup_incident_scheduler,
+                    display::setup_incident_display,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::run_incidents_on_day_tick.run_if(in_state(GameState::InGame)),
+                    display::announce_incidents,
+                    display::update_active_incidents_text,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, display::handle_incident_toggle_button);
+
+        // the next city rolls its incidents from the start of the seed again
+        for exit in GAME_EXIT {
+            app.add_systems(exit, systems::setup_incident_scheduler);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Things that can happen to the city without the player asking for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IncidentKind {
+    Fire,
+    Flood,
+    EconomicBoom,
+    Recession,
+    Festival,
+}
+
+impl IncidentKind {
+    pub const ALL: [IncidentKind; 5] = [
+        IncidentKind::Fire,
+        IncidentKind::Flood,
+        IncidentKind::EconomicBoom,
+        IncidentKind::Recession,
+        IncidentKind::Festival,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncidentKind::Fire => "Fire",
+            IncidentKind::Flood => "Flood",
+            IncidentKind::EconomicBoom => "Economic boom",
+            IncidentKind::Recession => "Recession",
+            IncidentKind::Festival => "Festival",
+        }
+    }
+
+    /// How many in‑game days the incident stays active
+    pub fn duration_days(&self) -> u32 {
+        match self {
+            IncidentKind::Fire => 1,
+            IncidentKind::Flood => 3,
+            IncidentKind::EconomicBoom => 5,
+            IncidentKind::Recession => 5,
+            IncidentKind::Festival => 2,
+        }
+    }
+
+    /// Relative chance of this kind being picked when an incident fires
+    pub fn weight(&self) -> u32 {
+        match self {
+            IncidentKind::Fire => 3,
+            IncidentKind::Flood => 2,
+            IncidentKind::EconomicBoom => 2,
+            IncidentKind::Recession => 2,
+            IncidentKind::Festival => 3,
+        }
+    }
+
+    /// Happiness change applied on every day the incident is active
+    pub fn daily_happiness_delta(&self) -> f32 {
+        match self {
+            IncidentKind::Fire => -0.03,
+            IncidentKind::Flood => -0.01,
+            IncidentKind::EconomicBoom => 0.005,
+            IncidentKind::Recession => -0.005,
+            IncidentKind::Festival => 0.02,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveIncident {
+    pub kind: IncidentKind,
+    pub started_day: u32,
+    pub ends_day: u32,
+    /// Tile the incident is centred on, for incidents that hit a specific building
+    pub tile_pos: Option<TilePos>,
+}
+
+/// Seeded scheduler that decides when incidents fire and tracks the ones in progress
+#[derive(Resource)]
+pub struct IncidentScheduler {
+    pub rng: StdRng,
+    pub active: Vec<ActiveIncident>,
+    pub last_incident_day: u32,
+}
+
+impl IncidentScheduler {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            active: Vec::new(),
+            last_incident_day: 0,
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TileStorage;
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::budget::{Budget, BudgetLedger, BuildingDemolished, BuildingType, LedgerCategory};
+use crate::city::resources::{CityPopulation, CityServices, Demographics};
+use crate::map::{
+    CommercialBuilding, IndustryBuilding, PlacedBuilding, ResidentialBuilding, TileState,
+    abandon_building,
+};
+use crate::settings::GameSettings;
+use crate::spatial::SpatialGrid;
+use crate::time::GameClock;
+
+use super::events::{IncidentEnded, IncidentStarted};
+use super::resources::{ActiveIncident, IncidentKind, IncidentScheduler};
+
+/// Chance that an incident fires on any given day once the cooldown has passed
+const INCIDENT_CHANCE_PER_DAY: f64 = 0.15;
+const MIN_DAYS_BETWEEN_INCIDENTS: u32 = 4;
+/// Give new cities a few quiet days before anything happens
+const FIRST_INCIDENT_DAY: u32 = 5;
+
+const FLOOD_CLEANUP_PER_DAY: i64 = 400;
+const FESTIVAL_COST_PER_DAY: i64 = 300;
+const ECONOMIC_SWING_PER_WORKER: i64 = 3;
+
+type IncidentBuildingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Option<&'static ResidentialBuilding>,
+        Option<&'static CommercialBuilding>,
+        Option<&'static IndustryBuilding>,
+    ),
+>;
+
+#[derive(SystemParam)]
+pub struct IncidentInputs<'w, 's> {
+    clock: Res<'w, GameClock>,
+    settings: Res<'w, GameSettings>,
+    scheduler: ResMut<'w, IncidentScheduler>,
+    population: ResMut<'w, CityPopulation>,
+    services: Res<'w, CityServices>,
+    demographics: Res<'w, Demographics>,
+    budget: ResMut<'w, Budget>,
+    ledger: ResMut<'w, BudgetLedger>,
+    started_writer: MessageWriter<'w, IncidentStarted>,
+    ended_writer: MessageWriter<'w, IncidentEnded>,
+    demolished_writer: MessageWriter<'w, BuildingDemolished>,
+    tile_storage_q: Query<'w, 's, &'static TileStorage>,
+    tile_state_q: Query<'w, 's, &'static mut TileState>,
+    spatial_grid: ResMut<'w, SpatialGrid>,
+    buildings_q: IncidentBuildingQuery<'w, 's>,
+    placed_q: Query<'w, 's, &'static PlacedBuilding>,
+    commands: Commands<'w, 's>,
+}
+
+pub fn setup_incident_scheduler(mut commands: Commands, settings: Res<GameSettings>) {
+    commands.insert_resource(IncidentScheduler::from_seed(settings.event_seed));
+}
+
+/// Weighted pick among the incident kinds that are not already in progress
+pub fn pick_incident_kind(rng: &mut StdRng, active: &[ActiveIncident]) -> Option<IncidentKind> {
+    let candidates: Vec<IncidentKind> = IncidentKind::ALL
+        .into_iter()
+        .filter(|kind| !active.iter().any(|incident| incident.kind == *kind))
+        .collect();
+
+    let total_weight: u32 = candidates.iter().map(|kind| kind.weight()).sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut roll = rng.gen_range(0..total_weight);
+    for kind in candidates {
+        if roll < kind.weight() {
+            return Some(kind);
+        }
+        roll -= kind.weight();
+    }
+
+    None
+}
+
+/// Once per in‑game day expire finished incidents, maybe start a new one and apply
+/// the daily effects of everything in progress
+pub fn run_incidents_on_day_tick(mut inputs: IncidentInputs, mut last_incident_tick: Local<u32>) {
+    let day = inputs.clock.day;
+    if *last_incident_tick == day {
+        return;
+    }
+    *last_incident_tick = day;
+
+    let mut ended = Vec::new();
+    inputs.scheduler.active.retain(|incident| {
+        if incident.ends_day <= day {
+            ended.push(incident.kind);
+            false
+        } else {
+            true
+        }
+    });
+    for kind in ended {
+        info!("{} is over", kind.label());
+        inputs.ended_writer.write(IncidentEnded { kind });
+    }
+
+    if inputs.settings.random_events
+        && day >= FIRST_INCIDENT_DAY
+        && day >= inputs.scheduler.last_incident_day + MIN_DAYS_BETWEEN_INCIDENTS
+        && inputs.population.population > 0
+    {
+        start_random_incident(&mut inputs, day);
+    }
+
+    apply_daily_incident_effects(&mut inputs, day);
+}
+
+fn start_random_incident(inputs: &mut IncidentInputs, day: u32) {
+    let scheduler = &mut *inputs.scheduler;
+    if !scheduler.rng.gen_bool(INCIDENT_CHANCE_PER_DAY) {
+        return;
+    }
+
+    let Some(kind) = pick_incident_kind(&mut scheduler.rng, &scheduler.active) else {
+        return;
+    };
+
+    let tile_pos = if kind == IncidentKind::Fire {
+        // A fire needs something to burn
+        let Some(pos) = burn_random_building(inputs) else {
+            return;
+        };
+        Some(pos)
+    } else {
+        None
+    };
+
+    let scheduler = &mut *inputs.scheduler;
+    scheduler.last_incident_day = day;
+    scheduler.active.push(ActiveIncident {
+        kind,
+        started_day: day,
+        ends_day: day + kind.duration_days(),
+        tile_pos,
+    });
+
+    match tile_pos {
+        Some(pos) => info!("{} broke out at {:?}!", kind.label(), pos),
+        None => info!("{} started!", kind.label()),
+    }
+    inputs
+        .started_writer
+        .write(IncidentStarted { kind, tile_pos });
+}
+
+/// Destroy a random residential, commercial or industry building, leaving a burnt lot behind
+fn burn_random_building(inputs: &mut IncidentInputs) -> Option<bevy_ecs_tilemap::prelude::TilePos> {
+    let targets: Vec<_> = inputs
+        .buildings_q
+        .iter()
+        .filter_map(|(entity, residential, commercial, industry)| {
+            if let Some(b) = residential {
+                Some((entity, b.tile_pos, BuildingType::Residential))
+            } else if let Some(b) = commercial {
+                Some((entity, b.tile_pos, BuildingType::Commercial))
+            } else {
+                industry.map(|b| (entity, b.tile_pos, BuildingType::Industry))
+            }
+        })
+        .collect();
+
+    if targets.is_empty() {
+        return None;
+    }
+
+    let index = inputs.scheduler.rng.gen_range(0..targets.len());
+    let (entity, pos, building_type) = targets[index];
+
+    let tile_storage = inputs.tile_storage_q.iter().next()?;
+    let building = *inputs.placed_q.get(entity).ok()?;
+
+    abandon_building(
+        &mut inputs.commands,
+        &mut inputs.spatial_grid,
+        tile_storage,
+        &mut inputs.tile_state_q,
+        entity,
+        &building,
+        inputs.clock.day,
+    );
+    inputs.demolished_writer.write(BuildingDemolished {
+        building_type,
+        tile_pos: pos,
+        footprint: building.footprint(),
+    });
+
+    Some(pos)
+}
+
+fn apply_daily_incident_effects(inputs: &mut IncidentInputs, day: u32) {
+    let employed = inputs
+        .demographics
+        .workforce()
+        .min(inputs.services.job_capacity.max(0));
+
+    let kinds: Vec<IncidentKind> = inputs
+        .scheduler
+        .active
+        .iter()
+        .map(|incident| incident.kind)
+        .collect();
+
+    for kind in kinds {
+        let old = inputs.population.happiness;
+        inputs.population.happiness = (old + kind.daily_happiness_delta()).clamp(0.0, 1.0);
+        if inputs.population.happiness < old {
+            info!(
+                "Happiness decreased from {:.3} to {:.3} due to {}",
+                old,
+                inputs.population.happiness,
+                kind.label()
+            );
+        }
+
+        let amount = match kind {
+            IncidentKind::Fire => 0,
+            IncidentKind::Flood => -FLOOD_CLEANUP_PER_DAY,
+            IncidentKind::EconomicBoom => employed * ECONOMIC_SWING_PER_WORKER,
+            IncidentKind::Recession => -employed * ECONOMIC_SWING_PER_WORKER,
+            IncidentKind::Festival => -FESTIVAL_COST_PER_DAY,
+        };
+
+        if amount != 0 {
+            let IncidentInputs { budget, ledger, .. } = inputs;
+            ledger.book(budget, day, LedgerCategory::CityEvents, amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn same_seed_picks_the_same_incidents() {
+        let mut first = StdRng::seed_from_u64(7);
+        let mut second = StdRng::seed_from_u64(7);
+
+        for _ in 0..20 {
+            assert_eq!(
+                pick_incident_kind(&mut first, &[]),
+                pick_incident_kind(&mut second, &[])
+            );
+        }
+    }
+
+    #[test]
+    fn active_incidents_are_not_picked_again() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let active: Vec<ActiveIncident> = IncidentKind::ALL
+            .into_iter()
+            .filter(|kind| *kind != IncidentKind::Flood)
+            .map(|kind| ActiveIncident {
+                kind,
+                started_day: 1,
+                ends_day: 2,
+                tile_pos: None,
+            })
+            .collect();
+
+        for _ in 0..10 {
+            assert_eq!(
+                pick_incident_kind(&mut rng, &active),
+                Some(IncidentKind::Flood)
+            );
+        }
+
+        let all_active: Vec<ActiveIncident> = IncidentKind::ALL
+            .into_iter()
+            .map(|kind| ActiveIncident {
+                kind,
+                started_day: 1,
+                ends_day: 2,
+                tile_pos: None,
+            })
+            .collect();
+        assert_eq!(pick_incident_kind(&mut rng, &all_active), None);
+    }
+}
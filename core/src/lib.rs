@@ -1,5 +1,14 @@
 pub mod budget;
+pub mod camera;
 pub mod city;
+pub mod incidents;
 pub mod map;
+pub mod menu;
+pub mod notifications;
+pub mod progression;
+pub mod save;
+pub mod scenario;
+pub mod settings;
 pub mod spatial;
 pub mod time;
+pub mod transit;
@@ -4,30 +4,54 @@ use bevy_ecs_tilemap::prelude::*;
 mod budget;
 mod camera;
 mod city;
+mod incidents;
 mod map;
+mod menu;
+mod notifications;
+mod progression;
+mod save;
+mod scenario;
+mod settings;
 mod spatial;
 mod time;
+mod transit;
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Gorod".into(),
-                ..default()
-            }),
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "Gorod".into(),
             ..default()
-        }))
-        .add_plugins(TilemapPlugin)
-        .add_plugins(time::GameTimePlugin)
-        .add_plugins(budget::BudgetPlugin)
-        .add_plugins(camera::CameraControllerPlugin)
-        .add_plugins(map::TilePlacementPlugin)
-        .add_plugins(city::SimulationPlugin)
-        .add_systems(Startup, setup)
-        .run();
+        }),
+        ..default()
+    }))
+    .add_plugins(TilemapPlugin)
+    .add_plugins(menu::MenuPlugin)
+    .add_plugins(settings::SettingsPlugin)
+    .add_plugins(time::GameTimePlugin)
+    .add_plugins(budget::BudgetPlugin)
+    .add_plugins(camera::CameraControllerPlugin)
+    .add_plugins(map::TilePlacementPlugin)
+    .add_plugins(city::SimulationPlugin)
+    .add_plugins(transit::TransitPlugin)
+    .add_plugins(incidents::IncidentPlugin)
+    .add_plugins(notifications::NotificationPlugin)
+    .add_plugins(progression::ProgressionPlugin)
+    .add_plugins(scenario::ScenarioPlugin)
+    .add_plugins(save::SavePlugin)
+    .add_systems(Startup, setup_camera);
+
+    for start in menu::GAME_START {
+        app.add_systems(start, setup_map);
+    }
+    for exit in menu::GAME_EXIT {
+        app.add_systems(exit, clear_map);
+    }
+
+    app.run();
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_camera(mut commands: Commands) {
     commands.spawn((
         Camera2d,
         camera::CameraController {
@@ -37,12 +61,42 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             max_zoom: 3.0,
         },
     ));
+}
 
-    let texture_handle: Handle<Image> = asset_server.load("sprites/tiles.png");
-    let map_size = TilemapSize { x: 32, y: 32 };
+/// Build the tilemap for a new or loaded city, sized by the save or by the
+/// scenario if one was picked
+fn setup_map(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    scenario: Option<Res<scenario::ActiveScenario>>,
+    loaded: Option<Res<save::LoadedCity>>,
+    settings: Res<settings::GameSettings>,
+) {
+    let map_size = match (loaded.as_ref(), scenario.as_ref()) {
+        (Some(loaded), _) => TilemapSize {
+            x: loaded.0.map_size.0,
+            y: loaded.0.map_size.1,
+        },
+        (None, Some(scenario)) => TilemapSize {
+            x: scenario.0.map_size.0,
+            y: scenario.0.map_size.1,
+        },
+        (None, None) => TilemapSize { x: 32, y: 32 },
+    };
 
-    let tile_size = TilemapTileSize { x: 96.0, y: 96.0 };
-    let grid_size = TilemapGridSize { x: 96.0, y: 96.0 };
+    let (texture_path, tile_size) = map::ground_tiles(settings.isometric);
+    let texture_handle: Handle<Image> = asset_server.load(texture_path);
+    // isometric diamonds are half as tall as they are wide, like their tile art
+    let (map_type, grid_size) = if settings.isometric {
+        (
+            TilemapType::Isometric(IsoCoordSystem::Diamond),
+            TilemapGridSize { x: 96.0, y: 48.0 },
+        )
+    } else {
+        (TilemapType::Square, TilemapGridSize { x: 96.0, y: 96.0 })
+    };
+
+    let outside_connections = city::OutsideConnections::at_edge_midpoints(&map_size);
 
     let tilemap_entity = commands.spawn_empty().id();
     let mut tile_storage = TileStorage::empty(map_size);
@@ -51,12 +105,19 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         for y in 0..map_size.y {
             let tile_pos = TilePos { x, y };
             let tile_entity = commands
-                .spawn(TileBundle {
-                    position: tile_pos,
-                    tilemap_id: TilemapId(tilemap_entity),
-                    texture_index: TileTextureIndex(0),
-                    ..Default::default()
-                })
+                .spawn((
+                    TileBundle {
+                        position: tile_pos,
+                        tilemap_id: TilemapId(tilemap_entity),
+                        texture_index: TileTextureIndex(0),
+                        ..Default::default()
+                    },
+                    if outside_connections.is_connection(&tile_pos) {
+                        map::TileState::Terrain
+                    } else {
+                        map::TileState::Empty
+                    },
+                ))
                 .id();
             tile_storage.set(&tile_pos, tile_entity);
         }
@@ -68,23 +129,32 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         storage: tile_storage,
         texture: TilemapTexture::Single(texture_handle),
         tile_size,
+        map_type,
         transform: Transform::from_xyz(0.0, 0.0, 0.0),
         anchor: TilemapAnchor::Center,
         ..Default::default()
     });
 
-    let mut placeable_map = map::PlaceableMap::default();
-    let center_x = map_size.x / 2;
-    let center_y = map_size.y / 2;
+    let land = match (loaded.as_ref(), scenario.as_ref()) {
+        (Some(loaded), _) => map::LandPlots {
+            purchased: loaded.0.plots_bought,
+            ..map::LandPlots::with_plots(&loaded.0.plots)
+        },
+        (None, Some(scenario)) if !scenario.0.starting_plots.is_empty() => {
+            map::LandPlots::with_plots(&scenario.0.starting_plots)
+        }
+        _ => map::LandPlots::starting(&map_size),
+    };
+    commands.insert_resource(land);
+    commands.insert_resource(outside_connections);
+}
 
-    for dx in -1..=1 {
-        for dy in -1..=1 {
-            let x = (center_x as i32 + dx) as u32;
-            let y = (center_y as i32 + dy) as u32;
-            let tile_pos = TilePos { x, y };
-            placeable_map.mark_placeable(tile_pos);
+/// Remove the tilemap of the city being left
+fn clear_map(mut commands: Commands, map_q: Query<(Entity, &TileStorage)>) {
+    for (map_entity, tile_storage) in map_q.iter() {
+        for tile_entity in tile_storage.iter().flatten() {
+            commands.entity(*tile_entity).despawn();
         }
+        commands.entity(map_entity).despawn();
     }
-
-    commands.insert_resource(placeable_map);
 }
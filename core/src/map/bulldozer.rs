@@ -0,0 +1,180 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::demolition::{Demolisher, DemolitionPlan, tiles_in_rect};
+use super::helpers::*;
+use super::plots::PlotTool;
+use super::resources::*;
+use crate::settings::{Action, ActionInput};
+
+const DRAG_RECT_COLOR: Color = Color::srgba(1.0, 0.3, 0.2, 0.9);
+const TARGET_COLOR: Color = Color::srgba(1.0, 0.1, 0.1, 0.7);
+/// How far, in tiles, a target's outline sits inside its footprint
+const TARGET_INSET: f32 = 0.03;
+
+/// The bulldozer: while active, dragging over the map clears every building in the rectangle
+#[derive(Resource, Debug, Default)]
+pub struct BulldozeTool {
+    pub active: bool,
+    /// Tile where the current drag started
+    pub drag_start: Option<TilePos>,
+    /// Tile under the cursor while dragging
+    pub drag_end: Option<TilePos>,
+    /// What releasing the mouse would demolish
+    pub preview: DemolitionPlan,
+}
+
+impl BulldozeTool {
+    pub fn put_away(&mut self) {
+        self.active = false;
+        self.drag_start = None;
+        self.drag_end = None;
+        self.preview = DemolitionPlan::default();
+    }
+}
+
+pub fn toggle_bulldoze_tool(
+    actions: ActionInput,
+    mut bulldozer: ResMut<BulldozeTool>,
+    mut plot_tool: ResMut<PlotTool>,
+    mut current_tile_type: ResMut<CurrentTileType>,
+) {
+    // Picking a building tool, the land tool or Esc puts the bulldozer away
+    if bulldozer.active
+        && ((current_tile_type.is_changed() && current_tile_type.texture_index != 0)
+            || (plot_tool.is_changed() && plot_tool.active)
+            || actions.just_pressed(Action::Cancel))
+    {
+        bulldozer.put_away();
+        return;
+    }
+
+    if actions.just_pressed(Action::Bulldoze) {
+        if bulldozer.active {
+            bulldozer.put_away();
+        } else {
+            bulldozer.active = true;
+            current_tile_type.texture_index = 0;
+            plot_tool.active = false;
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct BulldozeInputs<'w, 's> {
+    mouse_button: Res<'w, ButtonInput<MouseButton>>,
+    actions: ActionInput<'w>,
+    cursor_pos: Res<'w, CursorWorldPos>,
+    ui_click_blocker: Res<'w, UiClickBlocker>,
+    tilemap_q: Query<
+        'w,
+        's,
+        (
+            &'static TilemapSize,
+            &'static TilemapGridSize,
+            &'static TilemapTileSize,
+            &'static TilemapType,
+            &'static Transform,
+            &'static TilemapAnchor,
+        ),
+    >,
+}
+
+impl BulldozeInputs<'_, '_> {
+    fn hovered_tile(&self) -> Option<TilePos> {
+        let (map_size, grid_size, tile_size, map_type, map_transform, anchor) =
+            self.tilemap_q.iter().next()?;
+        let cursor_in_map_pos = cursor_to_map_pos(self.cursor_pos.0, map_transform);
+        TilePos::from_world_pos(
+            &cursor_in_map_pos,
+            map_size,
+            grid_size,
+            tile_size,
+            map_type,
+            anchor,
+        )
+    }
+}
+
+/// Press to start a rectangle, drag to grow it, release to demolish everything inside
+pub fn bulldoze_area_on_drag(
+    inputs: BulldozeInputs,
+    mut bulldozer: ResMut<BulldozeTool>,
+    mut demolisher: Demolisher,
+) {
+    if !bulldozer.active {
+        return;
+    }
+
+    let hovered = inputs.hovered_tile();
+
+    if inputs.mouse_button.just_pressed(MouseButton::Left)
+        && !inputs.ui_click_blocker.just_clicked_ui
+        // Shift-click still demolishes a single tile
+        && !inputs.actions.pressed(Action::Demolish)
+    {
+        bulldozer.drag_start = hovered;
+    }
+
+    let Some(start) = bulldozer.drag_start else {
+        return;
+    };
+
+    // Keep the last tile on the map when the cursor leaves it mid-drag
+    let end = hovered.or(bulldozer.drag_end).unwrap_or(start);
+    if bulldozer.drag_end != Some(end) {
+        bulldozer.drag_end = Some(end);
+        bulldozer.preview = demolisher.plan(tiles_in_rect(start, end));
+    }
+
+    if inputs.mouse_button.just_released(MouseButton::Left) {
+        // the clock kept running during the drag, so refunds and derelict lots may
+        // have changed since the preview was made
+        let plan = demolisher.plan(tiles_in_rect(start, end));
+        bulldozer.preview = DemolitionPlan::default();
+        bulldozer.drag_start = None;
+        bulldozer.drag_end = None;
+        demolisher.execute(&plan);
+    }
+}
+
+/// Outline the drag rectangle and every building it would take down
+pub fn draw_bulldoze_preview(
+    mut gizmos: Gizmos,
+    bulldozer: Res<BulldozeTool>,
+    map_q: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &Transform)>,
+) {
+    let (Some(start), Some(end)) = (bulldozer.drag_start, bulldozer.drag_end) else {
+        return;
+    };
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    let to_world = |point: Vec2| {
+        grid_point_to_world(point, map_size, grid_size, map_type, map_transform).truncate()
+    };
+    let corner = |tile_pos: &TilePos| Vec2::new(tile_pos.x as f32, tile_pos.y as f32);
+
+    let (min, max) = (
+        corner(&start).min(corner(&end)),
+        corner(&start).max(corner(&end)),
+    );
+    gizmos.linestrip_2d(
+        block_outline(min, max + Vec2::ONE, 0.0, to_world),
+        DRAG_RECT_COLOR,
+    );
+
+    for (tile_pos, _, building) in &bulldozer.preview.targets {
+        let footprint = building.footprint();
+        let size = Vec2::new(footprint.width as f32, footprint.height as f32);
+        let outline = block_outline(
+            corner(tile_pos),
+            corner(tile_pos) + size,
+            TARGET_INSET,
+            to_world,
+        );
+        gizmos.linestrip_2d(outline, TARGET_COLOR);
+    }
+}
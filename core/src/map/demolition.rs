@@ -1,30 +1,207 @@
+use super::derelict::clearance_cost;
 use super::helpers::*;
-use super::placeable_area::incremental_update_placeable_area;
 use super::resources::*;
-use crate::budget::{BuildingDemolished, BuildingType};
+use super::tile_state::{PlacedBuilding, TileState};
+use crate::budget::{
+    Budget, BudgetLedger, BuildingDemolished, LedgerCategory, TransactionFailed,
+    TransactionFailureReason,
+};
+use crate::settings::{Action, ActionInput};
 use crate::spatial::SpatialGrid;
-use crate::time::HelpOverlayState;
+use crate::time::GameClock;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
-type BuildingSpritesQuery<'w, 's> = Query<
-    'w,
-    's,
-    (
-        Entity,
-        Option<&'static ResidentialBuilding>,
-        Option<&'static CommercialBuilding>,
-        Option<&'static IndustryBuilding>,
-        Option<&'static RoadSegment>,
-        Option<&'static DecorativeBuilding>,
-    ),
->;
+/// Share of the construction cost paid back when a building comes down soon after going up
+const FRESH_REFUND_DIVISOR: i64 = 2;
+/// How many in-game hours a new building can still be taken down for a refund
+pub const REFUND_WINDOW_HOURS: u64 = 24;
+/// Share of the construction cost it takes to bulldoze an older building
+const DEMOLITION_COST_DIVISOR: i64 = 10;
+
+/// Money demolishing a building moves at `now_hour` (a `GameClock::hour_index`): positive
+/// is a refund, negative a cost. Derelict lots always cost their clearance fee.
+pub fn demolition_balance(building: &PlacedBuilding, derelict: bool, now_hour: u64) -> i64 {
+    let cost = building.building_type.cost();
+    if derelict {
+        -clearance_cost(building.building_type)
+    } else if now_hour.saturating_sub(building.placed_hour) < REFUND_WINDOW_HOURS {
+        cost / FRESH_REFUND_DIVISOR
+    } else {
+        -(cost / DEMOLITION_COST_DIVISOR)
+    }
+}
+
+/// What demolishing a set of tiles would remove and what it would cost
+#[derive(Debug, Clone, Default)]
+pub struct DemolitionPlan {
+    /// Origin tile of every building or derelict lot coming down, and what stands there
+    pub targets: Vec<(TilePos, TileState, PlacedBuilding)>,
+    /// Refunds minus demolition costs
+    pub balance: i64,
+}
+
+impl DemolitionPlan {
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+/// Work out what comes down on `tiles`, looking each one up with `standing`.
+/// A building is taken down whole as soon as any of its tiles is picked.
+pub fn plan_demolition(
+    tiles: impl IntoIterator<Item = TilePos>,
+    now_hour: u64,
+    standing: impl Fn(&TilePos) -> Option<(TileState, PlacedBuilding)>,
+) -> DemolitionPlan {
+    let mut plan = DemolitionPlan::default();
+
+    for tile_pos in tiles {
+        let Some((state, building)) = standing(&tile_pos) else {
+            continue;
+        };
+        if !state.is_developed() {
+            continue;
+        }
+        let origin = building.tile_pos;
+        if plan
+            .targets
+            .iter()
+            .any(|(planned, _, _)| *planned == origin)
+        {
+            continue;
+        }
+
+        let derelict = matches!(state, TileState::Abandoned(_));
+        plan.balance += demolition_balance(&building, derelict, now_hour);
+        plan.targets.push((origin, state, building));
+    }
+
+    plan
+}
+
+/// Every tile in the rectangle spanned by two corners
+pub fn tiles_in_rect(a: TilePos, b: TilePos) -> impl Iterator<Item = TilePos> {
+    let (min_x, max_x) = (a.x.min(b.x), a.x.max(b.x));
+    let (min_y, max_y) = (a.y.min(b.y), a.y.max(b.y));
+    (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| TilePos { x, y }))
+}
+
+/// Everything needed to take buildings off the map and settle the bill
+#[derive(SystemParam)]
+pub struct Demolisher<'w, 's> {
+    tilemap_q: Query<'w, 's, (&'static TileStorage, &'static TilemapSize)>,
+    tile_state_q: Query<'w, 's, &'static mut TileState>,
+    buildings_q: Query<'w, 's, &'static PlacedBuilding>,
+    placeable_map: Res<'w, PlaceableMap>,
+    spatial_grid: ResMut<'w, SpatialGrid>,
+    clock: Res<'w, GameClock>,
+    budget: ResMut<'w, Budget>,
+    ledger: ResMut<'w, BudgetLedger>,
+    demolished_writer: MessageWriter<'w, BuildingDemolished>,
+    failed_writer: MessageWriter<'w, TransactionFailed>,
+    commands: Commands<'w, 's>,
+}
+
+impl Demolisher<'_, '_> {
+    pub fn plan(&self, tiles: impl IntoIterator<Item = TilePos>) -> DemolitionPlan {
+        let Some((tile_storage, _)) = self.tilemap_q.iter().next() else {
+            return DemolitionPlan::default();
+        };
+
+        plan_demolition(tiles, self.clock.hour_index(), |tile_pos| {
+            let state = *self.tile_state_q.get(tile_storage.get(tile_pos)?).ok()?;
+            let (TileState::Occupied(entity) | TileState::Abandoned(entity)) = state else {
+                return None;
+            };
+            Some((state, *self.buildings_q.get(entity).ok()?))
+        })
+    }
+
+    /// Remove everything in the plan, or nothing if the city can't pay for it
+    pub fn execute(&mut self, plan: &DemolitionPlan) -> bool {
+        let Some(&(first_tile, _, _)) = plan.targets.first() else {
+            return false;
+        };
+        let Some((tile_storage, map_size)) = self.tilemap_q.iter().next() else {
+            return false;
+        };
+
+        let live = plan
+            .targets
+            .iter()
+            .filter(|(_, state, _)| matches!(state, TileState::Occupied(_)))
+            .map(|(_, _, building)| building.tiles().count() as u32)
+            .sum::<u32>();
+        if live > 0 && count_placed_tiles(tile_storage, &self.tile_state_q, map_size) <= live {
+            warn!("Cannot demolish the last tile!");
+            return false;
+        }
+
+        if plan.balance < 0 && !self.budget.can_afford(-plan.balance) {
+            warn!("Cannot afford to demolish! Cost: ${}", -plan.balance);
+            self.failed_writer.write(TransactionFailed {
+                reason: TransactionFailureReason::Unaffordable,
+                tile_pos: first_tile,
+            });
+            return false;
+        }
+
+        for (tile_pos, state, building) in &plan.targets {
+            for covered in building.tiles() {
+                let Some(tile_entity) = tile_storage.checked_get(&covered) else {
+                    continue;
+                };
+                if let Ok(mut tile_state) = self.tile_state_q.get_mut(tile_entity) {
+                    // the cleared tile is buildable again as long as the city owns its plot
+                    *tile_state = if self.placeable_map.is_placeable(&covered) {
+                        TileState::Placeable
+                    } else {
+                        TileState::Empty
+                    };
+                }
+            }
+
+            match *state {
+                TileState::Occupied(entity) => {
+                    self.demolished_writer.write(BuildingDemolished {
+                        building_type: building.building_type,
+                        tile_pos: *tile_pos,
+                        footprint: building.footprint(),
+                    });
+                    self.commands.entity(entity).despawn();
+                }
+                TileState::Abandoned(entity) => {
+                    self.spatial_grid
+                        .remove_derelict(tile_pos, building.footprint());
+                    self.commands.entity(entity).despawn();
+                }
+                _ => {}
+            }
+        }
+
+        if plan.balance != 0 {
+            self.ledger.book(
+                &mut self.budget,
+                self.clock.day,
+                LedgerCategory::Demolition,
+                plan.balance,
+            );
+        }
+        info!(
+            "Demolished {} buildings for {:+}",
+            plan.targets.len(),
+            plan.balance
+        );
+        true
+    }
+}
 
 #[derive(SystemParam)]
 pub struct DemolitionInputs<'w, 's> {
     mouse_button: Res<'w, ButtonInput<MouseButton>>,
-    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    actions: ActionInput<'w>,
     cursor_pos: Res<'w, CursorWorldPos>,
     ui_click_blocker: Res<'w, UiClickBlocker>,
     tilemap_q: Query<
@@ -35,28 +212,14 @@ pub struct DemolitionInputs<'w, 's> {
             &'static TilemapGridSize,
             &'static TilemapTileSize,
             &'static TilemapType,
-            &'static TileStorage,
             &'static Transform,
             &'static TilemapAnchor,
         ),
     >,
-    tile_texture_q: Query<'w, 's, &'static mut TileTextureIndex>,
 }
 
-pub fn demolish_tile_on_click(
-    mut inputs: DemolitionInputs,
-    mut placeable_map: ResMut<PlaceableMap>,
-    spatial_grid: Res<SpatialGrid>,
-    mut demolished_writer: MessageWriter<BuildingDemolished>,
-    mut commands: Commands,
-    building_sprites_q: BuildingSpritesQuery<'_, '_>,
-    help_state: Option<Res<HelpOverlayState>>,
-) {
-    if let Some(state) = help_state
-        && state.active
-    {
-        return;
-    }
+/// Shift-click demolishes the single building under the cursor
+pub fn demolish_tile_on_click(inputs: DemolitionInputs, mut demolisher: Demolisher) {
     if !inputs.mouse_button.just_pressed(MouseButton::Left) {
         return;
     }
@@ -65,13 +228,11 @@ pub fn demolish_tile_on_click(
         return;
     }
 
-    if !inputs.keyboard.pressed(KeyCode::ShiftLeft) && !inputs.keyboard.pressed(KeyCode::ShiftRight)
-    {
+    if !inputs.actions.pressed(Action::Demolish) {
         return;
     }
 
-    for (map_size, grid_size, tile_size, map_type, tile_storage, map_transform, anchor) in
-        inputs.tilemap_q.iter()
+    for (map_size, grid_size, tile_size, map_type, map_transform, anchor) in inputs.tilemap_q.iter()
     {
         let cursor_in_map_pos = cursor_to_map_pos(inputs.cursor_pos.0, map_transform);
 
@@ -82,85 +243,90 @@ pub fn demolish_tile_on_click(
             tile_size,
             map_type,
             anchor,
-        ) && let Some(tile_entity) = tile_storage.get(&tile_pos)
-        {
-            let current_texture = if let Ok(texture) = inputs.tile_texture_q.get(tile_entity) {
-                texture.0
-            } else {
-                return;
-            };
+        ) {
+            let plan = demolisher.plan([tile_pos]);
+            demolisher.execute(&plan);
+        }
+    }
+}
 
-            if current_texture >= 2 {
-                let placed_tile_count =
-                    count_placed_tiles(tile_storage, &inputs.tile_texture_q, map_size);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::{BuildingType, Rotation};
 
-                if placed_tile_count <= 1 {
-                    warn!("Cannot demolish the last tile!");
-                    return;
-                }
+    fn building(building_type: BuildingType, placed_hour: u64) -> PlacedBuilding {
+        PlacedBuilding {
+            building_type,
+            variant: 0,
+            tile_pos: TilePos { x: 0, y: 0 },
+            rotation: Rotation::North,
+            placed_day: (placed_hour / 24) as u32 + 1,
+            placed_hour,
+        }
+    }
 
-                let should_be_placeable = is_within_range_of_placed_tile(&tile_pos, &spatial_grid);
+    #[test]
+    fn fresh_buildings_refund_and_old_ones_cost() {
+        // put up at 22:00 on day 5
+        let house = building(BuildingType::Residential, 4 * 24 + 22);
 
-                if let Ok(mut texture_index) = inputs.tile_texture_q.get_mut(tile_entity) {
-                    if should_be_placeable {
-                        texture_index.0 = 1;
-                    } else {
-                        texture_index.0 = 0;
-                        placeable_map.placeable_tiles.remove(&tile_pos);
-                    }
-
-                    // Detect building type from sprite component for accurate event
-                    let mut detected_type: Option<BuildingType> = None;
-                    for (entity, residential, commercial, industry, road, decorative) in
-                        building_sprites_q.iter()
-                    {
-                        if let Some(b) = residential
-                            && b.tile_pos == tile_pos
-                        {
-                            detected_type = Some(BuildingType::Residential);
-                            commands.entity(entity).despawn();
-                        } else if let Some(b) = commercial
-                            && b.tile_pos == tile_pos
-                        {
-                            detected_type = Some(BuildingType::Commercial);
-                            commands.entity(entity).despawn();
-                        } else if let Some(b) = industry
-                            && b.tile_pos == tile_pos
-                        {
-                            detected_type = Some(BuildingType::Industry);
-                            commands.entity(entity).despawn();
-                        } else if let Some(b) = road
-                            && b.tile_pos == tile_pos
-                        {
-                            detected_type = Some(BuildingType::Road);
-                            commands.entity(entity).despawn();
-                        } else if let Some(b) = decorative
-                            && b.tile_pos == tile_pos
-                        {
-                            detected_type = Some(BuildingType::Decorative);
-                            commands.entity(entity).despawn();
-                        }
-                    }
-
-                    if let Some(building_type) = detected_type {
-                        demolished_writer.write(BuildingDemolished {
-                            building_type,
-                            tile_pos,
-                        });
-                    }
-
-                    info!("Demolished tile at {:?}", tile_pos);
-
-                    incremental_update_placeable_area(
-                        tile_pos,
-                        &mut placeable_map,
-                        &spatial_grid,
-                        tile_storage,
-                        &inputs.tile_texture_q,
-                        map_size,
-                    );
-                }
-            }
-        }
+        assert_eq!(demolition_balance(&house, false, 4 * 24 + 22), 500);
+        // still within a day after midnight
+        assert_eq!(demolition_balance(&house, false, 5 * 24 + 21), 500);
+        assert_eq!(demolition_balance(&house, false, 5 * 24 + 22), -100);
+        assert_eq!(
+            demolition_balance(&house, true, 4 * 24 + 22),
+            -clearance_cost(BuildingType::Residential)
+        );
+    }
+
+    #[test]
+    fn plan_covers_only_developed_tiles_in_the_rectangle() {
+        let entity = Entity::PLACEHOLDER;
+        let tiles: Vec<_> = tiles_in_rect(TilePos { x: 2, y: 1 }, TilePos { x: 0, y: 0 }).collect();
+        assert_eq!(tiles.len(), 6);
+
+        let plan = plan_demolition(tiles, 72, |tile_pos| match (tile_pos.x, tile_pos.y) {
+            (0, 0) => Some((
+                TileState::Occupied(entity),
+                building(BuildingType::Road, 60),
+            )),
+            (1, 1) => Some((
+                TileState::Occupied(entity),
+                PlacedBuilding {
+                    tile_pos: *tile_pos,
+                    ..building(BuildingType::Industry, 24)
+                },
+            )),
+            (2, 0) => Some((TileState::Placeable, building(BuildingType::Road, 0))),
+            _ => None,
+        });
+
+        assert_eq!(plan.targets.len(), 2);
+        assert_eq!(plan.balance, 25 - 200);
+    }
+
+    #[test]
+    fn a_multi_tile_building_comes_down_once_with_all_its_tiles() {
+        let stadium = PlacedBuilding {
+            tile_pos: TilePos { x: 4, y: 4 },
+            ..building(BuildingType::Stadium, 48)
+        };
+        let covered: Vec<_> = stadium.tiles().collect();
+
+        let plan = plan_demolition(
+            tiles_in_rect(TilePos { x: 3, y: 3 }, TilePos { x: 6, y: 6 }),
+            216,
+            |tile_pos| {
+                covered
+                    .contains(tile_pos)
+                    .then_some((TileState::Occupied(Entity::PLACEHOLDER), stadium))
+            },
+        );
+
+        assert_eq!(plan.targets.len(), 1);
+        assert_eq!(plan.targets[0].0, TilePos { x: 4, y: 4 });
+        assert_eq!(plan.balance, -800);
     }
 }
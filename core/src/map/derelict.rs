@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TileStorage;
+
+use super::resources::{insert_building_marker, remove_building_marker};
+use super::tile_state::{PlacedBuilding, TileState};
+use crate::budget::BuildingType;
+use crate::spatial::SpatialGrid;
+
+/// Tint applied to the sprite of an abandoned building
+const DERELICT_TINT: Color = Color::srgb(0.45, 0.42, 0.4);
+
+/// Share of the construction cost it takes to clear a derelict lot
+const CLEARANCE_COST_DIVISOR: i64 = 4;
+
+/// An abandoned building. The entity keeps its sprite and `PlacedBuilding`,
+/// but loses its per-type marker so it no longer counts as housing or jobs.
+#[derive(Component, Debug)]
+pub struct Derelict {
+    pub since_day: u32,
+}
+
+pub fn clearance_cost(building_type: BuildingType) -> i64 {
+    building_type.cost() / CLEARANCE_COST_DIVISOR
+}
+
+/// Turn the live building `entity` into a derelict lot on every tile it covers.
+/// Callers still announce the lost building with `BuildingDemolished`.
+pub fn abandon_building(
+    commands: &mut Commands,
+    spatial_grid: &mut SpatialGrid,
+    tile_storage: &TileStorage,
+    tile_state_q: &mut Query<&mut TileState>,
+    entity: Entity,
+    building: &PlacedBuilding,
+    day: u32,
+) {
+    let mut building_commands = commands.entity(entity);
+    remove_building_marker(&mut building_commands, building.building_type);
+    building_commands.insert(Derelict { since_day: day });
+
+    spatial_grid.insert_derelict(building.tile_pos, building.footprint());
+    set_footprint_state(
+        tile_storage,
+        tile_state_q,
+        building,
+        TileState::Abandoned(entity),
+    );
+}
+
+/// Bring a derelict lot back into use as its original building type.
+/// Callers announce the restored building with `BuildingPlaced`.
+pub fn restore_building(
+    commands: &mut Commands,
+    spatial_grid: &mut SpatialGrid,
+    tile_storage: &TileStorage,
+    tile_state_q: &mut Query<&mut TileState>,
+    entity: Entity,
+    building: &PlacedBuilding,
+) {
+    let mut building_commands = commands.entity(entity);
+    building_commands.remove::<Derelict>();
+    insert_building_marker(
+        &mut building_commands,
+        building.building_type,
+        building.tile_pos,
+    );
+
+    spatial_grid.remove_derelict(&building.tile_pos, building.footprint());
+    set_footprint_state(
+        tile_storage,
+        tile_state_q,
+        building,
+        TileState::Occupied(entity),
+    );
+}
+
+fn set_footprint_state(
+    tile_storage: &TileStorage,
+    tile_state_q: &mut Query<&mut TileState>,
+    building: &PlacedBuilding,
+    state: TileState,
+) {
+    for tile_pos in building.tiles() {
+        if let Some(tile_entity) = tile_storage.get(&tile_pos)
+            && let Ok(mut tile_state) = tile_state_q.get_mut(tile_entity)
+        {
+            *tile_state = state;
+        }
+    }
+}
+
+/// Grey out freshly abandoned buildings and restore the colour of recovered ones
+pub fn update_derelict_tint(
+    mut added_q: Query<&mut Sprite, (With<Derelict>, Added<Derelict>)>,
+    mut recovered: RemovedComponents<Derelict>,
+    mut sprites_q: Query<&mut Sprite, Without<Derelict>>,
+) {
+    for mut sprite in added_q.iter_mut() {
+        sprite.color = DERELICT_TINT;
+    }
+
+    for entity in recovered.read() {
+        if let Ok(mut sprite) = sprites_q.get_mut(entity) {
+            sprite.color = Color::WHITE;
+        }
+    }
+}
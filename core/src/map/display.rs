@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 
-use super::resources::{CurrentTileType, UiClickBlocker};
+use super::bulldozer::BulldozeTool;
+use super::plots::{LandPlots, PlotTool};
+use super::resources::{CurrentRotation, CurrentTileType, UiClickBlocker};
 use crate::budget::BuildingType;
-use crate::time::HelpOverlayState;
+use crate::progression::Progression;
+use crate::settings::{Action, KeyBindings};
 
 type TileSelectInteractionQuery<'w, 's> = Query<
     'w,
@@ -14,11 +17,36 @@ type TileSelectInteractionQuery<'w, 's> = Query<
 #[derive(Component)]
 pub struct SelectedTileDisplayText;
 
+#[derive(Component)]
+pub struct PlotToolButton;
+
+#[derive(Component)]
+pub struct BulldozeToolButton;
+
+/// Build bar color of the buy-plot button
+const PLOT_BUTTON_COLOR: Color = Color::srgba(0.75, 0.65, 0.45, 1.0);
+/// Build bar color of the bulldozer button
+const BULLDOZE_BUTTON_COLOR: Color = Color::srgba(0.85, 0.45, 0.35, 1.0);
+
 #[derive(Component)]
 pub struct TileSelectButton {
     building_type: BuildingType,
 }
 
+/// The key action that picks a building type
+fn select_action(building_type: BuildingType) -> Action {
+    match building_type {
+        BuildingType::Residential => Action::SelectResidential,
+        BuildingType::Commercial => Action::SelectCommercial,
+        BuildingType::Industry => Action::SelectIndustry,
+        BuildingType::Road => Action::SelectRoad,
+        BuildingType::Decorative => Action::SelectDecorative,
+        BuildingType::BusStop => Action::SelectBusStop,
+        BuildingType::School => Action::SelectSchool,
+        BuildingType::Stadium => Action::SelectStadium,
+    }
+}
+
 fn button_base_color(building_type: BuildingType) -> Color {
     match building_type {
         BuildingType::Residential => Color::srgba(0.6, 0.8, 1.0, 1.0),
@@ -26,6 +54,9 @@ fn button_base_color(building_type: BuildingType) -> Color {
         BuildingType::Industry => Color::srgba(1.0, 0.9, 0.6, 1.0),
         BuildingType::Road => Color::srgba(0.8, 0.8, 0.8, 1.0),
         BuildingType::Decorative => Color::srgba(1.0, 0.6, 0.9, 1.0),
+        BuildingType::BusStop => Color::srgba(0.95, 0.75, 0.2, 1.0),
+        BuildingType::School => Color::srgba(0.9, 0.5, 0.45, 1.0),
+        BuildingType::Stadium => Color::srgba(0.45, 0.8, 0.5, 1.0),
     }
 }
 
@@ -58,7 +89,11 @@ pub fn setup_selected_tile_display(mut commands: Commands, asset_server: Res<Ass
         });
 }
 
-pub fn setup_tile_select_buttons(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn setup_tile_select_buttons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<KeyBindings>,
+) {
     let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
 
     let container = commands
@@ -109,36 +144,87 @@ pub fn setup_tile_select_buttons(mut commands: Commands, asset_server: Res<Asset
         });
     };
 
-    spawn_button(
-        container,
+    for building_type in [
         BuildingType::Residential,
-        "R",
-        &font,
-        &mut commands,
-    );
-    spawn_button(
-        container,
         BuildingType::Commercial,
-        "C",
-        &font,
-        &mut commands,
-    );
-    spawn_button(container, BuildingType::Industry, "I", &font, &mut commands);
-    spawn_button(container, BuildingType::Road, "O", &font, &mut commands);
-    spawn_button(
-        container,
+        BuildingType::Industry,
+        BuildingType::Road,
         BuildingType::Decorative,
-        "B",
-        &font,
-        &mut commands,
-    );
+        BuildingType::BusStop,
+        BuildingType::School,
+        BuildingType::Stadium,
+    ] {
+        let label = bindings.short_label(select_action(building_type));
+        spawn_button(container, building_type, &label, &font, &mut commands);
+    }
+
+    commands.entity(container).with_children(|parent| {
+        parent
+            .spawn((
+                Button,
+                Node {
+                    width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(PLOT_BUTTON_COLOR),
+                PlotToolButton,
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new(bindings.short_label(Action::BuyLand)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::BLACK),
+                ));
+            });
+        parent
+            .spawn((
+                Button,
+                Node {
+                    width: Val::Px(32.0),
+                    height: Val::Px(32.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(BULLDOZE_BUTTON_COLOR),
+                BulldozeToolButton,
+            ))
+            .with_children(|button_parent| {
+                button_parent.spawn((
+                    Text::new(bindings.short_label(Action::Bulldoze)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::BLACK),
+                ));
+            });
+    });
 }
 
 pub fn update_selected_tile_display(
     current_tile_type: Res<CurrentTileType>,
+    current_rotation: Res<CurrentRotation>,
+    plot_tool: Res<PlotTool>,
+    bulldozer: Res<BulldozeTool>,
+    plots: Res<LandPlots>,
+    bindings: Res<KeyBindings>,
     mut query: Query<&mut Text, With<SelectedTileDisplayText>>,
 ) {
-    if !current_tile_type.is_changed() {
+    if !current_tile_type.is_changed()
+        && !current_rotation.is_changed()
+        && !plot_tool.is_changed()
+        && !bulldozer.is_changed()
+        && !plots.is_changed()
+    {
         return;
     }
 
@@ -146,29 +232,68 @@ pub fn update_selected_tile_display(
         return;
     };
 
-    let label = match BuildingType::from_selection_index(current_tile_type.texture_index) {
-        Some(BuildingType::Residential) => "Residential (R)",
-        Some(BuildingType::Commercial) => "Commercial (C)",
-        Some(BuildingType::Industry) => "Industry (I)",
-        Some(BuildingType::Road) => "Road (O)",
-        Some(BuildingType::Decorative) => "Decorative (B)",
-        None => "None",
+    if plot_tool.active {
+        text.0 = format!(
+            "Selected: Buy land ({}) ${}",
+            bindings.label(Action::BuyLand),
+            plots.price()
+        );
+        return;
+    }
+
+    if bulldozer.active {
+        let preview = &bulldozer.preview;
+        let count = preview.targets.len();
+        let noun = if count == 1 { "building" } else { "buildings" };
+        text.0 = if preview.is_empty() {
+            format!("Selected: Bulldoze ({})", bindings.label(Action::Bulldoze))
+        } else if preview.balance >= 0 {
+            format!("Bulldoze: {} {}, refund ${}", count, noun, preview.balance)
+        } else {
+            format!("Bulldoze: {} {}, costs ${}", count, noun, -preview.balance)
+        };
+        return;
+    }
+
+    let Some(building_type) = BuildingType::from_selection_index(current_tile_type.texture_index)
+    else {
+        text.0 = "Selected: None".to_string();
+        return;
+    };
+    let name = match building_type {
+        BuildingType::Residential => "Residential",
+        BuildingType::Commercial => "Commercial",
+        BuildingType::Industry => "Industry",
+        BuildingType::Road => "Road",
+        BuildingType::Decorative => "Decorative",
+        BuildingType::BusStop => "Bus Stop",
+        BuildingType::School => "School",
+        BuildingType::Stadium => "Stadium",
     };
 
-    text.0 = format!("Selected: {}", label);
+    text.0 = format!(
+        "Selected: {} ({})",
+        name,
+        bindings.label(select_action(building_type))
+    );
+
+    let footprint = building_type.footprint();
+    if !footprint.is_single() {
+        let rotated = footprint.rotated(current_rotation.rotation);
+        text.0.push_str(&format!(
+            " {}x{}, {} to rotate",
+            rotated.width,
+            rotated.height,
+            bindings.label(Action::Rotate)
+        ));
+    }
 }
 
 pub fn handle_tile_select_button_presses(
     mut interaction_q: TileSelectInteractionQuery<'_, '_>,
     mut current_tile_type: ResMut<CurrentTileType>,
     mut ui_click_blocker: ResMut<UiClickBlocker>,
-    help_state: Option<Res<HelpOverlayState>>,
 ) {
-    if let Some(state) = help_state
-        && state.active
-    {
-        return;
-    }
     for (interaction, button) in interaction_q.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
@@ -179,6 +304,9 @@ pub fn handle_tile_select_button_presses(
                     BuildingType::Industry => 4,
                     BuildingType::Road => 5,
                     BuildingType::Decorative => 6,
+                    BuildingType::BusStop => 7,
+                    BuildingType::School => 8,
+                    BuildingType::Stadium => 9,
                 };
             }
             Interaction::Hovered => {}
@@ -189,9 +317,10 @@ pub fn handle_tile_select_button_presses(
 
 pub fn update_tile_select_button_colors(
     current_tile_type: Res<CurrentTileType>,
+    progression: Res<Progression>,
     mut query: Query<(&TileSelectButton, &mut BackgroundColor)>,
 ) {
-    if !current_tile_type.is_changed() {
+    if !current_tile_type.is_changed() && !progression.is_changed() {
         return;
     }
 
@@ -201,9 +330,72 @@ pub fn update_tile_select_button_colors(
         if Some(button.building_type) == active_type {
             // Highlight currently selected tile type
             bg.0 = Color::srgba(1.0, 1.0, 1.0, 1.0);
+        } else if !progression.is_unlocked(button.building_type) {
+            // Dim types that a milestone still has to unlock
+            bg.0 = Color::srgba(0.3, 0.3, 0.3, 1.0);
         } else {
             // Reset to base color for other buttons
             bg.0 = button_base_color(button.building_type);
         }
     }
 }
+
+pub fn handle_plot_tool_button(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<PlotToolButton>)>,
+    mut plot_tool: ResMut<PlotTool>,
+    mut current_tile_type: ResMut<CurrentTileType>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+    mut button_q: Query<&mut BackgroundColor, With<PlotToolButton>>,
+) {
+    for interaction in interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_click_blocker.just_clicked_ui = true;
+            plot_tool.active = !plot_tool.active;
+            if plot_tool.active {
+                current_tile_type.texture_index = 0;
+            }
+        }
+    }
+
+    if plot_tool.is_changed() {
+        for mut bg in button_q.iter_mut() {
+            bg.0 = if plot_tool.active {
+                Color::WHITE
+            } else {
+                PLOT_BUTTON_COLOR
+            };
+        }
+    }
+}
+
+pub fn handle_bulldoze_tool_button(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<BulldozeToolButton>)>,
+    mut bulldozer: ResMut<BulldozeTool>,
+    mut plot_tool: ResMut<PlotTool>,
+    mut current_tile_type: ResMut<CurrentTileType>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+    mut button_q: Query<&mut BackgroundColor, With<BulldozeToolButton>>,
+) {
+    for interaction in interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_click_blocker.just_clicked_ui = true;
+            if bulldozer.active {
+                bulldozer.put_away();
+            } else {
+                bulldozer.active = true;
+                current_tile_type.texture_index = 0;
+                plot_tool.active = false;
+            }
+        }
+    }
+
+    if bulldozer.is_changed() {
+        for mut bg in button_q.iter_mut() {
+            bg.0 = if bulldozer.active {
+                Color::WHITE
+            } else {
+                BULLDOZE_BUTTON_COLOR
+            };
+        }
+    }
+}
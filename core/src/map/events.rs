@@ -1,4 +1,4 @@
-use crate::budget::BuildingType;
+use crate::budget::{BuildingType, Rotation};
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::TilePos;
 
@@ -6,4 +6,5 @@ use bevy_ecs_tilemap::prelude::TilePos;
 pub struct PlacementIntent {
     pub tile_pos: TilePos,
     pub building_type: BuildingType,
+    pub rotation: Rotation,
 }
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ecs_tilemap::prelude::*;
+use std::time::Duration;
+
+use super::tile_state::{TileState, tile_tint};
+use crate::budget::TransactionFailed;
+
+/// Real-time seconds a failed tile stays tinted red
+const FLASH_SECONDS: f32 = 0.6;
+const FLASH_COLOR: Color = Color::srgb(1.0, 0.25, 0.25);
+
+/// Real-time seconds the floating reason label is visible
+const LABEL_SECONDS: f32 = 1.5;
+/// How far (in pixels) the label drifts up while fading out
+const LABEL_RISE_PX: f32 = 30.0;
+/// Offset from the cursor so the label doesn't sit under the pointer
+const LABEL_CURSOR_OFFSET: Vec2 = Vec2::new(16.0, -8.0);
+
+const ERROR_TONE_HZ: f32 = 180.0;
+const ERROR_TONE_MS: u64 = 120;
+
+#[derive(Component)]
+pub struct FailedTileFlash {
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct FailureLabel {
+    timer: Timer,
+    origin: Vec2,
+}
+
+#[derive(Resource)]
+pub struct ErrorFeedbackAssets {
+    font: Handle<Font>,
+    tone: Handle<Pitch>,
+}
+
+pub fn setup_error_feedback(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pitches: ResMut<Assets<Pitch>>,
+) {
+    commands.insert_resource(ErrorFeedbackAssets {
+        font: asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf"),
+        tone: pitches.add(Pitch::new(
+            ERROR_TONE_HZ,
+            Duration::from_millis(ERROR_TONE_MS),
+        )),
+    });
+}
+
+/// Flash the tile, float the reason next to the cursor and buzz once per failed click
+pub fn react_to_failed_transactions(
+    mut commands: Commands,
+    assets: Res<ErrorFeedbackAssets>,
+    mut failed_reader: MessageReader<TransactionFailed>,
+    tile_storage_q: Query<&TileStorage>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+) {
+    let mut any_failed = false;
+    let cursor = window_q
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    for event in failed_reader.read() {
+        any_failed = true;
+
+        for tile_storage in tile_storage_q.iter() {
+            if let Some(tile_entity) = tile_storage.get(&event.tile_pos) {
+                commands.entity(tile_entity).insert(FailedTileFlash {
+                    timer: Timer::from_seconds(FLASH_SECONDS, TimerMode::Once),
+                });
+            }
+        }
+
+        let Some(cursor) = cursor else {
+            continue;
+        };
+        let origin = cursor + LABEL_CURSOR_OFFSET;
+
+        commands.spawn((
+            Text::new(event.reason.label()),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(origin.x),
+                top: Val::Px(origin.y),
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                ..default()
+            },
+            TextFont {
+                font: assets.font.clone(),
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(FLASH_COLOR),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            FailureLabel {
+                timer: Timer::from_seconds(LABEL_SECONDS, TimerMode::Once),
+                origin,
+            },
+        ));
+    }
+
+    if any_failed {
+        commands.spawn((AudioPlayer(assets.tone.clone()), PlaybackSettings::DESPAWN));
+    }
+}
+
+/// Fade flashing tiles from red back to their usual tint; runs after hover
+/// highlighting so it wins
+pub fn update_failed_tile_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_q: Query<(Entity, &mut FailedTileFlash, &mut TileColor, &TileState)>,
+) {
+    for (entity, mut flash, mut color, state) in flash_q.iter_mut() {
+        let tint = tile_tint(*state);
+        if flash.timer.tick(time.delta()).is_finished() {
+            color.0 = tint;
+            commands.entity(entity).remove::<FailedTileFlash>();
+            continue;
+        }
+
+        color.0 = FLASH_COLOR.mix(&tint, flash.timer.fraction());
+    }
+}
+
+pub fn update_failure_labels(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut label_q: Query<(
+        Entity,
+        &mut FailureLabel,
+        &mut Node,
+        &mut TextColor,
+        &mut BackgroundColor,
+    )>,
+) {
+    for (entity, mut label, mut node, mut text_color, mut background) in label_q.iter_mut() {
+        if label.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = label.timer.fraction();
+        node.top = Val::Px(label.origin.y - LABEL_RISE_PX * progress);
+        text_color.0 = FLASH_COLOR.with_alpha(1.0 - progress);
+        background.0 = Color::srgba(0.0, 0.0, 0.0, 0.7 * (1.0 - progress));
+    }
+}
@@ -1,35 +1,179 @@
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
-use crate::spatial::SpatialGrid;
+use super::tile_state::TileState;
+use crate::budget::Footprint;
 
-const PLACEABLE_EXPANSION_RADIUS: i32 = 2;
+/// Fraction of a layer's z range used to sort building sprites front to back
+const DEPTH_SPREAD: f32 = 0.9;
 
+/// Undo the map transform so the cursor can be handed to `TilePos::from_world_pos`,
+/// which takes care of the map's projection
 pub fn cursor_to_map_pos(cursor_pos: Vec2, map_transform: &Transform) -> Vec2 {
     let cursor_pos = Vec4::from((cursor_pos, 0.0, 1.0));
     let cursor_in_map_pos = map_transform.to_matrix().inverse() * cursor_pos;
     cursor_in_map_pos.xy()
 }
 
+/// Map-local center of the first tile and the steps to its neighbours along x and y.
+/// Both projections we use are affine, so these three vectors place any point.
+fn grid_basis(
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> (Vec2, Vec2, Vec2) {
+    let tile_size = TilemapTileSize {
+        x: grid_size.x,
+        y: grid_size.y,
+    };
+    let center = |x, y| {
+        TilePos { x, y }.center_in_world(
+            map_size,
+            grid_size,
+            &tile_size,
+            map_type,
+            &TilemapAnchor::Center,
+        )
+    };
+
+    let first = center(0, 0);
+    (first, center(1, 0) - first, center(0, 1) - first)
+}
+
+/// Convert a point in tile units into world space for a centered tilemap. Tile `(x, y)`
+/// spans `x..x + 1` and `y..y + 1`.
+pub fn grid_point_to_world(
+    point: Vec2,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+    map_transform: &Transform,
+) -> Vec3 {
+    let (first, step_x, step_y) = grid_basis(map_size, grid_size, map_type);
+    let local = first + step_x * (point.x - 0.5) + step_y * (point.y - 0.5);
+
+    map_transform.transform_point(local.extend(0.0))
+}
+
+/// Inverse of `grid_point_to_world`: where a world position falls in tile units. Unlike
+/// `TilePos::from_world_pos` it keeps the fraction and goes on past the map's edges.
+pub fn world_to_grid_point(
+    world: Vec2,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+    map_transform: &Transform,
+) -> Vec2 {
+    let (first, step_x, step_y) = grid_basis(map_size, grid_size, map_type);
+    let local = cursor_to_map_pos(world, map_transform);
+
+    Mat2::from_cols(step_x, step_y).inverse() * (local - first) + Vec2::splat(0.5)
+}
+
+/// Closed outline of the block of tiles spanning `min..max` in tile units, pulled in by
+/// `inset` tiles, ready for `Gizmos::linestrip_2d`
+pub fn block_outline(
+    min: Vec2,
+    max: Vec2,
+    inset: f32,
+    to_world: impl Fn(Vec2) -> Vec2,
+) -> [Vec2; 5] {
+    let (min, max) = (min + Vec2::splat(inset), max - Vec2::splat(inset));
+    let corners = [
+        min,
+        Vec2::new(max.x, min.y),
+        max,
+        Vec2::new(min.x, max.y),
+        min,
+    ];
+    corners.map(to_world)
+}
+
 /// Convert a `TilePos` into the world-space center of that tile for a centered tilemap
 pub fn tile_center_to_world(
     tile_pos: &TilePos,
     map_size: &TilemapSize,
     grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
     map_transform: &Transform,
 ) -> Vec3 {
-    let half_width = map_size.x as f32 * grid_size.x / 2.0;
-    let half_height = map_size.y as f32 * grid_size.y / 2.0;
+    let center = Vec2::new(tile_pos.x as f32 + 0.5, tile_pos.y as f32 + 0.5);
+    grid_point_to_world(center, map_size, grid_size, map_type, map_transform)
+}
 
-    let x = -half_width + (tile_pos.x as f32 + 0.5) * grid_size.x;
-    let y = -half_height + (tile_pos.y as f32 + 0.5) * grid_size.y;
+/// World-space center of a footprint whose bottom-left tile is `origin`
+pub fn footprint_center_to_world(
+    origin: &TilePos,
+    footprint: Footprint,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+    map_transform: &Transform,
+) -> Vec3 {
+    let center = Vec2::new(
+        origin.x as f32 + footprint.width as f32 / 2.0,
+        origin.y as f32 + footprint.height as f32 / 2.0,
+    );
+    grid_point_to_world(center, map_size, grid_size, map_type, map_transform)
+}
 
-    map_transform.transform_point(Vec3::new(x, y, 0.0))
+/// Map-local y of the lowest on-screen corner of a block of tiles
+fn lowest_corner(
+    origin: &TilePos,
+    footprint: Footprint,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> f32 {
+    let (x0, y0) = (origin.x as f32, origin.y as f32);
+    let (x1, y1) = (x0 + footprint.width as f32, y0 + footprint.height as f32);
+    [(x0, y0), (x1, y0), (x0, y1), (x1, y1)]
+        .into_iter()
+        .map(|(x, y)| {
+            grid_point_to_world(
+                Vec2::new(x, y),
+                map_size,
+                grid_size,
+                map_type,
+                &Transform::IDENTITY,
+            )
+            .y
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Z for a sprite standing on a footprint: its layer, nudged down the further up the
+/// screen its front edge is, so tall sprites in front cover the ones behind them.
+/// Stays within `layer - 1.0..=layer`.
+pub fn depth_sorted_z(
+    layer: f32,
+    origin: &TilePos,
+    footprint: Footprint,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> f32 {
+    let whole_map = Footprint {
+        width: map_size.x,
+        height: map_size.y,
+    };
+    let front = lowest_corner(origin, footprint, map_size, grid_size, map_type);
+    let bottom = lowest_corner(
+        &TilePos { x: 0, y: 0 },
+        whole_map,
+        map_size,
+        grid_size,
+        map_type,
+    );
+    let height = -2.0 * bottom;
+
+    layer - DEPTH_SPREAD * (front - bottom) / height.max(f32::EPSILON)
 }
 
+/// Count tiles that hold a live building; abandoned lots don't count
 pub fn count_placed_tiles(
     tile_storage: &TileStorage,
-    tile_texture_q: &Query<&mut TileTextureIndex>,
+    tile_state_q: &Query<&mut TileState>,
     map_size: &TilemapSize,
 ) -> u32 {
     let mut count = 0;
@@ -39,8 +183,7 @@ pub fn count_placed_tiles(
             let tile_pos = TilePos { x, y };
 
             if let Some(tile_entity) = tile_storage.get(&tile_pos)
-                && let Ok(texture) = tile_texture_q.get(tile_entity)
-                && texture.0 >= 2
+                && let Ok(TileState::Occupied(_)) = tile_state_q.get(tile_entity)
             {
                 count += 1;
             }
@@ -50,6 +193,97 @@ pub fn count_placed_tiles(
     count
 }
 
-pub fn is_within_range_of_placed_tile(tile_pos: &TilePos, spatial_grid: &SpatialGrid) -> bool {
-    spatial_grid.has_building_in_radius(tile_pos, PLACEABLE_EXPANSION_RADIUS)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP_SIZE: TilemapSize = TilemapSize { x: 8, y: 6 };
+
+    fn grids() -> [(TilemapGridSize, TilemapType); 2] {
+        [
+            (TilemapGridSize { x: 96.0, y: 96.0 }, TilemapType::Square),
+            (
+                TilemapGridSize { x: 96.0, y: 48.0 },
+                TilemapType::Isometric(IsoCoordSystem::Diamond),
+            ),
+        ]
+    }
+
+    #[test]
+    fn the_cursor_over_a_tile_center_picks_that_tile() {
+        let map_transform = Transform::from_xyz(40.0, -25.0, 0.0).with_scale(Vec3::splat(0.5));
+
+        for (grid_size, map_type) in grids() {
+            let tile_size = TilemapTileSize {
+                x: grid_size.x,
+                y: grid_size.y,
+            };
+            for tile_pos in [
+                TilePos { x: 0, y: 0 },
+                TilePos { x: 7, y: 2 },
+                TilePos { x: 3, y: 5 },
+            ] {
+                let world = tile_center_to_world(
+                    &tile_pos,
+                    &MAP_SIZE,
+                    &grid_size,
+                    &map_type,
+                    &map_transform,
+                );
+                let picked = TilePos::from_world_pos(
+                    &cursor_to_map_pos(world.truncate(), &map_transform),
+                    &MAP_SIZE,
+                    &grid_size,
+                    &tile_size,
+                    &map_type,
+                    &TilemapAnchor::Center,
+                );
+                assert_eq!(picked, Some(tile_pos), "{map_type:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn world_positions_map_back_to_the_same_grid_point() {
+        let map_transform = Transform::from_xyz(40.0, -25.0, 0.0).with_scale(Vec3::splat(0.5));
+
+        for (grid_size, map_type) in grids() {
+            for point in [Vec2::ZERO, Vec2::new(2.25, 5.5), Vec2::new(-3.0, 9.0)] {
+                let world =
+                    grid_point_to_world(point, &MAP_SIZE, &grid_size, &map_type, &map_transform);
+                let back = world_to_grid_point(
+                    world.truncate(),
+                    &MAP_SIZE,
+                    &grid_size,
+                    &map_type,
+                    &map_transform,
+                );
+                assert!(back.abs_diff_eq(point, 1e-3), "{map_type:?}: {back}");
+            }
+        }
+    }
+
+    #[test]
+    fn sprites_further_down_the_screen_draw_on_top() {
+        for (grid_size, map_type) in grids() {
+            let z = |x, y| {
+                depth_sorted_z(
+                    10.0,
+                    &TilePos { x, y },
+                    Footprint::SINGLE,
+                    &MAP_SIZE,
+                    &grid_size,
+                    &map_type,
+                )
+            };
+            for (front, back) in [((2, 1), (2, 2)), ((3, 0), (3, 4)), ((5, 0), (0, 5))] {
+                assert!(z(front.0, front.1) > z(back.0, back.1), "{map_type:?}");
+            }
+            for x in 0..MAP_SIZE.x {
+                for y in 0..MAP_SIZE.y {
+                    assert!((9.0..=10.0).contains(&z(x, y)));
+                }
+            }
+        }
+    }
 }
@@ -1,15 +1,31 @@
 use super::helpers::*;
+use super::placement::placeholder_shape;
 use super::resources::*;
-use crate::budget::BuildingType;
+use super::tile_state::{TileState, tile_tint};
+use crate::budget::{BuildingType, Footprint};
+use crate::progression::Progression;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 use bevy_image::TextureAtlas;
 use rand::Rng;
 
+/// Tiles the selected building would cover, or just the hovered tile
+fn selected_footprint(
+    current_tile_type: &CurrentTileType,
+    rotation: &CurrentRotation,
+) -> Footprint {
+    BuildingType::from_selection_index(current_tile_type.texture_index)
+        .map_or(Footprint::SINGLE, |building_type| {
+            building_type.footprint().rotated(rotation.rotation)
+        })
+}
+
 pub fn highlight_hovered_tile(
     mut commands: Commands,
     cursor_pos: Res<CursorWorldPos>,
+    current_tile_type: Res<CurrentTileType>,
+    current_rotation: Res<CurrentRotation>,
     tilemap_q: Query<(
         &TilemapSize,
         &TilemapGridSize,
@@ -20,12 +36,12 @@ pub fn highlight_hovered_tile(
         &TilemapAnchor,
     )>,
     highlighted_tiles_q: Query<Entity, With<HighlightedTile>>,
-    mut tile_color_q: Query<&mut TileColor>,
+    mut tile_color_q: Query<(&mut TileColor, &TileState)>,
 ) {
     for entity in highlighted_tiles_q.iter() {
         commands.entity(entity).remove::<HighlightedTile>();
-        if let Ok(mut color) = tile_color_q.get_mut(entity) {
-            color.0 = Color::WHITE;
+        if let Ok((mut color, state)) = tile_color_q.get_mut(entity) {
+            color.0 = tile_tint(*state);
         }
     }
 
@@ -41,11 +57,16 @@ pub fn highlight_hovered_tile(
             tile_size,
             map_type,
             anchor,
-        ) && let Some(tile_entity) = tile_storage.get(&tile_pos)
-        {
-            commands.entity(tile_entity).insert(HighlightedTile);
-            if let Ok(mut color) = tile_color_q.get_mut(tile_entity) {
-                color.0 = Color::srgba(1.0, 1.0, 0.8, 1.0);
+        ) {
+            let footprint = selected_footprint(&current_tile_type, &current_rotation);
+            for covered in footprint.tiles(tile_pos) {
+                let Some(tile_entity) = tile_storage.checked_get(&covered) else {
+                    continue;
+                };
+                commands.entity(tile_entity).insert(HighlightedTile);
+                if let Ok((mut color, _)) = tile_color_q.get_mut(tile_entity) {
+                    color.0 = Color::srgba(1.0, 1.0, 0.8, 1.0);
+                }
             }
         }
     }
@@ -56,6 +77,8 @@ pub struct HoverPreviewInputs<'w, 's> {
     commands: Commands<'w, 's>,
     cursor_pos: Res<'w, CursorWorldPos>,
     current_tile_type: Res<'w, CurrentTileType>,
+    current_rotation: Res<'w, CurrentRotation>,
+    progression: Res<'w, Progression>,
     variants: HoverPreviewVariantResources<'w>,
     atlases: HoverPreviewAtlasResources<'w>,
     tilemap_q: Query<
@@ -89,6 +112,8 @@ pub struct HoverPreviewAtlasResources<'w> {
     industry: Option<Res<'w, IndustryBuildingAtlas>>,
     decorative: Option<Res<'w, DecorativeBuildingAtlas>>,
     tile_preview: Option<Res<'w, TilePreviewAtlas>>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
 }
 
 /// Show a semi-transparent preview of the currently selected building under the cursor
@@ -123,7 +148,8 @@ pub fn update_road_hover_preview(
             map_type,
             anchor,
         ) {
-            let center = tile_center_to_world(&tile_pos, map_size, grid_size, map_transform);
+            let center =
+                tile_center_to_world(&tile_pos, map_size, grid_size, map_type, map_transform);
             let cell_size = Vec2::new(grid_size.x, grid_size.y);
             target_world_and_grid = Some((center, cell_size, tile_pos));
             break;
@@ -250,7 +276,10 @@ pub fn update_road_hover_preview(
                 return;
             };
 
-            let variants = commercial_atlas.variants.max(1);
+            // later milestones widen the pool of shop designs
+            let variants = inputs
+                .progression
+                .variant_limit(BuildingType::Commercial, commercial_atlas.variants.max(1));
 
             // Generate random variant once when tile or building type changes
             let variant_index = if needs_new_variant {
@@ -302,7 +331,9 @@ pub fn update_road_hover_preview(
                 return;
             };
 
-            let variants = industry_atlas.variants.max(1);
+            let variants = inputs
+                .progression
+                .variant_limit(BuildingType::Industry, industry_atlas.variants.max(1));
 
             // Generate random variant once when tile or building type changes
             let variant_index = if needs_new_variant {
@@ -389,5 +420,35 @@ pub fn update_road_hover_preview(
                 RoadHoverPreview,
             ));
         }
+        BuildingType::BusStop | BuildingType::School | BuildingType::Stadium => {
+            *cached_tile = Some((tile_pos, active_type));
+
+            let Some((map_size, grid_size, _, map_type, _, map_transform, _)) =
+                inputs.tilemap_q.iter().next()
+            else {
+                return;
+            };
+            let footprint = active_type
+                .footprint()
+                .rotated(inputs.current_rotation.rotation);
+            let center = footprint_center_to_world(
+                &tile_pos,
+                footprint,
+                map_size,
+                grid_size,
+                map_type,
+                map_transform,
+            );
+
+            let (shape, color) =
+                placeholder_shape(active_type, footprint, map_size, grid_size, map_type);
+
+            inputs.commands.spawn((
+                Mesh2d(inputs.atlases.meshes.add(shape)),
+                MeshMaterial2d(inputs.atlases.materials.add(color.with_alpha(0.5))),
+                Transform::from_xyz(center.x, center.y, 10.0),
+                RoadHoverPreview,
+            ));
+        }
     }
 }
@@ -0,0 +1,303 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::bulldozer::BulldozeTool;
+use super::derelict::clearance_cost;
+use super::helpers::cursor_to_map_pos;
+use super::plots::PlotTool;
+use super::resources::*;
+use super::tile_state::{BuildingLookup, PlacedBuilding, TileState};
+use crate::budget::spending::daily_upkeep;
+use crate::city::AtRisk;
+use crate::city::resources::{
+    PlacementImpacts, building_contribution, count_nearby_residential, is_accessible, land_value,
+};
+use crate::settings::{Action, ActionInput, KeyBindings};
+use crate::spatial::SpatialGrid;
+use crate::transit::LineEditor;
+
+/// Tile of the building currently shown in the inspector panel, if any
+#[derive(Resource, Default)]
+pub struct InspectedBuilding {
+    pub tile_pos: Option<TilePos>,
+}
+
+#[derive(Component)]
+pub struct InspectorPanel;
+
+#[derive(Component)]
+pub struct InspectorText;
+
+#[derive(Component)]
+pub struct InspectorCloseButton;
+
+pub fn setup_building_inspector(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(240.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            InspectorPanel,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new(""),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                InspectorText,
+            ));
+
+            panel
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.8, 0.8, 0.8, 1.0)),
+                    InspectorCloseButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new("Close"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::BLACK),
+                    ));
+                });
+        });
+}
+
+#[derive(SystemParam)]
+pub struct InspectClickInputs<'w, 's> {
+    mouse_button: Res<'w, ButtonInput<MouseButton>>,
+    actions: ActionInput<'w>,
+    cursor_pos: Res<'w, CursorWorldPos>,
+    current_tile_type: Res<'w, CurrentTileType>,
+    ui_click_blocker: Res<'w, UiClickBlocker>,
+    line_editor: Res<'w, LineEditor>,
+    plot_tool: Res<'w, PlotTool>,
+    bulldozer: Res<'w, BulldozeTool>,
+    tilemap_q: Query<
+        'w,
+        's,
+        (
+            &'static TilemapSize,
+            &'static TilemapGridSize,
+            &'static TilemapTileSize,
+            &'static TilemapType,
+            &'static Transform,
+            &'static TilemapAnchor,
+        ),
+    >,
+}
+
+/// Clicking a tile with no tool selected inspects the building on it;
+/// clicking an empty tile closes the inspector
+pub fn inspect_building_on_click(
+    inputs: InspectClickInputs,
+    buildings: BuildingLookup,
+    mut inspected: ResMut<InspectedBuilding>,
+) {
+    if inputs.actions.just_pressed(Action::Cancel) && inspected.tile_pos.is_some() {
+        inspected.tile_pos = None;
+        return;
+    }
+
+    if !inputs.mouse_button.just_pressed(MouseButton::Left)
+        || inputs.ui_click_blocker.just_clicked_ui
+        || inputs.current_tile_type.texture_index != 0
+        || inputs.line_editor.active
+        || inputs.plot_tool.active
+        || inputs.bulldozer.active
+    {
+        return;
+    }
+
+    // Click with the demolish key held is demolition
+    if inputs.actions.pressed(Action::Demolish) {
+        return;
+    }
+
+    for (map_size, grid_size, tile_size, map_type, map_transform, anchor) in inputs.tilemap_q.iter()
+    {
+        let cursor_in_map_pos = cursor_to_map_pos(inputs.cursor_pos.0, map_transform);
+
+        let Some(tile_pos) = TilePos::from_world_pos(
+            &cursor_in_map_pos,
+            map_size,
+            grid_size,
+            tile_size,
+            map_type,
+            anchor,
+        ) else {
+            continue;
+        };
+
+        if buildings.building_at(&tile_pos).is_some() {
+            info!("Inspecting building at {:?}", tile_pos);
+            inspected.tile_pos = Some(tile_pos);
+        } else {
+            inspected.tile_pos = None;
+        }
+    }
+}
+
+pub fn handle_inspector_close_button(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<InspectorCloseButton>)>,
+    mut inspected: ResMut<InspectedBuilding>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    for interaction in interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_click_blocker.just_clicked_ui = true;
+            inspected.tile_pos = None;
+        }
+    }
+}
+
+/// Everything the panel text is built from, and the change checks that tell
+/// whether it needs rebuilding
+#[derive(SystemParam)]
+pub struct InspectorSources<'w, 's> {
+    spatial_grid: Res<'w, SpatialGrid>,
+    impacts: Res<'w, PlacementImpacts>,
+    bindings: Res<'w, KeyBindings>,
+    at_risk_q: Query<'w, 's, &'static AtRisk>,
+    changed_tiles_q: Query<'w, 's, (), Changed<TileState>>,
+    changed_buildings_q: Query<'w, 's, (), Changed<PlacedBuilding>>,
+    changed_risk_q: Query<'w, 's, (), Changed<AtRisk>>,
+    removed_risk: RemovedComponents<'w, 's, AtRisk>,
+}
+
+impl InspectorSources<'_, '_> {
+    fn changed(&mut self) -> bool {
+        // drain the removals every frame so old ones don't trigger a rebuild later
+        let risk_removed = self.removed_risk.read().count() > 0;
+        risk_removed
+            || self.spatial_grid.is_changed()
+            || self.impacts.is_changed()
+            || self.bindings.is_changed()
+            || !self.changed_tiles_q.is_empty()
+            || !self.changed_buildings_q.is_empty()
+            || !self.changed_risk_q.is_empty()
+    }
+}
+
+/// Keep the panel contents in sync with the inspected building, closing it
+/// once the building is gone. Only rebuilt when the selection or the city changed.
+pub fn update_building_inspector(
+    mut inspected: ResMut<InspectedBuilding>,
+    mut sources: InspectorSources,
+    buildings: BuildingLookup,
+    mut panel_q: Query<&mut Node, With<InspectorPanel>>,
+    mut text_q: Query<&mut Text, With<InspectorText>>,
+) {
+    let city_changed = sources.changed();
+    if !city_changed && !inspected.is_changed() {
+        return;
+    }
+
+    let details = inspected
+        .tile_pos
+        .and_then(|tile_pos| buildings.building_at(&tile_pos));
+
+    if inspected.tile_pos.is_some() && details.is_none() {
+        inspected.tile_pos = None;
+    }
+
+    let Ok(mut panel) = panel_q.single_mut() else {
+        return;
+    };
+
+    let Some(building) = details else {
+        panel.display = Display::None;
+        return;
+    };
+    panel.display = Display::Flex;
+
+    let Ok(mut text) = text_q.single_mut() else {
+        return;
+    };
+    let InspectorSources {
+        spatial_grid,
+        impacts,
+        bindings,
+        at_risk_q,
+        ..
+    } = &sources;
+
+    let building_type = building.building_type;
+    let tile_pos = building.tile_pos;
+    let contribution = building_contribution(building_type);
+    let road_access = if is_accessible(&tile_pos, spatial_grid) {
+        "yes"
+    } else {
+        "no"
+    };
+    let impact = match impacts.by_tile.get(&tile_pos) {
+        Some(delta) => format!("{:+.3}", delta),
+        None => "unknown".to_string(),
+    };
+
+    if let Some(TileState::Abandoned(_)) = buildings.tile_state_at(&tile_pos) {
+        text.0 = format!(
+            "Derelict {} at ({}, {})\nLand value: {:.2}\nRoad access: {}\nClear for ${} (Click+{})",
+            building_type.label(),
+            tile_pos.x,
+            tile_pos.y,
+            land_value(&tile_pos, spatial_grid),
+            road_access,
+            clearance_cost(building_type),
+            bindings.label(Action::Demolish),
+        );
+        return;
+    }
+
+    text.0 = format!(
+        "{} (variant {}) at ({}, {})\nCost: ${}\nUpkeep: ${}/day\nHousing {} / Jobs {} / Fun {}\nRoad access: {}\nLand value: {:.2}\nNearby residential: {}\nHappiness when placed: {}",
+        building_type.label(),
+        building.variant + 1,
+        tile_pos.x,
+        tile_pos.y,
+        building_type.cost(),
+        daily_upkeep(building_type),
+        contribution.housing,
+        contribution.jobs,
+        contribution.entertainment,
+        road_access,
+        land_value(&tile_pos, spatial_grid),
+        count_nearby_residential(&tile_pos, spatial_grid),
+        impact,
+    );
+
+    if let Some(at_risk) = buildings
+        .entity_at(&tile_pos)
+        .and_then(|entity| at_risk_q.get(entity).ok())
+    {
+        text.0.push_str(&format!(
+            "\nAt risk of abandonment (score {:.2})",
+            at_risk.score
+        ));
+    }
+}
@@ -1,15 +1,33 @@
 use bevy::prelude::*;
 
+use crate::menu::{GAME_EXIT, GAME_START, GameState, reset_resource};
+
+mod bulldozer;
 mod demolition;
+mod derelict;
 mod display;
 mod events;
+mod feedback;
 mod helpers;
 mod highlighting;
+mod inspector;
 mod placeable_area;
 mod placement;
+mod plots;
 mod resources;
+mod tile_state;
+mod tooltip;
 
+pub use bulldozer::BulldozeTool;
+pub use demolition::REFUND_WINDOW_HOURS;
+pub use derelict::{Derelict, abandon_building, restore_building};
+pub use helpers::{
+    cursor_to_map_pos, grid_point_to_world, tile_center_to_world, world_to_grid_point,
+};
+pub use placement::{PlacementAtlasResources, spawn_building};
+pub use plots::{LandPlots, PLOT_SIZE, PlotTool};
 pub use resources::*;
+pub use tile_state::{PlacedBuilding, TileState};
 
 pub struct TilePlacementPlugin;
 
@@ -17,13 +35,18 @@ impl Plugin for TilePlacementPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CursorWorldPos>()
             .init_resource::<CurrentTileType>()
+            .init_resource::<CurrentRotation>()
             .init_resource::<CurrentRoadVariant>()
             .init_resource::<CurrentCommercialVariant>()
             .init_resource::<CurrentIndustryVariant>()
             .init_resource::<CurrentDecorativeVariant>()
             .init_resource::<PreviewVariant>()
             .init_resource::<PlaceableMap>()
+            .init_resource::<LandPlots>()
+            .init_resource::<PlotTool>()
+            .init_resource::<BulldozeTool>()
             .init_resource::<UiClickBlocker>()
+            .init_resource::<inspector::InspectedBuilding>()
             .add_message::<events::PlacementIntent>()
             .add_systems(
                 Startup,
@@ -35,7 +58,9 @@ impl Plugin for TilePlacementPlugin {
                     resources::setup_industry_building_atlas,
                     resources::setup_roads_atlas,
                     resources::setup_decorative_building_atlas,
-                    resources::setup_tile_preview_atlas,
+                    feedback::setup_error_feedback,
+                    inspector::setup_building_inspector,
+                    tooltip::setup_placement_tooltip,
                 ),
             )
             .add_systems(
@@ -49,23 +74,83 @@ impl Plugin for TilePlacementPlugin {
                 Update,
                 (
                     placement::change_tile_type,
+                    plots::toggle_plot_tool,
+                    bulldozer::toggle_bulldoze_tool,
                     highlighting::highlight_hovered_tile,
                     highlighting::update_road_hover_preview,
                     placement::collect_placement_intents,
                     placement::execute_placement_intents,
                     demolition::demolish_tile_on_click,
-                    placeable_area::expand_placeable_area,
+                    bulldozer::bulldoze_area_on_drag,
+                    plots::buy_plot_on_click,
+                    plots::sync_placeable_map,
                     placeable_area::update_placeable_indicators,
                 )
-                    .chain(),
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    feedback::react_to_failed_transactions,
+                    feedback::update_failed_tile_flash,
+                    feedback::update_failure_labels,
+                )
+                    .chain()
+                    .after(placement::execute_placement_intents)
+                    .after(highlighting::highlight_hovered_tile),
             )
             .add_systems(
                 Update,
                 (
                     display::update_selected_tile_display,
-                    display::handle_tile_select_button_presses,
+                    display::handle_tile_select_button_presses.run_if(in_state(GameState::InGame)),
                     display::update_tile_select_button_colors,
+                    display::handle_plot_tool_button.run_if(in_state(GameState::InGame)),
+                    display::handle_bulldoze_tool_button.run_if(in_state(GameState::InGame)),
+                    plots::draw_plot_borders,
+                    bulldozer::draw_bulldoze_preview,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    inspector::handle_inspector_close_button,
+                    inspector::inspect_building_on_click.run_if(in_state(GameState::InGame)),
+                    inspector::update_building_inspector,
+                )
+                    .chain()
+                    .after(placement::execute_placement_intents),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    tile_state::sync_tile_textures,
+                    derelict::update_derelict_tint,
+                ),
+            )
+            .add_systems(
+                Update,
+                tooltip::update_placement_tooltip.after(placement::execute_placement_intents),
+            );
+
+        for start in GAME_START {
+            app.add_systems(start, resources::setup_tile_preview_atlas);
+        }
+        for exit in GAME_EXIT {
+            app.add_systems(
+                exit,
+                (
+                    placement::clear_buildings,
+                    reset_resource::<CurrentTileType>,
+                    reset_resource::<CurrentRotation>,
+                    reset_resource::<PlaceableMap>,
+                    reset_resource::<LandPlots>,
+                    reset_resource::<PlotTool>,
+                    reset_resource::<BulldozeTool>,
+                    reset_resource::<inspector::InspectedBuilding>,
                 ),
             );
+        }
     }
 }
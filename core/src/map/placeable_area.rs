@@ -1,64 +1,12 @@
-use super::helpers::*;
 use super::resources::*;
+use super::tile_state::TileState;
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
-use crate::spatial::SpatialGrid;
-
-const PLACEABLE_EXPANSION_RADIUS: i32 = 2;
-
-pub fn expand_placeable_area(
-    mut placeable_map: ResMut<PlaceableMap>,
-    spatial_grid: Res<SpatialGrid>,
-    tilemap_size_q: Query<&TilemapSize>,
-) {
-    let Some(map_size) = tilemap_size_q.iter().next() else {
-        return;
-    };
-
-    let buildings = spatial_grid.buildings_in_radius(
-        &TilePos {
-            x: map_size.x / 2,
-            y: map_size.y / 2,
-        },
-        map_size.x.max(map_size.y) as i32,
-    );
-
-    let mut newly_placeable = Vec::new();
-
-    for building_pos in buildings {
-        for dx in -PLACEABLE_EXPANSION_RADIUS..=PLACEABLE_EXPANSION_RADIUS {
-            for dy in -PLACEABLE_EXPANSION_RADIUS..=PLACEABLE_EXPANSION_RADIUS {
-                if dx == 0 && dy == 0 {
-                    continue;
-                }
-
-                let nx = building_pos.x as i32 + dx;
-                let ny = building_pos.y as i32 + dy;
-
-                if nx >= 0 && nx < map_size.x as i32 && ny >= 0 && ny < map_size.y as i32 {
-                    let neighbor_pos = TilePos {
-                        x: nx as u32,
-                        y: ny as u32,
-                    };
-
-                    if !placeable_map.is_placeable(&neighbor_pos) {
-                        newly_placeable.push(neighbor_pos);
-                    }
-                }
-            }
-        }
-    }
-
-    for pos in newly_placeable {
-        placeable_map.mark_placeable(pos);
-    }
-}
-
 pub fn update_placeable_indicators(
     placeable_map: Res<PlaceableMap>,
     tile_storage_q: Query<&TileStorage>,
-    mut tile_texture_q: Query<&mut TileTextureIndex>,
+    mut tile_state_q: Query<&mut TileState>,
     tilemap_size_q: Query<&TilemapSize>,
 ) {
     if !placeable_map.is_changed() {
@@ -77,67 +25,16 @@ pub fn update_placeable_indicators(
             let tile_pos = TilePos { x, y };
 
             if let Some(tile_entity) = tile_storage.get(&tile_pos)
-                && let Ok(mut texture) = tile_texture_q.get_mut(tile_entity)
+                && let Ok(mut state) = tile_state_q.get_mut(tile_entity)
             {
-                if texture.0 >= 2 {
-                    continue;
-                }
-
                 let is_placeable = placeable_map.is_placeable(&tile_pos);
 
-                if is_placeable && texture.0 == 0 {
-                    texture.0 = 1;
-                } else if !is_placeable && texture.0 == 1 {
-                    texture.0 = 0;
-                }
-            }
-        }
-    }
-}
-
-const INCREMENTAL_UPDATE_RADIUS: i32 = 4;
-
-pub fn incremental_update_placeable_area(
-    demolished_pos: TilePos,
-    placeable_map: &mut PlaceableMap,
-    spatial_grid: &SpatialGrid,
-    tile_storage: &TileStorage,
-    tile_texture_q: &Query<&mut TileTextureIndex>,
-    map_size: &TilemapSize,
-) {
-    for dx in -INCREMENTAL_UPDATE_RADIUS..=INCREMENTAL_UPDATE_RADIUS {
-        for dy in -INCREMENTAL_UPDATE_RADIUS..=INCREMENTAL_UPDATE_RADIUS {
-            let nx = demolished_pos.x as i32 + dx;
-            let ny = demolished_pos.y as i32 + dy;
-
-            if nx >= 0 && nx < map_size.x as i32 && ny >= 0 && ny < map_size.y as i32 {
-                let check_pos = TilePos {
-                    x: nx as u32,
-                    y: ny as u32,
-                };
-
-                if let Some(entity) = tile_storage.get(&check_pos)
-                    && let Ok(texture) = tile_texture_q.get(entity)
-                {
-                    if texture.0 >= 2 {
-                        continue;
-                    }
-
-                    let should_be_placeable =
-                        is_within_range_of_placed_tile(&check_pos, spatial_grid);
-
-                    if should_be_placeable {
-                        placeable_map.mark_placeable(check_pos);
-                    } else {
-                        placeable_map.placeable_tiles.remove(&check_pos);
-                    }
+                if is_placeable && *state == TileState::Empty {
+                    *state = TileState::Placeable;
+                } else if !is_placeable && *state == TileState::Placeable {
+                    *state = TileState::Empty;
                 }
             }
         }
     }
-
-    info!(
-        "Incrementally updated placeable area around {:?}",
-        demolished_pos
-    );
 }
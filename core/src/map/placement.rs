@@ -6,15 +6,23 @@ use bevy_image::TextureAtlas;
 use super::events::*;
 use super::helpers::*;
 use super::resources::*;
-use crate::budget::{Budget, BuildingPlaced, BuildingType, TransactionFailed};
-use crate::time::HelpOverlayState;
+use super::tile_state::{PlacedBuilding, TileState};
+use crate::budget::{
+    Budget, BuildingPlaced, BuildingType, Footprint, Rotation, TransactionFailed,
+    TransactionFailureReason,
+};
+use crate::notifications::Notify;
+use crate::progression::Progression;
+use crate::settings::{Action, ActionInput};
+use crate::spatial::SpatialGrid;
+use crate::time::GameClock;
 
 pub fn collect_placement_intents(
     mouse_button: Res<ButtonInput<MouseButton>>,
     cursor_pos: Res<CursorWorldPos>,
     current_tile_type: Res<CurrentTileType>,
+    current_rotation: Res<CurrentRotation>,
     ui_click_blocker: Res<UiClickBlocker>,
-    help_state: Option<Res<HelpOverlayState>>,
     tilemap_q: Query<(
         &TilemapSize,
         &TilemapGridSize,
@@ -25,12 +33,6 @@ pub fn collect_placement_intents(
     )>,
     mut intent_writer: MessageWriter<PlacementIntent>,
 ) {
-    if let Some(state) = help_state
-        && state.active
-    {
-        return;
-    }
-
     if !mouse_button.just_pressed(MouseButton::Left) {
         return;
     }
@@ -55,6 +57,7 @@ pub fn collect_placement_intents(
             intent_writer.write(PlacementIntent {
                 tile_pos,
                 building_type,
+                rotation: current_rotation.rotation,
             });
         }
     }
@@ -62,12 +65,16 @@ pub fn collect_placement_intents(
 
 #[derive(SystemParam)]
 pub struct PlacementExecutionInputs<'w, 's> {
+    clock: Res<'w, GameClock>,
     placeable_map: Res<'w, PlaceableMap>,
+    spatial_grid: Res<'w, SpatialGrid>,
+    progression: Res<'w, Progression>,
     current_budget: ResMut<'w, Budget>,
     building_events: MessageWriter<'w, BuildingPlaced>,
     failed_events: MessageWriter<'w, TransactionFailed>,
+    notify_writer: MessageWriter<'w, Notify>,
     intent_reader: MessageReader<'w, 's, PlacementIntent>,
-    tile_q: Query<'w, 's, (&'static TilePos, &'static mut TileTextureIndex)>,
+    tile_q: Query<'w, 's, &'static mut TileState>,
     atlases: PlacementAtlasResources<'w>,
     variants: PlacementVariantResources<'w>,
     commands: Commands<'w, 's>,
@@ -77,11 +84,47 @@ pub struct PlacementExecutionInputs<'w, 's> {
         (
             &'static TilemapSize,
             &'static TilemapGridSize,
+            &'static TilemapType,
+            &'static TileStorage,
             &'static Transform,
         ),
     >,
 }
 
+/// The first tile of a footprint that can't take a new building, and why
+pub fn footprint_blocker(
+    tiles: impl IntoIterator<Item = TilePos>,
+    placeable: impl Fn(&TilePos) -> bool,
+    state_at: impl Fn(&TilePos) -> Option<TileState>,
+) -> Option<(TilePos, TransactionFailureReason)> {
+    tiles
+        .into_iter()
+        .find_map(|tile_pos| match state_at(&tile_pos) {
+            // off the map, or on land the city doesn't own
+            None => Some((tile_pos, TransactionFailureReason::NotPlaceable)),
+            Some(_) if !placeable(&tile_pos) => {
+                Some((tile_pos, TransactionFailureReason::NotPlaceable))
+            }
+            Some(TileState::Placeable) => None,
+            Some(_) => Some((tile_pos, TransactionFailureReason::Occupied)),
+        })
+}
+
+/// Tell the player why a placement was turned down: a log entry and toast, plus the
+/// tile flash and floating label driven by `TransactionFailed`
+fn reject_placement(
+    notify_writer: &mut MessageWriter<Notify>,
+    failed_writer: &mut MessageWriter<TransactionFailed>,
+    message: String,
+    reason: TransactionFailureReason,
+    tile_pos: TilePos,
+) {
+    notify_writer.write(Notify::warning(message).at(tile_pos));
+    failed_writer.write(TransactionFailed { reason, tile_pos });
+}
+
+/// Sprite sheets for the building types that have one, and the asset stores the
+/// flat blocks of the others are built in
 #[derive(SystemParam)]
 pub struct PlacementAtlasResources<'w> {
     residential: Res<'w, ResidentialBuildingAtlas>,
@@ -89,6 +132,8 @@ pub struct PlacementAtlasResources<'w> {
     industry: Res<'w, IndustryBuildingAtlas>,
     road: Res<'w, RoadAtlas>,
     decorative: Res<'w, DecorativeBuildingAtlas>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
 }
 
 #[derive(SystemParam)]
@@ -99,202 +144,315 @@ pub struct PlacementVariantResources<'w> {
 }
 
 pub fn execute_placement_intents(mut inputs: PlacementExecutionInputs) {
-    let (map_size, grid_size, map_transform) = if let Some(v) = inputs.map_q.iter().next() {
-        v
-    } else {
+    let Some((map_size, grid_size, map_type, tile_storage, map_transform)) =
+        inputs.map_q.iter().next()
+    else {
         return;
     };
 
     for intent in inputs.intent_reader.read() {
-        let desired_pos = intent.tile_pos;
+        let tile_pos = intent.tile_pos;
 
-        for (tile_pos, mut texture_index) in inputs.tile_q.iter_mut() {
-            if *tile_pos != desired_pos {
-                continue;
-            }
-
-            if !inputs.placeable_map.is_placeable(tile_pos) {
-                warn!("Cannot place here - tile not placeable!");
-                inputs.failed_events.write(TransactionFailed);
-                break;
-            }
+        if !inputs.progression.is_unlocked(intent.building_type) {
+            warn!(
+                "Cannot place {:?} - not unlocked yet!",
+                intent.building_type
+            );
+            reject_placement(
+                &mut inputs.notify_writer,
+                &mut inputs.failed_events,
+                format!("{} is not unlocked yet", intent.building_type.label()),
+                TransactionFailureReason::Locked,
+                tile_pos,
+            );
+            continue;
+        }
 
-            if texture_index.0 != 1 {
+        // only buildings bigger than a tile can be turned
+        let rotation = if intent.building_type.footprint().is_single() {
+            Rotation::North
+        } else {
+            intent.rotation
+        };
+        let footprint = intent.building_type.footprint().rotated(rotation);
+
+        let blocker = footprint_blocker(
+            footprint.tiles(tile_pos),
+            |pos| inputs.placeable_map.is_placeable(pos),
+            |pos| {
+                let tile_entity = tile_storage.checked_get(pos)?;
+                inputs.tile_q.get(tile_entity).ok().copied()
+            },
+        );
+        if let Some((blocked_pos, reason)) = blocker {
+            if reason == TransactionFailureReason::Occupied {
                 warn!("Cannot place here - tile already occupied!");
-                inputs.failed_events.write(TransactionFailed);
-                break;
-            }
-
-            let cost = intent.building_type.cost();
-
-            if !inputs.current_budget.can_afford(cost) {
-                warn!(
-                    "Cannot afford {:?}! Cost: ${}, Balance: ${}",
-                    intent.building_type, cost, inputs.current_budget.money
-                );
-                inputs.failed_events.write(TransactionFailed);
-                break;
+            } else {
+                warn!("Cannot place here - tile not placeable!");
             }
+            reject_placement(
+                &mut inputs.notify_writer,
+                &mut inputs.failed_events,
+                format!(
+                    "Cannot build {} at ({}, {}): {}",
+                    intent.building_type.label(),
+                    blocked_pos.x,
+                    blocked_pos.y,
+                    reason.label().to_lowercase()
+                ),
+                reason,
+                blocked_pos,
+            );
+            continue;
+        }
 
-            inputs.current_budget.spend(cost);
-
-            let (new_texture_index, variant_index) = match intent.building_type {
-                BuildingType::Residential => {
-                    let variant = inputs.variants.preview.residential.unwrap_or(0);
-                    // Houses 1-2 (indices 0,1) use tile 3; houses 3-5 (indices 2,3,4) use tile 2
-                    let tile_index = if variant <= 1 { 3 } else { 2 };
-                    (tile_index, variant)
-                }
-                BuildingType::Commercial => {
-                    let variant = inputs.variants.preview.commercial.unwrap_or(0);
-                    (4, variant)
-                }
-                BuildingType::Industry => {
-                    let variant = inputs.variants.preview.industry.unwrap_or(0);
-                    (4, variant)
-                }
-                BuildingType::Road => {
-                    let variant =
-                        (inputs.variants.road.index as usize) % inputs.atlases.road.variants.max(1);
-                    (4, variant)
-                }
-                BuildingType::Decorative => {
-                    let variant = (inputs.variants.decorative.index as usize)
-                        % inputs.atlases.decorative.variants.max(1);
-                    (4, variant)
-                }
-            };
+        // Bus stops are only useful right next to a road
+        if intent.building_type == BuildingType::BusStop
+            && !inputs.spatial_grid.has_road_in_radius(&tile_pos, 1)
+        {
+            warn!("Cannot place bus stop here - no adjacent road!");
+            reject_placement(
+                &mut inputs.notify_writer,
+                &mut inputs.failed_events,
+                "A bus stop needs a road right next to it".to_string(),
+                TransactionFailureReason::NoRoadAccess,
+                tile_pos,
+            );
+            continue;
+        }
 
-            texture_index.0 = new_texture_index;
+        let cost = intent.building_type.cost();
 
-            info!(
-                "Built {:?} for ${}. Balance: ${}",
+        if !inputs.current_budget.can_afford(cost) {
+            warn!(
+                "Cannot afford {:?}! Cost: ${}, Balance: ${}",
                 intent.building_type, cost, inputs.current_budget.money
             );
+            reject_placement(
+                &mut inputs.notify_writer,
+                &mut inputs.failed_events,
+                format!(
+                    "Cannot afford {}: costs ${}, you have ${}",
+                    intent.building_type.label(),
+                    cost,
+                    inputs.current_budget.money
+                ),
+                TransactionFailureReason::Unaffordable,
+                tile_pos,
+            );
+            continue;
+        }
+
+        inputs.current_budget.spend(cost);
 
-            inputs.building_events.write(BuildingPlaced {
+        let variant_index = match intent.building_type {
+            BuildingType::Residential => inputs.variants.preview.residential.unwrap_or(0),
+            BuildingType::Commercial => inputs.variants.preview.commercial.unwrap_or(0),
+            BuildingType::Industry => inputs.variants.preview.industry.unwrap_or(0),
+            BuildingType::Road => {
+                (inputs.variants.road.index as usize) % inputs.atlases.road.variants.max(1)
+            }
+            BuildingType::Decorative => {
+                (inputs.variants.decorative.index as usize)
+                    % inputs.atlases.decorative.variants.max(1)
+            }
+            BuildingType::BusStop | BuildingType::School | BuildingType::Stadium => 0,
+        };
+
+        info!(
+            "Built {:?} for ${}. Balance: ${}",
+            intent.building_type, cost, inputs.current_budget.money
+        );
+
+        let building_entity = spawn_building(
+            &mut inputs.commands,
+            &mut inputs.atlases,
+            PlacedBuilding {
                 building_type: intent.building_type,
-                tile_pos: *tile_pos,
-            });
+                variant: variant_index,
+                tile_pos,
+                rotation,
+                placed_day: inputs.clock.day,
+                placed_hour: inputs.clock.hour_index(),
+            },
+            map_size,
+            grid_size,
+            map_type,
+            map_transform,
+        );
 
-            if intent.building_type == BuildingType::Residential
-                && inputs.atlases.residential.variants > 0
-            {
-                let world_pos = tile_center_to_world(tile_pos, map_size, grid_size, map_transform);
-
-                let sprite = Sprite::from_atlas_image(
-                    inputs.atlases.residential.texture.clone(),
-                    TextureAtlas {
-                        layout: inputs.atlases.residential.layout.clone(),
-                        index: variant_index,
-                    },
-                );
-
-                inputs.commands.spawn((
-                    sprite,
-                    Transform::from_xyz(world_pos.x, world_pos.y, 10.0),
-                    ResidentialBuilding {
-                        tile_pos: *tile_pos,
-                    },
-                ));
-            } else if intent.building_type == BuildingType::Commercial
-                && inputs.atlases.commercial.variants > 0
+        for covered in footprint.tiles(tile_pos) {
+            if let Some(tile_entity) = tile_storage.checked_get(&covered)
+                && let Ok(mut tile_state) = inputs.tile_q.get_mut(tile_entity)
             {
-                let world_pos = tile_center_to_world(tile_pos, map_size, grid_size, map_transform);
-
-                let sprite = Sprite::from_atlas_image(
-                    inputs.atlases.commercial.texture.clone(),
-                    TextureAtlas {
-                        layout: inputs.atlases.commercial.layout.clone(),
-                        index: variant_index,
-                    },
-                );
-
-                inputs.commands.spawn((
-                    sprite,
-                    Transform::from_xyz(world_pos.x, world_pos.y, 10.0),
-                    CommercialBuilding {
-                        tile_pos: *tile_pos,
-                    },
-                ));
-            } else if intent.building_type == BuildingType::Industry
-                && inputs.atlases.industry.variants > 0
-            {
-                let world_pos = tile_center_to_world(tile_pos, map_size, grid_size, map_transform);
-
-                let sprite = Sprite::from_atlas_image(
-                    inputs.atlases.industry.texture.clone(),
-                    TextureAtlas {
-                        layout: inputs.atlases.industry.layout.clone(),
-                        index: variant_index,
-                    },
-                );
-
-                inputs.commands.spawn((
-                    sprite,
-                    Transform::from_xyz(world_pos.x, world_pos.y, 10.0),
-                    IndustryBuilding {
-                        tile_pos: *tile_pos,
-                    },
-                ));
-            } else if intent.building_type == BuildingType::Road && inputs.atlases.road.variants > 0
-            {
-                let world_pos = tile_center_to_world(tile_pos, map_size, grid_size, map_transform);
-                let variant_index =
-                    (inputs.variants.road.index as usize) % inputs.atlases.road.variants.max(1);
-
-                let sprite = Sprite::from_atlas_image(
-                    inputs.atlases.road.texture.clone(),
-                    TextureAtlas {
-                        layout: inputs.atlases.road.layout.clone(),
-                        index: variant_index,
-                    },
-                );
-
-                inputs.commands.spawn((
-                    sprite,
-                    Transform::from_xyz(world_pos.x, world_pos.y, 5.0),
-                    RoadSegment {
-                        tile_pos: *tile_pos,
-                    },
-                ));
-            } else if intent.building_type == BuildingType::Decorative
-                && inputs.atlases.decorative.variants > 0
-            {
-                let world_pos = tile_center_to_world(tile_pos, map_size, grid_size, map_transform);
-                let variant_index = (inputs.variants.decorative.index as usize)
-                    % inputs.atlases.decorative.variants.max(1);
-
-                let sprite = Sprite::from_atlas_image(
-                    inputs.atlases.decorative.texture.clone(),
-                    TextureAtlas {
-                        layout: inputs.atlases.decorative.layout.clone(),
-                        index: variant_index,
-                    },
-                );
-
-                inputs.commands.spawn((
-                    sprite,
-                    Transform::from_xyz(world_pos.x, world_pos.y, 10.0),
-                    DecorativeBuilding {
-                        tile_pos: *tile_pos,
-                    },
-                ));
+                *tile_state = TileState::Occupied(building_entity);
             }
+        }
 
-            break;
+        inputs.building_events.write(BuildingPlaced {
+            building_type: intent.building_type,
+            tile_pos,
+            footprint,
+        });
+    }
+}
+
+/// Spawn the entity for `building` with its sprite and per-type marker. Callers
+/// mark the tiles it covers and announce it with `BuildingPlaced`.
+pub fn spawn_building(
+    commands: &mut Commands,
+    atlases: &mut PlacementAtlasResources,
+    building: PlacedBuilding,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+    map_transform: &Transform,
+) -> Entity {
+    let PlacedBuilding {
+        building_type,
+        variant,
+        tile_pos,
+        rotation,
+        ..
+    } = building;
+    let footprint = building.footprint();
+
+    let world_pos = footprint_center_to_world(
+        &tile_pos,
+        footprint,
+        map_size,
+        grid_size,
+        map_type,
+        map_transform,
+    );
+    // roads sit below buildings so sprites overlapping from neighbours stay on top,
+    // and within each layer sprites nearer the bottom of the screen cover those behind
+    let layer = if building_type == BuildingType::Road {
+        ROAD_LAYER
+    } else {
+        BUILDING_LAYER
+    };
+    let z = depth_sorted_z(layer, &tile_pos, footprint, map_size, grid_size, map_type);
+
+    let atlas = match building_type {
+        BuildingType::Residential => Some((
+            &atlases.residential.texture,
+            &atlases.residential.layout,
+            atlases.residential.variants,
+        )),
+        BuildingType::Commercial => Some((
+            &atlases.commercial.texture,
+            &atlases.commercial.layout,
+            atlases.commercial.variants,
+        )),
+        BuildingType::Industry => Some((
+            &atlases.industry.texture,
+            &atlases.industry.layout,
+            atlases.industry.variants,
+        )),
+        BuildingType::Road => Some((
+            &atlases.road.texture,
+            &atlases.road.layout,
+            atlases.road.variants,
+        )),
+        BuildingType::Decorative => Some((
+            &atlases.decorative.texture,
+            &atlases.decorative.layout,
+            atlases.decorative.variants,
+        )),
+        BuildingType::BusStop | BuildingType::School | BuildingType::Stadium => None,
+    };
+
+    // sprite sheets are turned by the transform, but only on the top-down grid: on the
+    // isometric one that would tip them off their diamond. Placeholder blocks are
+    // built from the rotated footprint and never need turning.
+    let turn = match (map_type, &atlas) {
+        (TilemapType::Square, Some(_)) => Quat::from_rotation_z(rotation.angle()),
+        _ => Quat::IDENTITY,
+    };
+    let building_entity = commands
+        .spawn((
+            Transform::from_xyz(world_pos.x, world_pos.y, z).with_rotation(turn),
+            building,
+        ))
+        .id();
+
+    let mut building_commands = commands.entity(building_entity);
+    insert_building_marker(&mut building_commands, building_type, tile_pos);
+
+    match atlas {
+        Some((texture, layout, variants)) if variants > 0 => {
+            building_commands.insert(Sprite::from_atlas_image(
+                texture.clone(),
+                TextureAtlas {
+                    layout: layout.clone(),
+                    index: variant,
+                },
+            ));
+        }
+        Some(_) => {}
+        None => {
+            let (shape, color) =
+                placeholder_shape(building_type, footprint, map_size, grid_size, map_type);
+            building_commands.insert((
+                Mesh2d(atlases.meshes.add(shape)),
+                MeshMaterial2d(atlases.materials.add(color)),
+            ));
         }
     }
+
+    building_entity
+}
+
+type BuildingOrPreview = Or<(With<PlacedBuilding>, With<RoadHoverPreview>)>;
+
+/// Take the buildings and the hover preview of the city being left off the map
+pub fn clear_buildings(mut commands: Commands, buildings_q: Query<Entity, BuildingOrPreview>) {
+    for entity in buildings_q.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Flat coloured block for building types without a sprite sheet, laid over the
+/// (already rotated) `footprint` the way the map projects it: a rectangle on the
+/// top-down grid, a diamond on the isometric one. Points are relative to the
+/// footprint's center, so the block goes where `footprint_center_to_world` says.
+pub fn placeholder_shape(
+    building_type: BuildingType,
+    footprint: Footprint,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    map_type: &TilemapType,
+) -> (ConvexPolygon, Color) {
+    // the sizes are world units across a tile; the inset is in tiles on each side
+    let (color, inset) = match building_type {
+        BuildingType::School => (SCHOOL_COLOR, (grid_size.x - SCHOOL_SPRITE_SIZE) / 2.0),
+        BuildingType::BusStop => (BUS_STOP_COLOR, (grid_size.x - BUS_STOP_SPRITE_SIZE) / 2.0),
+        _ => (STADIUM_COLOR, FOOTPRINT_SPRITE_MARGIN / 2.0),
+    };
+    let inset = inset / grid_size.x;
+
+    let size = Vec2::new(footprint.width as f32, footprint.height as f32);
+    let to_world = |point: Vec2| {
+        grid_point_to_world(point, map_size, grid_size, map_type, &Transform::IDENTITY).xy()
+    };
+    let center = to_world(size / 2.0);
+    let outline = block_outline(Vec2::ZERO, size, inset, |point| to_world(point) - center);
+
+    (
+        ConvexPolygon::new_unchecked(outline[..4].iter().copied()),
+        color,
+    )
 }
 
 #[derive(SystemParam)]
 pub struct TileTypeChangeInputs<'w> {
-    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    actions: ActionInput<'w>,
     current_tile_type: ResMut<'w, CurrentTileType>,
+    rotation: ResMut<'w, CurrentRotation>,
     variants: TileVariantResources<'w>,
     preview_variant: ResMut<'w, PreviewVariant>,
     atlases: TileAtlasResources<'w>,
-    help_state: Option<Res<'w, HelpOverlayState>>,
 }
 
 #[derive(SystemParam)]
@@ -313,44 +471,50 @@ pub struct TileAtlasResources<'w> {
 }
 
 pub fn change_tile_type(mut inputs: TileTypeChangeInputs) {
-    if let Some(state) = inputs.help_state.as_ref()
-        && state.active
-    {
-        return;
-    }
-    if inputs.keyboard.just_pressed(KeyCode::Escape) {
+    if inputs.actions.just_pressed(Action::Cancel) {
         inputs.current_tile_type.texture_index = 0;
         info!("Selected: None");
-    } else if inputs.keyboard.just_pressed(KeyCode::KeyR) {
+    } else if inputs.actions.just_pressed(Action::SelectResidential) {
         inputs.current_tile_type.texture_index = 2;
         info!("Selected: Residential/Housing");
-    } else if inputs.keyboard.just_pressed(KeyCode::KeyC) {
+    } else if inputs.actions.just_pressed(Action::SelectCommercial) {
         inputs.current_tile_type.texture_index = 3;
         info!("Selected: Commercial");
-    } else if inputs.keyboard.just_pressed(KeyCode::KeyI) {
+    } else if inputs.actions.just_pressed(Action::SelectIndustry) {
         inputs.current_tile_type.texture_index = 4;
         info!("Selected: Industry");
-    } else if inputs.keyboard.just_pressed(KeyCode::KeyO) {
+    } else if inputs.actions.just_pressed(Action::SelectRoad) {
         inputs.current_tile_type.texture_index = 5;
         info!("Selected: Road");
-    } else if inputs.keyboard.just_pressed(KeyCode::KeyB) {
+    } else if inputs.actions.just_pressed(Action::SelectDecorative) {
         inputs.current_tile_type.texture_index = 6;
         info!("Selected: Decorative");
+    } else if inputs.actions.just_pressed(Action::SelectBusStop) {
+        inputs.current_tile_type.texture_index = 7;
+        info!("Selected: Bus Stop");
+    } else if inputs.actions.just_pressed(Action::SelectSchool) {
+        inputs.current_tile_type.texture_index = 8;
+        info!("Selected: School");
+    } else if inputs.actions.just_pressed(Action::SelectStadium) {
+        inputs.current_tile_type.texture_index = 9;
+        info!("Selected: Stadium");
+    }
+
+    if inputs.actions.just_pressed(Action::Rotate) {
+        inputs.rotation.rotation = inputs.rotation.rotation.next();
+        info!("Rotation: {:?}", inputs.rotation.rotation);
     }
 
     // When a building type is selected, allow cycling through its variants
-    // using ',' (next) and '.' (previous)
     if let Some(active_type) =
         crate::budget::BuildingType::from_selection_index(inputs.current_tile_type.texture_index)
     {
         let mut delta: i32 = 0;
 
-        if inputs.keyboard.just_pressed(KeyCode::Comma) {
-            // ',' => +1 variant
+        if inputs.actions.just_pressed(Action::NextVariant) {
             delta += 1;
         }
-        if inputs.keyboard.just_pressed(KeyCode::Period) {
-            // '.' => -1 variant
+        if inputs.actions.just_pressed(Action::PreviousVariant) {
             delta -= 1;
         }
 
@@ -430,7 +594,105 @@ pub fn change_tile_type(mut inputs: TileTypeChangeInputs) {
                         );
                     }
                 }
+                // Bus stops, schools and stadiums only come in one look
+                crate::budget::BuildingType::BusStop
+                | crate::budget::BuildingType::School
+                | crate::budget::BuildingType::Stadium => {}
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::Footprint;
+
+    #[test]
+    fn every_tile_of_a_footprint_must_be_free_and_owned() {
+        let stadium = Footprint {
+            width: 3,
+            height: 2,
+        };
+        let origin = TilePos { x: 0, y: 0 };
+        let owned = |pos: &TilePos| pos.x < 3;
+        let free = |pos: &TilePos| (pos.y < 2).then_some(TileState::Placeable);
+
+        assert_eq!(footprint_blocker(stadium.tiles(origin), owned, free), None);
+
+        let house = TilePos { x: 2, y: 1 };
+        let blocked = footprint_blocker(stadium.tiles(origin), owned, |pos| {
+            if *pos == house {
+                Some(TileState::Occupied(Entity::PLACEHOLDER))
+            } else {
+                free(pos)
+            }
+        });
+        assert_eq!(blocked, Some((house, TransactionFailureReason::Occupied)));
+
+        // shifted right it runs off the owned land, turned on its side it runs off the map
+        assert_eq!(
+            footprint_blocker(stadium.tiles(TilePos { x: 1, y: 0 }), owned, free),
+            Some((
+                TilePos { x: 3, y: 0 },
+                TransactionFailureReason::NotPlaceable
+            ))
+        );
+        let turned = stadium.rotated(Rotation::East);
+        assert_eq!(
+            footprint_blocker(turned.tiles(origin), owned, free),
+            Some((
+                TilePos { x: 0, y: 2 },
+                TransactionFailureReason::NotPlaceable
+            ))
+        );
+    }
+
+    #[test]
+    fn placeholder_blocks_follow_the_projection() {
+        let map_size = TilemapSize { x: 8, y: 8 };
+        let stadium = BuildingType::Stadium.footprint().rotated(Rotation::East);
+        let extent = |shape: &ConvexPolygon| {
+            let xs = shape.vertices().iter().map(|v| v.x);
+            let ys = shape.vertices().iter().map(|v| v.y);
+            (
+                xs.clone().fold(f32::MIN, f32::max) - xs.fold(f32::MAX, f32::min),
+                ys.clone().fold(f32::MIN, f32::max) - ys.fold(f32::MAX, f32::min),
+            )
+        };
+
+        // top-down, the turned stadium is a 2x3 rectangle short of its margin
+        let square = TilemapGridSize { x: 96.0, y: 96.0 };
+        let (shape, _) = placeholder_shape(
+            BuildingType::Stadium,
+            stadium,
+            &map_size,
+            &square,
+            &TilemapType::Square,
+        );
+        assert_eq!(extent(&shape), (2.0 * 96.0 - 8.0, 3.0 * 96.0 - 8.0));
+
+        // isometric, a school is a diamond half as tall as it is wide
+        let iso = TilemapGridSize { x: 96.0, y: 48.0 };
+        let (shape, _) = placeholder_shape(
+            BuildingType::School,
+            Footprint::SINGLE,
+            &map_size,
+            &iso,
+            &TilemapType::Isometric(IsoCoordSystem::Diamond),
+        );
+        let (width, height) = extent(&shape);
+        assert!((width - SCHOOL_SPRITE_SIZE).abs() < 1e-3, "{}", width);
+        assert!(
+            (height - SCHOOL_SPRITE_SIZE / 2.0).abs() < 1e-3,
+            "{}",
+            height
+        );
+        assert!(
+            shape
+                .vertices()
+                .iter()
+                .all(|v| v.x.abs() < 1e-3 || v.y.abs() < 1e-3)
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use std::collections::HashSet;
+
+use super::helpers::*;
+use super::resources::*;
+use crate::budget::{
+    Budget, BudgetLedger, LedgerCategory, TransactionFailed, TransactionFailureReason,
+};
+use crate::notifications::Notify;
+use crate::progression::Progression;
+use crate::settings::{Action, ActionInput, KeyBindings};
+use crate::time::GameClock;
+
+/// Width and height of a plot in tiles
+pub const PLOT_SIZE: u32 = 8;
+/// A new plot costs this much for every plot the city already owns
+const PLOT_PRICE_PER_OWNED_PLOT: i64 = 1_500;
+/// Plots that can be bought before any milestone, enough to reach a highway
+const STARTING_PURCHASES: u32 = 1;
+
+const OWNED_BORDER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+const BUYABLE_PLOT_COLOR: Color = Color::srgba(1.0, 0.85, 0.2, 0.9);
+/// How far, in tiles, the outline of a plot for sale sits inside its border
+const BUYABLE_PLOT_INSET: f32 = 0.05;
+
+/// Plot coordinates: tile coordinates divided by `PLOT_SIZE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlotPos {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl PlotPos {
+    pub fn containing(tile_pos: &TilePos) -> Self {
+        Self {
+            x: tile_pos.x / PLOT_SIZE,
+            y: tile_pos.y / PLOT_SIZE,
+        }
+    }
+
+    /// Tiles of this plot that lie on the map
+    pub fn tiles(&self, map_size: &TilemapSize) -> impl Iterator<Item = TilePos> {
+        let x_range = self.x * PLOT_SIZE..((self.x + 1) * PLOT_SIZE).min(map_size.x);
+        let y_range = self.y * PLOT_SIZE..((self.y + 1) * PLOT_SIZE).min(map_size.y);
+        x_range.flat_map(move |x| y_range.clone().map(move |y| TilePos { x, y }))
+    }
+
+    fn neighbors(&self) -> impl Iterator<Item = PlotPos> {
+        let (x, y) = (self.x as i64, self.y as i64);
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|(x, y)| *x >= 0 && *y >= 0)
+            .map(|(x, y)| PlotPos {
+                x: x as u32,
+                y: y as u32,
+            })
+    }
+}
+
+fn plots_across(map_size: &TilemapSize) -> (u32, u32) {
+    (
+        map_size.x.div_ceil(PLOT_SIZE),
+        map_size.y.div_ceil(PLOT_SIZE),
+    )
+}
+
+/// Land the city owns. Only tiles on owned plots can be built on.
+#[derive(Resource, Debug, Default)]
+pub struct LandPlots {
+    pub owned: HashSet<PlotPos>,
+    /// Plots bought by the player, not counting the starting land
+    pub purchased: u32,
+}
+
+impl LandPlots {
+    /// The plots touching the middle of the map
+    pub fn starting(map_size: &TilemapSize) -> Self {
+        let (center_x, center_y) = (map_size.x / 2, map_size.y / 2);
+        let owned = [
+            (center_x.saturating_sub(1), center_y.saturating_sub(1)),
+            (center_x, center_y.saturating_sub(1)),
+            (center_x.saturating_sub(1), center_y),
+            (center_x, center_y),
+        ]
+        .into_iter()
+        .map(|(x, y)| PlotPos::containing(&TilePos { x, y }))
+        .collect();
+
+        Self {
+            owned,
+            purchased: 0,
+        }
+    }
+
+    /// Land given by a scenario, in plot coordinates
+    pub fn with_plots(plots: &[(u32, u32)]) -> Self {
+        Self {
+            owned: plots.iter().map(|&(x, y)| PlotPos { x, y }).collect(),
+            purchased: 0,
+        }
+    }
+
+    pub fn owns_tile(&self, tile_pos: &TilePos) -> bool {
+        self.owned.contains(&PlotPos::containing(tile_pos))
+    }
+
+    pub fn price(&self) -> i64 {
+        PLOT_PRICE_PER_OWNED_PLOT * self.owned.len() as i64
+    }
+
+    /// Whether milestones allow one more purchase
+    pub fn can_buy_more(&self, regions_unlocked: u32) -> bool {
+        self.purchased < STARTING_PURCHASES + regions_unlocked
+    }
+
+    /// Plots on the map that are not owned yet but border owned land
+    pub fn is_buyable(&self, plot: &PlotPos, map_size: &TilemapSize) -> bool {
+        let (across_x, across_y) = plots_across(map_size);
+        plot.x < across_x
+            && plot.y < across_y
+            && !self.owned.contains(plot)
+            && plot.neighbors().any(|n| self.owned.contains(&n))
+    }
+
+    pub fn buyable(&self, map_size: &TilemapSize) -> Vec<PlotPos> {
+        let (across_x, across_y) = plots_across(map_size);
+        (0..across_x)
+            .flat_map(|x| (0..across_y).map(move |y| PlotPos { x, y }))
+            .filter(|plot| self.is_buyable(plot, map_size))
+            .collect()
+    }
+
+    pub fn buy(&mut self, plot: PlotPos) {
+        if self.owned.insert(plot) {
+            self.purchased += 1;
+        }
+    }
+
+    pub fn placeable_map(&self, map_size: &TilemapSize) -> PlaceableMap {
+        PlaceableMap {
+            placeable_tiles: self
+                .owned
+                .iter()
+                .flat_map(|plot| plot.tiles(map_size))
+                .collect(),
+        }
+    }
+}
+
+/// The buy-plot tool: while active, clicking the map buys the plot under the cursor
+#[derive(Resource, Debug, Default)]
+pub struct PlotTool {
+    pub active: bool,
+}
+
+pub fn toggle_plot_tool(
+    actions: ActionInput,
+    mut plot_tool: ResMut<PlotTool>,
+    mut current_tile_type: ResMut<CurrentTileType>,
+) {
+    // Picking a building tool or Esc puts the land tool away
+    if plot_tool.active
+        && ((current_tile_type.is_changed() && current_tile_type.texture_index != 0)
+            || actions.just_pressed(Action::Cancel))
+    {
+        plot_tool.active = false;
+        return;
+    }
+
+    if actions.just_pressed(Action::BuyLand) {
+        plot_tool.active = !plot_tool.active;
+        if plot_tool.active {
+            current_tile_type.texture_index = 0;
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct PlotPurchaseInputs<'w, 's> {
+    mouse_button: Res<'w, ButtonInput<MouseButton>>,
+    cursor_pos: Res<'w, CursorWorldPos>,
+    ui_click_blocker: Res<'w, UiClickBlocker>,
+    plot_tool: Res<'w, PlotTool>,
+    progression: Res<'w, Progression>,
+    bindings: Res<'w, KeyBindings>,
+    clock: Res<'w, GameClock>,
+    tilemap_q: Query<
+        'w,
+        's,
+        (
+            &'static TilemapSize,
+            &'static TilemapGridSize,
+            &'static TilemapTileSize,
+            &'static TilemapType,
+            &'static Transform,
+            &'static TilemapAnchor,
+        ),
+    >,
+}
+
+pub fn buy_plot_on_click(
+    inputs: PlotPurchaseInputs,
+    mut plots: ResMut<LandPlots>,
+    mut budget: ResMut<Budget>,
+    mut ledger: ResMut<BudgetLedger>,
+    mut failed_writer: MessageWriter<TransactionFailed>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    if !inputs.plot_tool.active
+        || !inputs.mouse_button.just_pressed(MouseButton::Left)
+        || inputs.ui_click_blocker.just_clicked_ui
+    {
+        return;
+    }
+
+    for (map_size, grid_size, tile_size, map_type, map_transform, anchor) in inputs.tilemap_q.iter()
+    {
+        let cursor_in_map_pos = cursor_to_map_pos(inputs.cursor_pos.0, map_transform);
+
+        let Some(tile_pos) = TilePos::from_world_pos(
+            &cursor_in_map_pos,
+            map_size,
+            grid_size,
+            tile_size,
+            map_type,
+            anchor,
+        ) else {
+            continue;
+        };
+
+        let plot = PlotPos::containing(&tile_pos);
+        if plots.owned.contains(&plot) {
+            return;
+        }
+
+        if !plots.is_buyable(&plot, map_size) {
+            notify_writer.write(Notify::warning("You can only buy land next to your own"));
+            failed_writer.write(TransactionFailed {
+                reason: TransactionFailureReason::NotPlaceable,
+                tile_pos,
+            });
+            return;
+        }
+
+        if !plots.can_buy_more(inputs.progression.regions_unlocked()) {
+            notify_writer.write(Notify::warning(format!(
+                "Reach the next milestone to buy more land ({})",
+                inputs.bindings.label(Action::ToggleMilestones)
+            )));
+            failed_writer.write(TransactionFailed {
+                reason: TransactionFailureReason::Locked,
+                tile_pos,
+            });
+            return;
+        }
+
+        let price = plots.price();
+        if !budget.can_afford(price) {
+            notify_writer.write(Notify::warning(format!(
+                "Cannot afford land: costs ${}, you have ${}",
+                price, budget.money
+            )));
+            failed_writer.write(TransactionFailed {
+                reason: TransactionFailureReason::Unaffordable,
+                tile_pos,
+            });
+            return;
+        }
+
+        ledger.book(
+            &mut budget,
+            inputs.clock.day,
+            LedgerCategory::LandPurchases,
+            -price,
+        );
+        plots.buy(plot);
+        info!("Bought plot {:?} for ${}", plot, price);
+        notify_writer.write(Notify::info(format!("Bought a new plot for ${}", price)).at(tile_pos));
+        return;
+    }
+}
+
+/// Rebuild the buildable area whenever the city's land changes
+pub fn sync_placeable_map(
+    plots: Res<LandPlots>,
+    mut placeable_map: ResMut<PlaceableMap>,
+    tilemap_size_q: Query<&TilemapSize>,
+    mut synced: Local<bool>,
+) {
+    if *synced && !plots.is_changed() {
+        return;
+    }
+    let Some(map_size) = tilemap_size_q.iter().next() else {
+        return;
+    };
+
+    *placeable_map = plots.placeable_map(map_size);
+    *synced = true;
+}
+
+/// Outline the city's land, and the plots for sale while the land tool is active
+pub fn draw_plot_borders(
+    mut gizmos: Gizmos,
+    plots: Res<LandPlots>,
+    plot_tool: Res<PlotTool>,
+    map_q: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &Transform)>,
+) {
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    let to_world = |point: Vec2| {
+        grid_point_to_world(point, map_size, grid_size, map_type, map_transform).truncate()
+    };
+    // tile coordinates of a plot corner, given in plot coordinates
+    let corner_point = |x: u32, y: u32| {
+        Vec2::new(
+            (x * PLOT_SIZE).min(map_size.x) as f32,
+            (y * PLOT_SIZE).min(map_size.y) as f32,
+        )
+    };
+    let corner = |x: u32, y: u32| to_world(corner_point(x, y));
+
+    for plot in &plots.owned {
+        let (x, y) = (plot.x, plot.y);
+        let edges = [
+            (
+                PlotPos { x, y: y + 1 },
+                corner(x, y + 1),
+                corner(x + 1, y + 1),
+            ),
+            (
+                PlotPos { x: x + 1, y },
+                corner(x + 1, y),
+                corner(x + 1, y + 1),
+            ),
+        ];
+        for (neighbor, from, to) in edges {
+            if !plots.owned.contains(&neighbor) {
+                gizmos.line_2d(from, to, OWNED_BORDER_COLOR);
+            }
+        }
+        if y == 0 || !plots.owned.contains(&PlotPos { x, y: y - 1 }) {
+            gizmos.line_2d(corner(x, y), corner(x + 1, y), OWNED_BORDER_COLOR);
+        }
+        if x == 0 || !plots.owned.contains(&PlotPos { x: x - 1, y }) {
+            gizmos.line_2d(corner(x, y), corner(x, y + 1), OWNED_BORDER_COLOR);
+        }
+    }
+
+    if plot_tool.active {
+        for plot in plots.buyable(map_size) {
+            let outline = block_outline(
+                corner_point(plot.x, plot.y),
+                corner_point(plot.x + 1, plot.y + 1),
+                BUYABLE_PLOT_INSET,
+                to_world,
+            );
+            gizmos.linestrip_2d(outline, BUYABLE_PLOT_COLOR);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: TilemapSize = TilemapSize { x: 32, y: 32 };
+
+    #[test]
+    fn the_city_starts_with_the_plots_around_the_center() {
+        let plots = LandPlots::starting(&MAP);
+
+        assert_eq!(plots.owned.len(), 4);
+        assert!(plots.owns_tile(&TilePos { x: 8, y: 8 }));
+        assert!(plots.owns_tile(&TilePos { x: 23, y: 23 }));
+        assert!(!plots.owns_tile(&TilePos { x: 16, y: 0 }));
+        assert_eq!(plots.placeable_map(&MAP).placeable_tiles.len(), 256);
+    }
+
+    #[test]
+    fn only_bordering_plots_are_for_sale() {
+        let plots = LandPlots::starting(&MAP);
+
+        assert!(plots.is_buyable(&PlotPos { x: 2, y: 0 }, &MAP));
+        assert!(!plots.is_buyable(&PlotPos { x: 0, y: 0 }, &MAP));
+        assert!(!plots.is_buyable(&PlotPos { x: 1, y: 1 }, &MAP));
+        assert!(!plots.is_buyable(&PlotPos { x: 4, y: 2 }, &MAP));
+        assert_eq!(plots.buyable(&MAP).len(), 8);
+    }
+
+    #[test]
+    fn land_gets_pricier_and_milestones_gate_purchases() {
+        let mut plots = LandPlots::starting(&MAP);
+        let first_price = plots.price();
+
+        assert!(plots.can_buy_more(0));
+        plots.buy(PlotPos { x: 2, y: 0 });
+
+        assert!(plots.price() > first_price);
+        assert!(!plots.can_buy_more(0));
+        assert!(plots.can_buy_more(1));
+    }
+}
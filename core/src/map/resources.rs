@@ -4,8 +4,8 @@ use bevy_ecs_tilemap::prelude::*;
 use bevy_image::TextureAtlasLayout;
 use std::collections::HashSet;
 
-/// Texture index for abandoned/demolished tiles in `tiles.png`
-pub const ABANDONED_TEXTURE_INDEX: u32 = 4;
+use crate::budget::{BuildingType, Rotation};
+use crate::settings::GameSettings;
 
 #[derive(Resource, Default)]
 pub struct CursorWorldPos(pub Vec2);
@@ -23,6 +23,12 @@ pub struct CurrentTileType {
     pub texture_index: u32,
 }
 
+/// Which way the next building will face
+#[derive(Resource, Default)]
+pub struct CurrentRotation {
+    pub rotation: Rotation,
+}
+
 /// Tracks which road sprite in `roads.png` is currently selected
 #[derive(Resource, Default)]
 pub struct CurrentRoadVariant {
@@ -64,10 +70,6 @@ impl PlaceableMap {
     pub fn is_placeable(&self, pos: &TilePos) -> bool {
         self.placeable_tiles.contains(pos)
     }
-
-    pub fn mark_placeable(&mut self, pos: TilePos) {
-        self.placeable_tiles.insert(pos);
-    }
 }
 
 pub fn update_cursor_world_pos(
@@ -103,9 +105,22 @@ pub const ROAD_VARIANT_COUNT: usize = 11;
 /// Number of decorative building variants in the `decorative.png` sprite sheet
 pub const DECORATIVE_VARIANT_COUNT: usize = 4;
 
-/// Number of tile variants in the `tiles.png` sprite sheet used for previews
+/// Number of tile variants in the ground tile sheets, also used for previews
 pub const TILE_PREVIEW_VARIANT_COUNT: usize = 5;
 
+/// Ground tile sheet and tile size for the map's projection. `tiles.png` is a column
+/// of 96x96 top-down squares; `tiles_iso.png` holds the same art as 96x48 diamonds.
+pub fn ground_tiles(isometric: bool) -> (&'static str, TilemapTileSize) {
+    if isometric {
+        (
+            "sprites/tiles_iso.png",
+            TilemapTileSize { x: 96.0, y: 48.0 },
+        )
+    } else {
+        ("sprites/tiles.png", TilemapTileSize { x: 96.0, y: 96.0 })
+    }
+}
+
 #[derive(Resource)]
 pub struct ResidentialBuildingAtlas {
     pub texture: Handle<Image>,
@@ -163,15 +178,75 @@ pub struct IndustryBuilding {
 }
 
 #[derive(Component)]
-pub struct RoadSegment {
-    pub tile_pos: TilePos,
-}
+pub struct RoadSegment;
 
 #[derive(Component)]
-pub struct DecorativeBuilding {
-    pub tile_pos: TilePos,
+pub struct DecorativeBuilding;
+
+#[derive(Component)]
+pub struct BusStopBuilding;
+
+#[derive(Component)]
+pub struct SchoolBuilding;
+
+#[derive(Component)]
+pub struct StadiumBuilding;
+
+/// Attach the per-type marker component for `building_type` to a building entity
+pub fn insert_building_marker(
+    building: &mut EntityCommands,
+    building_type: BuildingType,
+    tile_pos: TilePos,
+) {
+    match building_type {
+        BuildingType::Residential => building.insert(ResidentialBuilding { tile_pos }),
+        BuildingType::Commercial => building.insert(CommercialBuilding { tile_pos }),
+        BuildingType::Industry => building.insert(IndustryBuilding { tile_pos }),
+        BuildingType::Road => building.insert(RoadSegment),
+        BuildingType::Decorative => building.insert(DecorativeBuilding),
+        BuildingType::BusStop => building.insert(BusStopBuilding),
+        BuildingType::School => building.insert(SchoolBuilding),
+        BuildingType::Stadium => building.insert(StadiumBuilding),
+    };
 }
 
+pub fn remove_building_marker(building: &mut EntityCommands, building_type: BuildingType) {
+    match building_type {
+        BuildingType::Residential => building.remove::<ResidentialBuilding>(),
+        BuildingType::Commercial => building.remove::<CommercialBuilding>(),
+        BuildingType::Industry => building.remove::<IndustryBuilding>(),
+        BuildingType::Road => building.remove::<RoadSegment>(),
+        BuildingType::Decorative => building.remove::<DecorativeBuilding>(),
+        BuildingType::BusStop => building.remove::<BusStopBuilding>(),
+        BuildingType::School => building.remove::<SchoolBuilding>(),
+        BuildingType::Stadium => building.remove::<StadiumBuilding>(),
+    };
+}
+
+/// Bus stops have no sprite sheet yet, so they are drawn as a flat sign-coloured block
+pub const BUS_STOP_COLOR: Color = Color::srgb(0.95, 0.75, 0.2);
+
+/// Width of the bus stop block in world units, a square top-down and a diamond isometric
+pub const BUS_STOP_SPRITE_SIZE: f32 = 40.0;
+
+/// Schools are drawn as a brick-red block until they get a sprite sheet
+pub const SCHOOL_COLOR: Color = Color::srgb(0.75, 0.3, 0.25);
+
+/// Width of the school block in world units, a square top-down and a diamond isometric
+pub const SCHOOL_SPRITE_SIZE: f32 = 64.0;
+
+/// Stadiums are drawn as a turf-green block covering their whole footprint
+pub const STADIUM_COLOR: Color = Color::srgb(0.3, 0.6, 0.35);
+
+/// Gap left between a multi-tile building's block and the edge of its footprint
+pub const FOOTPRINT_SPRITE_MARGIN: f32 = 8.0;
+
+/// Z of road sprites; each building's depth sorting keeps it within a unit below this
+pub const ROAD_LAYER: f32 = 5.0;
+
+/// Z of every other building sprite, above the roads
+pub const BUILDING_LAYER: f32 = 10.0;
+
 /// Marker component for the semi-transparent road preview shown under the cursor
 #[derive(Component)]
 pub struct RoadHoverPreview;
@@ -286,16 +361,20 @@ pub fn setup_decorative_building_atlas(
     });
 }
 
+/// Tile previews use the same sheet as the ground, so they follow the projection
+/// the new map is laid out in
 pub fn setup_tile_preview_atlas(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let texture: Handle<Image> = asset_server.load("sprites/tiles.png");
+    let (path, tile_size) = ground_tiles(settings.isometric);
+    let texture: Handle<Image> = asset_server.load(path);
 
-    // `tiles.png` is arranged as a single column of 96x96 sprites (5 rows)
+    // both sheets are a single column of tiles (5 rows)
     let layout = layouts.add(TextureAtlasLayout::from_grid(
-        UVec2::new(96, 96),
+        UVec2::new(tile_size.x as u32, tile_size.y as u32),
         1,
         TILE_PREVIEW_VARIANT_COUNT as u32,
         None,
@@ -0,0 +1,218 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::budget::{BuildingType, Footprint, Rotation};
+
+/// Ground textures in `tiles.png` and its isometric twin `tiles_iso.png`
+const UNPLACEABLE_TEXTURE_INDEX: u32 = 0;
+const PLACEABLE_TEXTURE_INDEX: u32 = 1;
+const LARGE_HOUSE_GROUND_TEXTURE_INDEX: u32 = 2;
+const SMALL_HOUSE_GROUND_TEXTURE_INDEX: u32 = 3;
+const BUILT_GROUND_TEXTURE_INDEX: u32 = 4;
+/// Derelict lots keep the built ground but are tinted so they stand out from live ones
+const ABANDONED_TEXTURE_INDEX: u32 = BUILT_GROUND_TEXTURE_INDEX;
+const ABANDONED_TINT: Color = Color::srgb(0.55, 0.42, 0.35);
+
+/// What a map tile currently holds. Systems read and write this instead of the
+/// tile texture, which `sync_tile_textures` derives from it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileState {
+    /// Outside the buildable area
+    #[default]
+    Empty,
+    /// Free ground next to the city that can be built on
+    Placeable,
+    /// Holds the building entity standing on it
+    Occupied(Entity),
+    /// Holds the derelict remains of an abandoned or burnt-down building
+    Abandoned(Entity),
+    /// Ground that can never be built on, such as a highway entry point
+    Terrain,
+}
+
+impl TileState {
+    /// Whether something man-made (a building or its ruin) stands on the tile
+    pub fn is_developed(&self) -> bool {
+        matches!(self, TileState::Occupied(_) | TileState::Abandoned(_))
+    }
+}
+
+/// Type, sprite variant, tile and construction day of a building entity; lives next to the
+/// per-type marker (`ResidentialBuilding`, `RoadSegment`, ...)
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct PlacedBuilding {
+    pub building_type: BuildingType,
+    pub variant: usize,
+    /// Bottom-left tile of the footprint
+    pub tile_pos: TilePos,
+    pub rotation: Rotation,
+    pub placed_day: u32,
+    /// `GameClock::hour_index` when the building went up
+    pub placed_hour: u64,
+}
+
+impl PlacedBuilding {
+    pub fn footprint(&self) -> Footprint {
+        self.building_type.footprint().rotated(self.rotation)
+    }
+
+    /// Every tile the building stands on
+    pub fn tiles(&self) -> impl Iterator<Item = TilePos> {
+        self.footprint().tiles(self.tile_pos)
+    }
+}
+
+/// Read-only lookup from a tile to the building standing on it
+#[derive(SystemParam)]
+pub struct BuildingLookup<'w, 's> {
+    tile_storage_q: Query<'w, 's, &'static TileStorage>,
+    tile_state_q: Query<'w, 's, &'static TileState>,
+    buildings_q: Query<'w, 's, &'static PlacedBuilding>,
+}
+
+impl BuildingLookup<'_, '_> {
+    pub fn tile_state_at(&self, tile_pos: &TilePos) -> Option<TileState> {
+        let tile_storage = self.tile_storage_q.iter().next()?;
+        let tile_entity = tile_storage.get(tile_pos)?;
+        self.tile_state_q.get(tile_entity).ok().copied()
+    }
+
+    /// Entity of the live building or derelict lot standing on `tile_pos`
+    pub fn entity_at(&self, tile_pos: &TilePos) -> Option<Entity> {
+        match self.tile_state_at(tile_pos)? {
+            TileState::Occupied(entity) | TileState::Abandoned(entity) => Some(entity),
+            _ => None,
+        }
+    }
+
+    pub fn building_at(&self, tile_pos: &TilePos) -> Option<PlacedBuilding> {
+        let entity = self.entity_at(tile_pos)?;
+        self.buildings_q.get(entity).ok().copied()
+    }
+}
+
+/// Ground texture drawn under a building
+pub fn building_ground_texture(building: &PlacedBuilding) -> u32 {
+    match building.building_type {
+        // Houses 1-2 (variants 0,1) use tile 3; houses 3-5 (variants 2,3,4) use tile 2
+        BuildingType::Residential if building.variant <= 1 => SMALL_HOUSE_GROUND_TEXTURE_INDEX,
+        BuildingType::Residential => LARGE_HOUSE_GROUND_TEXTURE_INDEX,
+        _ => BUILT_GROUND_TEXTURE_INDEX,
+    }
+}
+
+pub fn tile_texture_for_state(state: TileState, building: Option<&PlacedBuilding>) -> u32 {
+    match state {
+        TileState::Empty | TileState::Terrain => UNPLACEABLE_TEXTURE_INDEX,
+        TileState::Placeable => PLACEABLE_TEXTURE_INDEX,
+        TileState::Occupied(_) => {
+            building.map_or(BUILT_GROUND_TEXTURE_INDEX, building_ground_texture)
+        }
+        TileState::Abandoned(_) => ABANDONED_TEXTURE_INDEX,
+    }
+}
+
+/// Color a tile is drawn with when it isn't highlighted or flashing
+pub fn tile_tint(state: TileState) -> Color {
+    match state {
+        TileState::Abandoned(_) => ABANDONED_TINT,
+        _ => Color::WHITE,
+    }
+}
+
+/// Render tile textures and tints from their `TileState`
+pub fn sync_tile_textures(
+    mut tile_q: Query<(&TileState, &mut TileTextureIndex, &mut TileColor), Changed<TileState>>,
+    buildings_q: Query<&PlacedBuilding>,
+) {
+    for (state, mut texture, mut color) in tile_q.iter_mut() {
+        color.0 = tile_tint(*state);
+
+        let building = match state {
+            TileState::Occupied(entity) => buildings_q.get(*entity).ok(),
+            _ => None,
+        };
+
+        let index = tile_texture_for_state(*state, building);
+        if texture.0 != index {
+            texture.0 = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn residential_ground_depends_on_variant() {
+        let small = PlacedBuilding {
+            building_type: BuildingType::Residential,
+            variant: 1,
+            tile_pos: TilePos { x: 0, y: 0 },
+            rotation: Rotation::North,
+            placed_day: 0,
+            placed_hour: 0,
+        };
+        let large = PlacedBuilding {
+            building_type: BuildingType::Residential,
+            variant: 3,
+            tile_pos: TilePos { x: 0, y: 0 },
+            rotation: Rotation::North,
+            placed_day: 0,
+            placed_hour: 0,
+        };
+
+        assert_eq!(
+            building_ground_texture(&small),
+            SMALL_HOUSE_GROUND_TEXTURE_INDEX
+        );
+        assert_eq!(
+            building_ground_texture(&large),
+            LARGE_HOUSE_GROUND_TEXTURE_INDEX
+        );
+    }
+
+    #[test]
+    fn unbuilt_states_map_to_ground_textures() {
+        assert_eq!(
+            tile_texture_for_state(TileState::Empty, None),
+            UNPLACEABLE_TEXTURE_INDEX
+        );
+        assert_eq!(
+            tile_texture_for_state(TileState::Terrain, None),
+            UNPLACEABLE_TEXTURE_INDEX
+        );
+        assert_eq!(
+            tile_texture_for_state(TileState::Placeable, None),
+            PLACEABLE_TEXTURE_INDEX
+        );
+        assert_eq!(
+            tile_texture_for_state(TileState::Abandoned(Entity::PLACEHOLDER), None),
+            ABANDONED_TEXTURE_INDEX
+        );
+    }
+
+    #[test]
+    fn derelict_lots_look_different_from_built_ones() {
+        let abandoned = TileState::Abandoned(Entity::PLACEHOLDER);
+        let occupied = TileState::Occupied(Entity::PLACEHOLDER);
+
+        assert_eq!(
+            tile_texture_for_state(occupied, None),
+            tile_texture_for_state(abandoned, None)
+        );
+        assert_ne!(tile_tint(abandoned), tile_tint(occupied));
+        assert_eq!(tile_tint(occupied), Color::WHITE);
+    }
+
+    #[test]
+    fn only_buildings_and_ruins_are_developed() {
+        assert!(TileState::Occupied(Entity::PLACEHOLDER).is_developed());
+        assert!(TileState::Abandoned(Entity::PLACEHOLDER).is_developed());
+        assert!(!TileState::Placeable.is_developed());
+        assert!(!TileState::Empty.is_developed());
+        assert!(!TileState::Terrain.is_developed());
+    }
+}
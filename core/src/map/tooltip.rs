@@ -0,0 +1,228 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::helpers::cursor_to_map_pos;
+use super::plots::LandPlots;
+use super::resources::*;
+use super::tile_state::TileState;
+use crate::budget::{Budget, BuildingType};
+use crate::city::resources::{
+    CityPopulation, CityServices, is_accessible, placement_happiness_delta,
+};
+use crate::menu::GameState;
+use crate::progression::Progression;
+use crate::settings::{Action, KeyBindings};
+use crate::spatial::SpatialGrid;
+
+/// Offset from the cursor so the tooltip doesn't sit under the pointer
+const TOOLTIP_CURSOR_OFFSET: Vec2 = Vec2::new(18.0, 18.0);
+
+const TOOLTIP_OK_COLOR: Color = Color::srgb(0.85, 1.0, 0.85);
+const TOOLTIP_BLOCKED_COLOR: Color = Color::srgb(1.0, 0.6, 0.6);
+
+#[derive(Component)]
+pub struct PlacementTooltip;
+
+pub fn setup_placement_tooltip(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(6.0)),
+            display: Display::None,
+            ..default()
+        },
+        TextFont {
+            font,
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(TOOLTIP_OK_COLOR),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        GlobalZIndex(10),
+        PlacementTooltip,
+    ));
+}
+
+/// A tile under the hovered footprint and its state, `None` when it's off the map
+type CoveredTile = (TilePos, Option<TileState>);
+
+#[derive(SystemParam)]
+pub struct TooltipCityInputs<'w> {
+    budget: Res<'w, Budget>,
+    population: Res<'w, CityPopulation>,
+    services: Res<'w, CityServices>,
+    spatial_grid: Res<'w, SpatialGrid>,
+    placeable_map: Res<'w, PlaceableMap>,
+    plots: Res<'w, LandPlots>,
+    progression: Res<'w, Progression>,
+}
+
+#[derive(SystemParam)]
+pub struct TooltipCursorInputs<'w, 's> {
+    cursor_pos: Res<'w, CursorWorldPos>,
+    current_tile_type: Res<'w, CurrentTileType>,
+    current_rotation: Res<'w, CurrentRotation>,
+    bindings: Res<'w, KeyBindings>,
+    state: Res<'w, State<GameState>>,
+    window_q: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    tilemap_q: Query<
+        'w,
+        's,
+        (
+            &'static TilemapSize,
+            &'static TilemapGridSize,
+            &'static TilemapTileSize,
+            &'static TilemapType,
+            &'static TileStorage,
+            &'static Transform,
+            &'static TilemapAnchor,
+        ),
+    >,
+}
+
+/// Show what placing the selected building on the hovered tile would cost and do
+pub fn update_placement_tooltip(
+    cursor: TooltipCursorInputs,
+    city: TooltipCityInputs,
+    tile_state_q: Query<&TileState>,
+    mut tooltip_q: Query<(&mut Text, &mut Node, &mut TextColor), With<PlacementTooltip>>,
+) {
+    let Ok((mut text, mut node, mut color)) = tooltip_q.single_mut() else {
+        return;
+    };
+
+    let in_game = *cursor.state.get() == GameState::InGame;
+    let building_type = BuildingType::from_selection_index(cursor.current_tile_type.texture_index);
+    let screen_cursor = cursor
+        .window_q
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let (Some(building_type), Some(screen_cursor), true) = (building_type, screen_cursor, in_game)
+    else {
+        node.display = Display::None;
+        return;
+    };
+
+    let footprint = building_type
+        .footprint()
+        .rotated(cursor.current_rotation.rotation);
+    let mut hovered: Option<(TilePos, Vec<CoveredTile>)> = None;
+    for (map_size, grid_size, tile_size, map_type, tile_storage, map_transform, anchor) in
+        cursor.tilemap_q.iter()
+    {
+        let cursor_in_map_pos = cursor_to_map_pos(cursor.cursor_pos.0, map_transform);
+
+        if let Some(tile_pos) = TilePos::from_world_pos(
+            &cursor_in_map_pos,
+            map_size,
+            grid_size,
+            tile_size,
+            map_type,
+            anchor,
+        ) {
+            let covered = footprint
+                .tiles(tile_pos)
+                .map(|pos| {
+                    let state = tile_storage
+                        .checked_get(&pos)
+                        .and_then(|tile_entity| tile_state_q.get(tile_entity).ok().copied());
+                    (pos, state)
+                })
+                .collect();
+            hovered = Some((tile_pos, covered));
+            break;
+        }
+    }
+
+    let Some((tile_pos, covered)) = hovered else {
+        node.display = Display::None;
+        return;
+    };
+
+    // a building bigger than a tile is only as buildable as the worst tile under it
+    let (status_pos, status_state) = covered
+        .iter()
+        .copied()
+        .find(|(pos, state)| {
+            *state != Some(TileState::Placeable) || !city.placeable_map.is_placeable(pos)
+        })
+        .unwrap_or(covered[0]);
+
+    let cost = building_type.cost();
+    let affordable = city.budget.can_afford(cost);
+    let placeable = city.placeable_map.is_placeable(&status_pos)
+        && status_state.is_some_and(|state| state != TileState::Terrain);
+    let free = !status_state.is_some_and(|state| state.is_developed());
+    let road_access = if building_type == BuildingType::BusStop {
+        city.spatial_grid.has_road_in_radius(&tile_pos, 1)
+    } else {
+        building_type == BuildingType::Road || is_accessible(&tile_pos, &city.spatial_grid)
+    };
+    let delta = placement_happiness_delta(
+        building_type,
+        &tile_pos,
+        &city.population,
+        &city.services,
+        &city.spatial_grid,
+    );
+
+    let tile_status = if status_state.is_none() {
+        "off the map".to_string()
+    } else if !city.plots.owns_tile(&status_pos) {
+        format!(
+            "not your land, buy it with {}",
+            cursor.bindings.label(Action::BuyLand)
+        )
+    } else if !placeable {
+        "not placeable".to_string()
+    } else if let Some(TileState::Abandoned(_)) = status_state {
+        "derelict, clear it first".to_string()
+    } else if !free {
+        "occupied".to_string()
+    } else {
+        "free".to_string()
+    };
+    let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+    text.0 = format!(
+        "{}: ${}\nAffordable: {}\nTile: {}\nRoad access: {}\nHappiness: {:+.3}",
+        building_type.label(),
+        cost,
+        yes_no(affordable),
+        tile_status,
+        yes_no(road_access),
+        delta,
+    );
+
+    let locked = !city.progression.is_unlocked(building_type);
+    if locked {
+        text.0
+            .push_str(&match city.progression.locked_until(building_type) {
+                Some(milestone) => format!("\nLocked until the {} milestone", milestone.name),
+                None => "\nNot available in this scenario".to_string(),
+            });
+    }
+
+    let blocked = locked
+        || !affordable
+        || !placeable
+        || !free
+        || (building_type == BuildingType::BusStop && !road_access);
+    color.0 = if blocked {
+        TOOLTIP_BLOCKED_COLOR
+    } else {
+        TOOLTIP_OK_COLOR
+    };
+
+    let origin = screen_cursor + TOOLTIP_CURSOR_OFFSET;
+    node.left = Val::Px(origin.x);
+    node.top = Val::Px(origin.y);
+    node.display = Display::Flex;
+}
@@ -0,0 +1,373 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::save;
+use crate::scenario::ScenarioCatalog;
+use crate::settings::GameSettings;
+
+use super::resources::{GameState, SettingsPanelState};
+
+const MENU_BACKGROUND: Color = Color::srgb(0.08, 0.1, 0.12);
+const BUTTON_COLOR: Color = Color::srgba(0.8, 0.8, 0.8, 1.0);
+const SELECTED_BUTTON_COLOR: Color = Color::srgb(0.55, 0.85, 0.55);
+
+/// What a menu button does when pressed
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum MenuButton {
+    NewGame,
+    LoadGame,
+    Settings,
+    RandomEvents,
+    MapView,
+    EdgePanning,
+    Quit,
+    /// Pick a scenario on the new-game screen; `None` is the sandbox
+    PickScenario(Option<usize>),
+    Start,
+    Back,
+    /// Write the running city to disk from the pause screen
+    SaveGame,
+    /// Leave the running city for the main menu
+    MainMenu,
+}
+
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Component)]
+pub struct RandomEventsLabel;
+
+#[derive(Component)]
+pub struct MapViewLabel;
+
+#[derive(Component)]
+pub struct EdgePanningLabel;
+
+#[derive(Component)]
+pub struct ScenarioDescriptionText;
+
+fn text(font: &Handle<Font>, content: impl Into<String>, size: f32, color: Color) -> impl Bundle {
+    (
+        Text::new(content),
+        TextFont {
+            font: font.clone(),
+            font_size: size,
+            ..default()
+        },
+        TextColor(color),
+    )
+}
+
+fn button(font: &Handle<Font>, label: impl Into<String>, action: MenuButton) -> impl Bundle {
+    (
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_COLOR),
+        action,
+        children![text(font, label, 16.0, Color::BLACK)],
+    )
+}
+
+/// Opaque full-screen backdrop that hides the HUD and swallows clicks
+fn screen_root(state: GameState) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.0),
+            left: Val::Px(0.0),
+            right: Val::Px(0.0),
+            bottom: Val::Px(0.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(MENU_BACKGROUND),
+        FocusPolicy::Block,
+        GlobalZIndex(30),
+        DespawnOnExit(state),
+    )
+}
+
+fn random_events_label(settings: &GameSettings) -> String {
+    format!(
+        "Random events: {}",
+        if settings.random_events { "ON" } else { "OFF" }
+    )
+}
+
+fn map_view_label(settings: &GameSettings) -> String {
+    format!(
+        "Map view: {}",
+        if settings.isometric {
+            "ISOMETRIC"
+        } else {
+            "TOP-DOWN"
+        }
+    )
+}
+
+fn edge_panning_label(settings: &GameSettings) -> String {
+    format!(
+        "Edge panning: {}",
+        if settings.edge_panning { "ON" } else { "OFF" }
+    )
+}
+
+pub fn setup_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    settings_panel: Res<SettingsPanelState>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands
+        .spawn(screen_root(GameState::MainMenu))
+        .with_children(|menu| {
+            menu.spawn(text(&font, "GOROD", 48.0, Color::WHITE));
+            menu.spawn(button(&font, "New game", MenuButton::NewGame));
+            if save::has_saved_city() {
+                menu.spawn(button(&font, "Load game", MenuButton::LoadGame));
+            }
+            menu.spawn(button(&font, "Settings", MenuButton::Settings));
+            menu.spawn(button(&font, "Quit", MenuButton::Quit));
+
+            menu.spawn((
+                Node {
+                    display: if settings_panel.open {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    padding: UiRect::all(Val::Px(12.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                SettingsPanel,
+            ))
+            .with_children(|panel| {
+                panel.spawn(text(&font, "Settings", 16.0, Color::WHITE));
+                panel.spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(BUTTON_COLOR),
+                    MenuButton::RandomEvents,
+                    children![(
+                        text(&font, random_events_label(&settings), 12.0, Color::BLACK),
+                        RandomEventsLabel,
+                    )],
+                ));
+                panel.spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(BUTTON_COLOR),
+                    MenuButton::MapView,
+                    children![(
+                        text(&font, map_view_label(&settings), 12.0, Color::BLACK),
+                        MapViewLabel,
+                    )],
+                ));
+                panel.spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(BUTTON_COLOR),
+                    MenuButton::EdgePanning,
+                    children![(
+                        text(&font, edge_panning_label(&settings), 12.0, Color::BLACK),
+                        EdgePanningLabel,
+                    )],
+                ));
+            });
+        });
+}
+
+/// Save and leave buttons along the bottom of the screen while the game is paused
+pub fn setup_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(24.0),
+            left: Val::Px(0.0),
+            right: Val::Px(0.0),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(12.0),
+            ..default()
+        },
+        GlobalZIndex(25),
+        DespawnOnExit(GameState::Paused),
+        children![
+            button(&font, "Save game", MenuButton::SaveGame),
+            button(&font, "Main menu", MenuButton::MainMenu),
+        ],
+    ));
+}
+
+/// What the new-game screen says about the sandbox or a scenario
+fn describe_choice(catalog: &ScenarioCatalog) -> String {
+    let Some(scenario) = catalog.selected_scenario() else {
+        return "Free play. Milestones unlock new buildings as the city grows.".to_string();
+    };
+
+    let mut lines = vec![scenario.description.clone()];
+    lines.push(format!(
+        "Budget: ${}, map {}x{}",
+        scenario.starting_budget, scenario.map_size.0, scenario.map_size.1
+    ));
+    lines.extend(
+        scenario
+            .objectives
+            .iter()
+            .map(|objective| objective.describe()),
+    );
+    lines.extend(
+        scenario
+            .fail_conditions
+            .iter()
+            .map(|condition| condition.describe()),
+    );
+    lines.join("\n")
+}
+
+fn choice_color(catalog: &ScenarioCatalog, choice: Option<usize>) -> Color {
+    if catalog.selected == choice {
+        SELECTED_BUTTON_COLOR
+    } else {
+        BUTTON_COLOR
+    }
+}
+
+pub fn setup_new_game_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Res<ScenarioCatalog>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands
+        .spawn(screen_root(GameState::NewGameSetup))
+        .with_children(|screen| {
+            screen
+                .spawn((
+                    Node {
+                        width: Val::Px(520.0),
+                        padding: UiRect::all(Val::Px(24.0)),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|card| {
+                    card.spawn(text(&font, "NEW GAME", 16.0, Color::WHITE));
+
+                    let choices = std::iter::once((None, "Sandbox")).chain(
+                        catalog
+                            .scenarios
+                            .iter()
+                            .enumerate()
+                            .map(|(index, scenario)| (Some(index), scenario.name.as_str())),
+                    );
+                    for (choice, name) in choices {
+                        card.spawn(button(&font, name, MenuButton::PickScenario(choice)))
+                            .insert(BackgroundColor(choice_color(&catalog, choice)));
+                    }
+
+                    card.spawn((
+                        text(&font, describe_choice(&catalog), 12.0, Color::WHITE),
+                        ScenarioDescriptionText,
+                    ));
+
+                    card.spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(button(&font, "Back", MenuButton::Back));
+                        row.spawn(button(&font, "Start", MenuButton::Start));
+                    });
+                });
+        });
+}
+
+type EdgePanningLabelOnly = (
+    With<EdgePanningLabel>,
+    Without<RandomEventsLabel>,
+    Without<MapViewLabel>,
+);
+
+pub fn update_settings_panel(
+    settings: Res<GameSettings>,
+    settings_panel: Res<SettingsPanelState>,
+    mut panel_q: Query<&mut Node, With<SettingsPanel>>,
+    mut label_q: Query<&mut Text, With<RandomEventsLabel>>,
+    mut map_view_q: Query<&mut Text, (With<MapViewLabel>, Without<RandomEventsLabel>)>,
+    mut edge_panning_q: Query<&mut Text, EdgePanningLabelOnly>,
+) {
+    if !settings.is_changed() && !settings_panel.is_changed() {
+        return;
+    }
+
+    for mut node in panel_q.iter_mut() {
+        node.display = if settings_panel.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for mut text in label_q.iter_mut() {
+        text.0 = random_events_label(&settings);
+    }
+    for mut text in map_view_q.iter_mut() {
+        text.0 = map_view_label(&settings);
+    }
+    for mut text in edge_panning_q.iter_mut() {
+        text.0 = edge_panning_label(&settings);
+    }
+}
+
+/// Highlight the picked scenario and show what it asks of the player
+pub fn update_scenario_choices(
+    catalog: Res<ScenarioCatalog>,
+    mut button_q: Query<(&MenuButton, &mut BackgroundColor)>,
+    mut description_q: Query<&mut Text, With<ScenarioDescriptionText>>,
+) {
+    if !catalog.is_changed() {
+        return;
+    }
+
+    for (button, mut color) in button_q.iter_mut() {
+        if let MenuButton::PickScenario(choice) = *button {
+            color.0 = choice_color(&catalog, choice);
+        }
+    }
+    for mut text in description_q.iter_mut() {
+        text.0 = describe_choice(&catalog);
+    }
+}
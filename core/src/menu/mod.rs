@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::scenario::RetryScenario;
+
+mod display;
+mod resources;
+mod systems;
+
+pub use resources::{GAME_EXIT, GAME_START, GameState, reset_resource};
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>()
+            .init_resource::<resources::SettingsPanelState>()
+            .add_systems(
+                OnEnter(GameState::MainMenu),
+                display::setup_main_menu.run_if(not(resource_exists::<RetryScenario>)),
+            )
+            .add_systems(
+                OnEnter(GameState::NewGameSetup),
+                display::setup_new_game_screen,
+            )
+            .add_systems(OnEnter(GameState::Paused), display::setup_pause_menu)
+            .add_systems(
+                Update,
+                (
+                    systems::handle_menu_buttons,
+                    display::update_settings_panel,
+                    display::update_scenario_choices,
+                )
+                    .chain()
+                    .run_if(
+                        in_state(GameState::MainMenu)
+                            .or(in_state(GameState::NewGameSetup))
+                            .or(in_state(GameState::Paused)),
+                    ),
+            );
+    }
+}
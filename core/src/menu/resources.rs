@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+/// Which screen the game is on. Simulation and map input only run `InGame`.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    NewGameSetup,
+    InGame,
+    /// The help overlay is open and the city is frozen underneath it
+    Paused,
+    /// A scenario was won or lost and the results screen is up
+    GameOver,
+}
+
+/// Transitions that start a city: from the new-game screen, or straight from
+/// the main menu when loading. Systems that build the world run on these.
+pub const GAME_START: [OnTransition<GameState>; 2] = [
+    OnTransition {
+        exited: GameState::NewGameSetup,
+        entered: GameState::InGame,
+    },
+    OnTransition {
+        exited: GameState::MainMenu,
+        entered: GameState::InGame,
+    },
+];
+
+/// Transitions that leave a city for the main menu. Systems that tear the world
+/// down run on these, so the next `GAME_START` begins from a clean slate.
+pub const GAME_EXIT: [OnTransition<GameState>; 2] = [
+    OnTransition {
+        exited: GameState::Paused,
+        entered: GameState::MainMenu,
+    },
+    OnTransition {
+        exited: GameState::GameOver,
+        entered: GameState::MainMenu,
+    },
+];
+
+/// Put a city resource back the way a fresh app starts with it. Plugins run this
+/// on `GAME_EXIT` for every resource that belongs to the city.
+pub fn reset_resource<R: Resource + FromWorld>(mut commands: Commands) {
+    commands.remove_resource::<R>();
+    commands.init_resource::<R>();
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct SettingsPanelState {
+    pub open: bool,
+}
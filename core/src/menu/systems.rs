@@ -0,0 +1,77 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::save::{self, LoadedCity, SaveCity};
+use crate::scenario::{ActiveScenario, ScenarioCatalog};
+use crate::settings::GameSettings;
+
+use super::display::MenuButton;
+use super::resources::{GameState, SettingsPanelState};
+
+#[derive(SystemParam)]
+pub struct MenuChoices<'w> {
+    catalog: ResMut<'w, ScenarioCatalog>,
+    settings: ResMut<'w, GameSettings>,
+    settings_panel: ResMut<'w, SettingsPanelState>,
+}
+
+pub fn handle_menu_buttons(
+    mut commands: Commands,
+    interaction_q: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut choices: MenuChoices,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut save_writer: MessageWriter<SaveCity>,
+    mut exit_writer: MessageWriter<AppExit>,
+) {
+    for (interaction, button) in interaction_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match *button {
+            MenuButton::NewGame => next_state.set(GameState::NewGameSetup),
+            MenuButton::LoadGame => match save::load_city() {
+                Ok(city) => {
+                    if let Some(name) = &city.scenario {
+                        match choices.catalog.scenarios.iter().find(|s| s.name == *name) {
+                            Some(scenario) => {
+                                commands.insert_resource(ActiveScenario(scenario.clone()))
+                            }
+                            None => warn!("Saved scenario {} is gone, loading as sandbox", name),
+                        }
+                    }
+                    commands.insert_resource(LoadedCity(city));
+                    next_state.set(GameState::InGame);
+                }
+                Err(err) => error!("Could not load the city: {}", err),
+            },
+            MenuButton::Settings => {
+                choices.settings_panel.open = !choices.settings_panel.open;
+            }
+            MenuButton::RandomEvents => {
+                choices.settings.random_events = !choices.settings.random_events;
+            }
+            MenuButton::MapView => {
+                choices.settings.isometric = !choices.settings.isometric;
+            }
+            MenuButton::EdgePanning => {
+                choices.settings.edge_panning = !choices.settings.edge_panning;
+            }
+            MenuButton::Quit => {
+                exit_writer.write(AppExit::Success);
+            }
+            MenuButton::PickScenario(index) => choices.catalog.selected = index,
+            MenuButton::Start => {
+                if let Some(scenario) = choices.catalog.selected_scenario() {
+                    commands.insert_resource(ActiveScenario(scenario.clone()));
+                }
+                next_state.set(GameState::InGame);
+            }
+            MenuButton::Back => next_state.set(GameState::MainMenu),
+            MenuButton::SaveGame => {
+                save_writer.write(SaveCity);
+            }
+            MenuButton::MainMenu => next_state.set(GameState::MainMenu),
+        }
+    }
+}
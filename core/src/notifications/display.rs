@@ -0,0 +1,347 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy_ecs_tilemap::prelude::TilePos;
+use std::time::Duration;
+
+use crate::camera::FocusCameraOnTile;
+use crate::map::UiClickBlocker;
+use crate::settings::{Action, KeyBindings};
+
+use super::events::Notify;
+use super::resources::{NotificationLog, NotificationPanelState};
+
+/// Real-time seconds a toast stays on screen
+const TOAST_SECONDS: f32 = 4.0;
+const MAX_TOASTS: usize = 4;
+/// Pixels scrolled per mouse wheel line
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+#[derive(Component)]
+pub struct ToastContainer;
+
+#[derive(Component)]
+pub struct Toast {
+    timer: Timer,
+}
+
+impl Toast {
+    fn new() -> Self {
+        Self {
+            timer: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once),
+        }
+    }
+
+    /// Advance the toast's clock; true once it has been up long enough
+    fn tick(&mut self, delta: Duration) -> bool {
+        self.timer.tick(delta).is_finished()
+    }
+}
+
+/// With the screen full of toasts, take the one that has been up longest out of
+/// `live` (entity and seconds shown) so a new one can take its place
+fn make_room(live: &mut Vec<(Entity, f32)>) -> Option<Entity> {
+    if live.len() < MAX_TOASTS {
+        return None;
+    }
+    let oldest = live
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.1.total_cmp(&b.1.1))
+        .map(|(index, _)| index)?;
+    Some(live.remove(oldest).0)
+}
+
+#[derive(Component)]
+pub struct NotificationLogButton;
+
+#[derive(Component)]
+pub struct NotificationLogList;
+
+#[derive(Component)]
+pub struct NotificationEntry {
+    tile_pos: Option<TilePos>,
+}
+
+#[derive(Resource)]
+pub struct NotificationFont(Handle<Font>);
+
+pub fn setup_notification_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<KeyBindings>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    // Toasts stack below the "Selected" bar in the middle of the screen
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(44.0),
+            left: Val::Px(0.0),
+            right: Val::Px(0.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        ToastContainer,
+    ));
+
+    commands
+        .spawn((Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(120.0),
+            right: Val::Px(10.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.8, 0.8, 0.8, 1.0)),
+                    NotificationLogButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new(format!("Log ({})", bindings.label(Action::ToggleEventLog))),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::BLACK),
+                    ));
+                });
+
+            parent.spawn((
+                Node {
+                    display: Display::None,
+                    width: Val::Px(440.0),
+                    height: Val::Px(260.0),
+                    padding: UiRect::all(Val::Px(6.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                ScrollPosition::default(),
+                RelativeCursorPosition::default(),
+                NotificationLogList,
+            ));
+        });
+
+    commands.insert_resource(NotificationFont(font));
+}
+
+pub fn spawn_notification_toasts(
+    mut commands: Commands,
+    font: Res<NotificationFont>,
+    mut notify_reader: MessageReader<Notify>,
+    container_q: Query<Entity, With<ToastContainer>>,
+    toasts_q: Query<(Entity, &Toast)>,
+) {
+    let Ok(container) = container_q.single() else {
+        return;
+    };
+
+    let mut live: Vec<(Entity, f32)> = toasts_q
+        .iter()
+        .map(|(entity, toast)| (entity, toast.timer.elapsed_secs()))
+        .collect();
+
+    for notify in notify_reader.read() {
+        if let Some(oldest) = make_room(&mut live) {
+            commands.entity(oldest).despawn();
+        }
+
+        let toast = commands
+            .spawn((
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                Toast::new(),
+                children![(
+                    Text::new(notify.message.clone()),
+                    TextFont {
+                        font: font.0.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(notify.severity.color()),
+                )],
+            ))
+            .id();
+        commands.entity(container).add_child(toast);
+        live.push((toast, 0.0));
+    }
+}
+
+pub fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts_q: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in toasts_q.iter_mut() {
+        if toast.tick(time.delta()) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn handle_notification_log_button(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<NotificationLogButton>)>,
+    mut panel: ResMut<NotificationPanelState>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    for interaction in interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_click_blocker.just_clicked_ui = true;
+            panel.open = !panel.open;
+        }
+    }
+}
+
+/// Show or hide the log panel and rebuild its entries whenever the log changes
+pub fn update_notification_panel(
+    mut commands: Commands,
+    font: Res<NotificationFont>,
+    panel: Res<NotificationPanelState>,
+    log: Res<NotificationLog>,
+    mut list_q: Query<(Entity, &mut Node), With<NotificationLogList>>,
+) {
+    if !panel.is_changed() && !log.is_changed() {
+        return;
+    }
+
+    let Ok((list, mut node)) = list_q.single_mut() else {
+        return;
+    };
+
+    node.display = if panel.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    if !panel.open {
+        return;
+    }
+
+    commands.entity(list).despawn_children();
+    commands.entity(list).with_children(|list| {
+        if log.entries.is_empty() {
+            list.spawn((
+                Text::new("Nothing has happened yet"),
+                TextFont {
+                    font: font.0.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        }
+
+        for entry in log.entries.iter() {
+            list.spawn((
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                NotificationEntry {
+                    tile_pos: entry.tile_pos,
+                },
+                children![(
+                    Text::new(format!("[{}] {}", entry.timestamp(), entry.message)),
+                    TextFont {
+                        font: font.0.clone(),
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(entry.severity.color()),
+                )],
+            ));
+        }
+    });
+}
+
+pub fn handle_notification_entry_clicks(
+    interaction_q: Query<(&Interaction, &NotificationEntry), Changed<Interaction>>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+    mut focus_writer: MessageWriter<FocusCameraOnTile>,
+) {
+    for (interaction, entry) in interaction_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        ui_click_blocker.just_clicked_ui = true;
+        if let Some(tile_pos) = entry.tile_pos {
+            focus_writer.write(FocusCameraOnTile { tile_pos });
+        }
+    }
+}
+
+pub fn scroll_notification_log(
+    mut wheel_reader: MessageReader<MouseWheel>,
+    mut list_q: Query<(&mut ScrollPosition, &RelativeCursorPosition), With<NotificationLogList>>,
+) {
+    let Ok((mut scroll, cursor)) = list_q.single_mut() else {
+        wheel_reader.clear();
+        return;
+    };
+
+    for wheel in wheel_reader.read() {
+        if !cursor.cursor_over() {
+            continue;
+        }
+
+        let dy = match wheel.unit {
+            MouseScrollUnit::Line => wheel.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => wheel.y,
+        };
+        scroll.0.y = (scroll.0.y - dy).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_expire_after_their_time_on_screen() {
+        let mut toast = Toast::new();
+
+        assert!(!toast.tick(Duration::from_secs_f32(TOAST_SECONDS - 0.5)));
+        assert!(toast.tick(Duration::from_secs_f32(0.5)));
+    }
+
+    #[test]
+    fn a_full_screen_drops_the_oldest_toast() {
+        let entity = |index| Entity::from_raw_u32(index).unwrap();
+        let mut live: Vec<(Entity, f32)> = (0..MAX_TOASTS as u32 - 1)
+            .map(|index| (entity(index), index as f32))
+            .collect();
+
+        assert_eq!(make_room(&mut live), None);
+
+        live.push((entity(10), 0.5));
+        assert_eq!(make_room(&mut live), Some(entity(MAX_TOASTS as u32 - 2)));
+        assert_eq!(live.len(), MAX_TOASTS - 1);
+    }
+}
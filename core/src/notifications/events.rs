@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+
+use super::resources::Severity;
+
+/// Ask the notification system to show a toast and record an entry in the log
+#[derive(Message)]
+pub struct Notify {
+    pub severity: Severity,
+    pub message: String,
+    pub tile_pos: Option<TilePos>,
+}
+
+impl Notify {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Info,
+            message: message.into(),
+            tile_pos: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            tile_pos: None,
+        }
+    }
+
+    pub fn critical(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Critical,
+            message: message.into(),
+            tile_pos: None,
+        }
+    }
+
+    /// Attach the tile the notification is about, so the log can jump to it
+    pub fn at(mut self, tile_pos: TilePos) -> Self {
+        self.tile_pos = Some(tile_pos);
+        self
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::menu::{GAME_EXIT, GameState, reset_resource};
+
+mod display;
+mod events;
+mod resources;
+mod systems;
+
+pub use events::Notify;
+
+pub struct NotificationPlugin;
+
+impl Plugin for NotificationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<resources::NotificationLog>()
+            .init_resource::<resources::NotificationPanelState>()
+            .add_message::<Notify>()
+            .add_systems(Startup, display::setup_notification_ui)
+            .add_systems(
+                Update,
+                (
+                    systems::record_notifications,
+                    display::spawn_notification_toasts,
+                    display::expire_toasts,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::toggle_notification_panel_on_key.run_if(in_state(GameState::InGame)),
+                    display::handle_notification_log_button,
+                    display::update_notification_panel,
+                )
+                    .chain()
+                    .after(systems::record_notifications),
+            )
+            .add_systems(
+                Update,
+                (
+                    display::handle_notification_entry_clicks,
+                    display::scroll_notification_log,
+                ),
+            );
+
+        for exit in GAME_EXIT {
+            app.add_systems(exit, reset_resource::<resources::NotificationLog>);
+        }
+    }
+}
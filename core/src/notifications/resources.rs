@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+use std::collections::VecDeque;
+
+/// How many notifications the log keeps before dropping the oldest ones
+const MAX_LOG_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn color(&self) -> Color {
+        match self {
+            Severity::Info => Color::WHITE,
+            Severity::Warning => Color::srgb(1.0, 0.8, 0.3),
+            Severity::Critical => Color::srgb(1.0, 0.3, 0.3),
+        }
+    }
+}
+
+/// A notification stamped with the in‑game time it was raised at
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub severity: Severity,
+    pub day: u32,
+    pub hour: u8,
+    pub minute: u8,
+    pub message: String,
+    pub tile_pos: Option<TilePos>,
+}
+
+impl Notification {
+    pub fn timestamp(&self) -> String {
+        format!("Day {} {:02}:{:02}", self.day, self.hour, self.minute)
+    }
+}
+
+/// Newest-first history of everything the city told the player
+#[derive(Resource, Default)]
+pub struct NotificationLog {
+    pub entries: VecDeque<Notification>,
+}
+
+impl NotificationLog {
+    pub fn push(&mut self, notification: Notification) {
+        self.entries.push_front(notification);
+        self.entries.truncate(MAX_LOG_ENTRIES);
+    }
+}
+
+/// Whether the scrollable log panel is currently shown
+#[derive(Resource, Default)]
+pub struct NotificationPanelState {
+    pub open: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u32) -> Notification {
+        Notification {
+            severity: Severity::Info,
+            day,
+            hour: 8,
+            minute: 0,
+            message: format!("day {}", day),
+            tile_pos: None,
+        }
+    }
+
+    #[test]
+    fn the_log_keeps_the_newest_entries_first() {
+        let mut log = NotificationLog::default();
+        for day in 1..=MAX_LOG_ENTRIES as u32 + 5 {
+            log.push(entry(day));
+        }
+
+        assert_eq!(log.entries.len(), MAX_LOG_ENTRIES);
+        assert_eq!(log.entries.front().unwrap().day, MAX_LOG_ENTRIES as u32 + 5);
+        assert_eq!(log.entries.back().unwrap().day, 6);
+    }
+
+    #[test]
+    fn timestamps_pad_the_clock() {
+        let notification = Notification {
+            hour: 7,
+            minute: 5,
+            ..entry(3)
+        };
+
+        assert_eq!(notification.timestamp(), "Day 3 07:05");
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::settings::{Action, ActionInput};
+use crate::time::GameClock;
+
+use super::events::Notify;
+use super::resources::{Notification, NotificationLog, NotificationPanelState};
+
+/// Stamp incoming notifications with the game clock and store them in the log
+pub fn record_notifications(
+    clock: Res<GameClock>,
+    mut log: ResMut<NotificationLog>,
+    mut notify_reader: MessageReader<Notify>,
+) {
+    for notify in notify_reader.read() {
+        log.push(Notification {
+            severity: notify.severity,
+            day: clock.day,
+            hour: clock.hour,
+            minute: clock.minute,
+            message: notify.message.clone(),
+            tile_pos: notify.tile_pos,
+        });
+    }
+}
+
+pub fn toggle_notification_panel_on_key(
+    actions: ActionInput,
+    mut panel: ResMut<NotificationPanelState>,
+) {
+    if actions.just_pressed(Action::ToggleEventLog) {
+        panel.open = !panel.open;
+    }
+}
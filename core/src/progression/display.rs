@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+
+use crate::city::resources::CityPopulation;
+use crate::map::UiClickBlocker;
+use crate::settings::{Action, KeyBindings};
+
+use super::resources::{MILESTONES, MilestonePanelState, Progression};
+
+const REACHED_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+const NEXT_COLOR: Color = Color::WHITE;
+const LATER_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+#[derive(Component)]
+pub struct MilestoneButton;
+
+/// Label of the milestone button, with the key that opens the list
+#[derive(Component)]
+pub struct MilestoneButtonText {
+    key: String,
+}
+
+#[derive(Component)]
+pub struct MilestoneList;
+
+#[derive(Resource)]
+pub struct MilestoneFont(Handle<Font>);
+
+pub fn setup_milestone_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<KeyBindings>,
+) {
+    let key = bindings.label(Action::ToggleMilestones);
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    // Button sits just above the build bar, the list opens upwards from it
+    commands
+        .spawn((Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(60.0),
+            right: Val::Px(10.0),
+            flex_direction: FlexDirection::ColumnReverse,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.8, 0.8, 0.8, 1.0)),
+                    MilestoneButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new(format!("Milestones ({})", key)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::BLACK),
+                        MilestoneButtonText { key },
+                    ));
+                });
+
+            parent.spawn((
+                Node {
+                    display: Display::None,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                MilestoneList,
+            ));
+        });
+
+    commands.insert_resource(MilestoneFont(font));
+}
+
+pub fn handle_milestone_button(
+    interaction_q: Query<&Interaction, (Changed<Interaction>, With<MilestoneButton>)>,
+    mut panel: ResMut<MilestonePanelState>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    for interaction in interaction_q.iter() {
+        if *interaction == Interaction::Pressed {
+            ui_click_blocker.just_clicked_ui = true;
+            panel.open = !panel.open;
+        }
+    }
+}
+
+/// Keep the button label and the milestone list in step with the city
+pub fn update_milestone_panel(
+    mut commands: Commands,
+    font: Res<MilestoneFont>,
+    panel: Res<MilestonePanelState>,
+    progression: Res<Progression>,
+    population: Res<CityPopulation>,
+    mut button_text_q: Query<(&mut Text, &MilestoneButtonText)>,
+    mut list_q: Query<(Entity, &mut Node), With<MilestoneList>>,
+) {
+    if !panel.is_changed() && !progression.is_changed() && !population.is_changed() {
+        return;
+    }
+
+    if let Ok((mut text, label)) = button_text_q.single_mut() {
+        text.0 = format!(
+            "Milestones {}/{} ({})",
+            progression.reached.min(MILESTONES.len()),
+            MILESTONES.len(),
+            label.key
+        );
+    }
+
+    let Ok((list, mut node)) = list_q.single_mut() else {
+        return;
+    };
+
+    node.display = if panel.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    if !panel.open {
+        return;
+    }
+
+    commands.entity(list).despawn_children();
+    commands.entity(list).with_children(|list| {
+        list.spawn((
+            Text::new(format!(
+                "Map regions unlocked: {}",
+                progression.regions_unlocked()
+            )),
+            TextFont {
+                font: font.0.clone(),
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(NEXT_COLOR),
+        ));
+
+        for (index, milestone) in MILESTONES.iter().enumerate() {
+            let (status, color) = if index < progression.reached {
+                ("done".to_string(), REACHED_COLOR)
+            } else if index == progression.reached {
+                (
+                    format!(
+                        "{}/{} citizens, {:.0}/{:.0}% happy",
+                        population.population,
+                        milestone.population,
+                        population.happiness * 100.0,
+                        milestone.happiness * 100.0
+                    ),
+                    NEXT_COLOR,
+                )
+            } else {
+                (
+                    format!(
+                        "{} citizens, {:.0}% happy",
+                        milestone.population,
+                        milestone.happiness * 100.0
+                    ),
+                    LATER_COLOR,
+                )
+            };
+
+            list.spawn((
+                Text::new(format!(
+                    "{}: {}\n  unlocks {}",
+                    milestone.name,
+                    status,
+                    milestone.describe_unlocks()
+                )),
+                TextFont {
+                    font: font.0.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(color),
+            ));
+        }
+    });
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::menu::{GAME_EXIT, GAME_START, GameState, reset_resource};
+
+mod display;
+mod resources;
+mod systems;
+
+pub use resources::Progression;
+
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Progression>()
+            .init_resource::<resources::MilestonePanelState>()
+            .add_systems(Startup, display::setup_milestone_panel)
+            .add_systems(
+                Update,
+                (
+                    (
+                        systems::check_milestones,
+                        systems::reject_locked_selection,
+                        systems::toggle_milestone_panel_on_key,
+                    )
+                        .run_if(in_state(GameState::InGame)),
+                    display::handle_milestone_button,
+                    display::update_milestone_panel,
+                )
+                    .chain(),
+            );
+
+        for start in GAME_START {
+            app.add_systems(start, systems::reset_progress);
+        }
+        for exit in GAME_EXIT {
+            app.add_systems(exit, reset_resource::<Progression>);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+
+use crate::budget::BuildingType;
+
+/// Something a milestone hands out besides its cash reward
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unlock {
+    Building(BuildingType),
+    /// Raises how many sprite variants of a building type can appear
+    Variants(BuildingType, usize),
+    /// Permission to buy one more map region
+    Region,
+}
+
+pub struct Milestone {
+    pub name: &'static str,
+    pub population: i64,
+    pub happiness: f32,
+    pub reward: i64,
+    pub unlocks: &'static [Unlock],
+}
+
+pub const MILESTONES: [Milestone; 5] = [
+    Milestone {
+        name: "Village",
+        population: 50,
+        happiness: 0.6,
+        reward: 5_000,
+        unlocks: &[Unlock::Building(BuildingType::Decorative), Unlock::Region],
+    },
+    Milestone {
+        name: "Town",
+        population: 150,
+        happiness: 0.65,
+        reward: 10_000,
+        unlocks: &[
+            Unlock::Building(BuildingType::School),
+            Unlock::Variants(BuildingType::Commercial, 4),
+        ],
+    },
+    Milestone {
+        name: "Small City",
+        population: 300,
+        happiness: 0.7,
+        reward: 20_000,
+        unlocks: &[
+            Unlock::Building(BuildingType::BusStop),
+            Unlock::Variants(BuildingType::Industry, 2),
+            Unlock::Region,
+        ],
+    },
+    Milestone {
+        name: "City",
+        population: 600,
+        happiness: 0.75,
+        reward: 40_000,
+        unlocks: &[
+            Unlock::Building(BuildingType::Stadium),
+            Unlock::Region,
+            Unlock::Region,
+        ],
+    },
+    Milestone {
+        name: "Metropolis",
+        population: 1200,
+        happiness: 0.8,
+        reward: 80_000,
+        unlocks: &[Unlock::Region, Unlock::Region],
+    },
+];
+
+/// Sprite variants available before any milestone; unlisted types get all of them
+const STARTING_VARIANTS: [(BuildingType, usize); 2] =
+    [(BuildingType::Commercial, 2), (BuildingType::Industry, 1)];
+
+impl Milestone {
+    pub fn is_met(&self, population: i64, happiness: f32) -> bool {
+        population >= self.population && happiness >= self.happiness
+    }
+
+    pub fn describe_unlocks(&self) -> String {
+        let mut parts = vec![format!("${}", self.reward)];
+        let mut regions = 0;
+
+        for unlock in self.unlocks {
+            match unlock {
+                Unlock::Building(building_type) => parts.push(building_type.label().to_string()),
+                Unlock::Variants(building_type, _) => {
+                    parts.push(format!("more {} looks", building_type.label()))
+                }
+                Unlock::Region => regions += 1,
+            }
+        }
+
+        match regions {
+            0 => {}
+            1 => parts.push("1 region".to_string()),
+            n => parts.push(format!("{} regions", n)),
+        }
+
+        parts.join(", ")
+    }
+}
+
+/// How far the city has come along `MILESTONES`. Milestones are reached in
+/// order, so everything else is derived from the count.
+#[derive(Resource, Debug, Default)]
+pub struct Progression {
+    pub reached: usize,
+    /// Building types a scenario limits the city to, replacing the milestone unlocks
+    pub allowed: Option<Vec<BuildingType>>,
+}
+
+impl Progression {
+    fn reached_unlocks(&self) -> impl Iterator<Item = &'static Unlock> {
+        MILESTONES[..self.reached.min(MILESTONES.len())]
+            .iter()
+            .flat_map(|milestone| milestone.unlocks.iter())
+    }
+
+    pub fn next(&self) -> Option<&'static Milestone> {
+        MILESTONES.get(self.reached)
+    }
+
+    /// The milestone that unlocks `building_type`, if it is still locked
+    pub fn locked_until(&self, building_type: BuildingType) -> Option<&'static Milestone> {
+        MILESTONES
+            .iter()
+            .skip(self.reached)
+            .find(|milestone| milestone.unlocks.contains(&Unlock::Building(building_type)))
+    }
+
+    pub fn is_unlocked(&self, building_type: BuildingType) -> bool {
+        match &self.allowed {
+            Some(allowed) => allowed.contains(&building_type),
+            None => self.locked_until(building_type).is_none(),
+        }
+    }
+
+    /// How many of the `total` sprite variants of `building_type` may be used
+    pub fn variant_limit(&self, building_type: BuildingType, total: usize) -> usize {
+        let Some((_, starting)) = STARTING_VARIANTS
+            .iter()
+            .find(|(gated, _)| *gated == building_type)
+        else {
+            return total;
+        };
+
+        self.reached_unlocks()
+            .filter_map(|unlock| match unlock {
+                Unlock::Variants(unlocked, count) if *unlocked == building_type => Some(*count),
+                _ => None,
+            })
+            .fold(*starting, usize::max)
+            .clamp(1, total.max(1))
+    }
+
+    /// Map regions the player has earned the right to buy
+    pub fn regions_unlocked(&self) -> u32 {
+        self.reached_unlocks()
+            .filter(|unlock| **unlock == Unlock::Region)
+            .count() as u32
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct MilestonePanelState {
+    pub open: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buildings_unlock_at_their_milestone() {
+        let mut progression = Progression::default();
+        assert!(progression.is_unlocked(BuildingType::Residential));
+        assert!(!progression.is_unlocked(BuildingType::School));
+        assert_eq!(
+            progression
+                .locked_until(BuildingType::School)
+                .map(|m| m.name),
+            Some("Town")
+        );
+
+        progression.reached = 2;
+        assert!(progression.is_unlocked(BuildingType::School));
+        assert!(!progression.is_unlocked(BuildingType::BusStop));
+
+        // a scenario's building list overrides the milestones
+        progression.allowed = Some(vec![BuildingType::BusStop]);
+        assert!(progression.is_unlocked(BuildingType::BusStop));
+        assert!(!progression.is_unlocked(BuildingType::Residential));
+    }
+
+    #[test]
+    fn variants_grow_with_milestones() {
+        let mut progression = Progression::default();
+        assert_eq!(progression.variant_limit(BuildingType::Commercial, 4), 2);
+        assert_eq!(progression.variant_limit(BuildingType::Road, 11), 11);
+
+        progression.reached = 2;
+        assert_eq!(progression.variant_limit(BuildingType::Commercial, 4), 4);
+        assert_eq!(progression.variant_limit(BuildingType::Industry, 2), 1);
+    }
+
+    #[test]
+    fn unlocks_are_described_in_plain_words() {
+        assert_eq!(
+            MILESTONES[2].describe_unlocks(),
+            "$20000, Bus stop, more Industry looks, 1 region"
+        );
+    }
+
+    #[test]
+    fn regions_accumulate() {
+        let progression = Progression {
+            reached: MILESTONES.len(),
+            ..default()
+        };
+
+        assert_eq!(progression.regions_unlocked(), 6);
+        assert!(progression.next().is_none());
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+
+use crate::budget::{Budget, BudgetLedger, BuildingType, LedgerCategory};
+use crate::city::resources::CityPopulation;
+use crate::map::CurrentTileType;
+use crate::notifications::Notify;
+use crate::settings::{Action, ActionInput};
+use crate::time::GameClock;
+
+use super::resources::{MilestonePanelState, Progression};
+
+/// Milestones belong to the city that reached them, so every new city starts from
+/// scratch; a loaded city gets its count back from the save
+pub fn reset_progress(mut progression: ResMut<Progression>) {
+    progression.reached = 0;
+}
+
+/// Award the next milestone once the city is big and happy enough for it
+pub fn check_milestones(
+    clock: Res<GameClock>,
+    population: Res<CityPopulation>,
+    mut progression: ResMut<Progression>,
+    mut budget: ResMut<Budget>,
+    mut ledger: ResMut<BudgetLedger>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    let Some(milestone) = progression.next() else {
+        return;
+    };
+    if !milestone.is_met(population.population, population.happiness) {
+        return;
+    }
+
+    progression.reached += 1;
+    ledger.book(
+        &mut budget,
+        clock.day,
+        LedgerCategory::MilestoneRewards,
+        milestone.reward,
+    );
+    notify_writer.write(Notify::info(format!(
+        "Milestone reached: {}! Unlocked {}",
+        milestone.name,
+        milestone.describe_unlocks()
+    )));
+}
+
+/// Drop a selection the player has not unlocked yet and say what unlocks it
+pub fn reject_locked_selection(
+    progression: Res<Progression>,
+    mut current_tile_type: ResMut<CurrentTileType>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    if !current_tile_type.is_changed() {
+        return;
+    }
+    let Some(building_type) = BuildingType::from_selection_index(current_tile_type.texture_index)
+    else {
+        return;
+    };
+    if progression.is_unlocked(building_type) {
+        return;
+    }
+
+    current_tile_type.texture_index = 0;
+    let message = match progression.locked_until(building_type) {
+        Some(milestone) => format!(
+            "{} unlocks at the {} milestone ({} citizens)",
+            building_type.label(),
+            milestone.name,
+            milestone.population
+        ),
+        None => format!(
+            "{} is not available in this scenario",
+            building_type.label()
+        ),
+    };
+    notify_writer.write(Notify::warning(message));
+}
+
+pub fn toggle_milestone_panel_on_key(actions: ActionInput, mut panel: ResMut<MilestonePanelState>) {
+    if actions.just_pressed(Action::ToggleMilestones) {
+        panel.open = !panel.open;
+    }
+}
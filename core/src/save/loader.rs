@@ -0,0 +1,328 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy_ecs_tilemap::prelude::TilePos;
+
+use crate::budget::Rotation;
+use crate::map::PlacedBuilding;
+use crate::scenario::parse_building_type;
+
+use super::resources::{CitySave, SavedBuilding};
+
+const SAVE_DIR: &str = "saves";
+/// The city "Save game" writes and "Load game" reads back
+const SAVE_FILE: &str = "saves/city.txt";
+
+pub fn has_saved_city() -> bool {
+    Path::new(SAVE_FILE).exists()
+}
+
+pub fn load_city() -> Result<CitySave, String> {
+    let contents = fs::read_to_string(SAVE_FILE)
+        .map_err(|err| format!("could not read {}: {}", SAVE_FILE, err))?;
+    parse_save(&contents).map_err(|err| format!("{}: {}", SAVE_FILE, err))
+}
+
+pub fn save_city(save: &CitySave) -> io::Result<()> {
+    fs::create_dir_all(SAVE_DIR)?;
+    fs::write(SAVE_FILE, format_save(save))
+}
+
+/// Write a city in the same `key: value` format scenarios use. `plot`,
+/// `objective_done`, `building` and `line` repeat.
+pub fn format_save(save: &CitySave) -> String {
+    let taxes = &save.tax_rates;
+    let people = &save.demographics;
+    let mut lines = vec![
+        "# Gorod city save".to_string(),
+        format!("time: {}", save.elapsed_seconds),
+        format!("budget: {}", save.money),
+        format!(
+            "taxes: {} {} {}",
+            taxes.residential, taxes.commercial, taxes.industrial
+        ),
+        format!("map: {} {}", save.map_size.0, save.map_size.1),
+        format!("plots_bought: {}", save.plots_bought),
+    ];
+    lines.extend(save.plots.iter().map(|(x, y)| format!("plot: {} {}", x, y)));
+    lines.push(format!(
+        "population: {} {}",
+        save.population, save.happiness
+    ));
+    lines.push(format!(
+        "demographics: {} {} {} {} {}",
+        people.children, people.workers, people.retirees, people.education, people.health
+    ));
+    lines.push(format!("milestones: {}", save.milestones_reached));
+    if let Some(name) = &save.scenario {
+        lines.push(format!("scenario: {}", name));
+    }
+    lines.extend(
+        save.objectives_done
+            .iter()
+            .map(|index| format!("objective_done: {}", index)),
+    );
+    for saved in &save.buildings {
+        let building = &saved.building;
+        let mut line = format!(
+            "building: {:?} {} {} {} {:?} {} {}",
+            building.building_type,
+            building.variant,
+            building.tile_pos.x,
+            building.tile_pos.y,
+            building.rotation,
+            building.placed_day,
+            building.placed_hour
+        );
+        if let Some(day) = saved.derelict_since {
+            line.push_str(&format!(" derelict {}", day));
+        }
+        lines.push(line);
+    }
+    for stops in &save.bus_lines {
+        let coords: Vec<String> = stops
+            .iter()
+            .map(|stop| format!("{} {}", stop.x, stop.y))
+            .collect();
+        lines.push(format!("line: {}", coords.join(" ")));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn parse_save(contents: &str) -> Result<CitySave, String> {
+    let mut save = CitySave::default();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: String| format!("line {}: {}", index + 1, message);
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(error(format!("expected `key: value`, got `{}`", line)));
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "time" => save.elapsed_seconds = parse_number(value).map_err(error)?,
+            "budget" => save.money = parse_number(value).map_err(error)?,
+            "taxes" => {
+                let [residential, commercial, industrial] = parse_numbers(value).map_err(error)?;
+                save.tax_rates.residential = residential;
+                save.tax_rates.commercial = commercial;
+                save.tax_rates.industrial = industrial;
+            }
+            "map" => {
+                let [width, height] = parse_numbers(value).map_err(error)?;
+                save.map_size = (width, height);
+            }
+            "plots_bought" => save.plots_bought = parse_number(value).map_err(error)?,
+            "plot" => {
+                let [x, y] = parse_numbers(value).map_err(error)?;
+                save.plots.push((x, y));
+            }
+            "population" => {
+                let (population, happiness) = value.split_once(' ').unwrap_or((value, ""));
+                save.population = parse_number(population).map_err(error)?;
+                save.happiness = parse_number(happiness.trim()).map_err(error)?;
+            }
+            "demographics" => {
+                let [children, workers, retirees, education, health] =
+                    parse_numbers(value).map_err(error)?;
+                save.demographics.children = children;
+                save.demographics.workers = workers;
+                save.demographics.retirees = retirees;
+                save.demographics.education = education;
+                save.demographics.health = health;
+            }
+            "milestones" => save.milestones_reached = parse_number(value).map_err(error)?,
+            "scenario" => save.scenario = Some(value.to_string()),
+            "objective_done" => save
+                .objectives_done
+                .push(parse_number(value).map_err(error)?),
+            "building" => save.buildings.push(parse_building(value).map_err(error)?),
+            "line" => save.bus_lines.push(parse_stops(value).map_err(error)?),
+            other => return Err(error(format!("unknown key `{}`", other))),
+        }
+    }
+
+    let (width, height) = save.map_size;
+    if width == 0 || height == 0 {
+        return Err("the save has no map size".to_string());
+    }
+    let outside = |tile: &TilePos| tile.x >= width || tile.y >= height;
+    if let Some(saved) = save
+        .buildings
+        .iter()
+        .find(|saved| saved.building.tiles().any(|tile| outside(&tile)))
+    {
+        let tile = saved.building.tile_pos;
+        return Err(format!(
+            "building at {} {} is outside the {}x{} map",
+            tile.x, tile.y, width, height
+        ));
+    }
+
+    Ok(save)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("`{}` is not a valid number", value))
+}
+
+fn parse_numbers<T: std::str::FromStr, const N: usize>(value: &str) -> Result<[T; N], String> {
+    let numbers = value
+        .split_whitespace()
+        .map(parse_number)
+        .collect::<Result<Vec<T>, _>>()?;
+    numbers
+        .try_into()
+        .map_err(|_| format!("expected {} numbers, got `{}`", N, value))
+}
+
+fn parse_rotation(name: &str) -> Result<Rotation, String> {
+    match name.to_lowercase().as_str() {
+        "north" => Ok(Rotation::North),
+        "east" => Ok(Rotation::East),
+        "south" => Ok(Rotation::South),
+        "west" => Ok(Rotation::West),
+        _ => Err(format!("unknown rotation `{}`", name)),
+    }
+}
+
+/// `Residential 2 10 12 North 3 75`, optionally followed by `derelict 12`
+fn parse_building(value: &str) -> Result<SavedBuilding, String> {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    let (fields, derelict) = match words[..] {
+        [.., "derelict", day] => (&words[..words.len() - 2], Some(parse_number(day)?)),
+        _ => (&words[..], None),
+    };
+    let [
+        building_type,
+        variant,
+        x,
+        y,
+        rotation,
+        placed_day,
+        placed_hour,
+    ] = fields[..]
+    else {
+        return Err(format!(
+            "expected `type variant x y rotation day hour`, got `{}`",
+            value
+        ));
+    };
+
+    Ok(SavedBuilding {
+        building: PlacedBuilding {
+            building_type: parse_building_type(building_type)?,
+            variant: parse_number(variant)?,
+            tile_pos: TilePos {
+                x: parse_number(x)?,
+                y: parse_number(y)?,
+            },
+            rotation: parse_rotation(rotation)?,
+            placed_day: parse_number(placed_day)?,
+            placed_hour: parse_number(placed_hour)?,
+        },
+        derelict_since: derelict,
+    })
+}
+
+fn parse_stops(value: &str) -> Result<Vec<TilePos>, String> {
+    let coords = value
+        .split_whitespace()
+        .map(parse_number)
+        .collect::<Result<Vec<u32>, _>>()?;
+    if !coords.len().is_multiple_of(2) {
+        return Err(format!("expected `x y` pairs, got `{}`", value));
+    }
+    Ok(coords
+        .chunks(2)
+        .map(|pair| TilePos {
+            x: pair[0],
+            y: pair[1],
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::BuildingType;
+
+    #[test]
+    fn a_saved_city_reads_back_the_same() {
+        let stadium = PlacedBuilding {
+            building_type: BuildingType::Stadium,
+            variant: 0,
+            tile_pos: TilePos { x: 4, y: 5 },
+            rotation: Rotation::East,
+            placed_day: 10,
+            placed_hour: 230,
+        };
+        let bus_stop = PlacedBuilding {
+            building_type: BuildingType::BusStop,
+            tile_pos: TilePos { x: 9, y: 9 },
+            rotation: Rotation::North,
+            ..stadium
+        };
+        let mut save = CitySave {
+            elapsed_seconds: 864_000,
+            money: -250,
+            map_size: (24, 32),
+            plots: vec![(1, 1), (2, 1)],
+            plots_bought: 1,
+            population: 120,
+            happiness: 0.65,
+            milestones_reached: 3,
+            scenario: Some("First Town".to_string()),
+            objectives_done: vec![0],
+            buildings: vec![
+                SavedBuilding {
+                    building: stadium,
+                    derelict_since: Some(12),
+                },
+                SavedBuilding {
+                    building: bus_stop,
+                    derelict_since: None,
+                },
+            ],
+            bus_lines: vec![vec![TilePos { x: 9, y: 9 }, TilePos { x: 3, y: 1 }]],
+            ..CitySave::default()
+        };
+        save.tax_rates.commercial = 0.15;
+        save.demographics.workers = 80.5;
+
+        let loaded = parse_save(&format_save(&save)).unwrap();
+
+        assert_eq!(loaded.elapsed_seconds, 864_000);
+        assert_eq!(loaded.money, -250);
+        assert_eq!(loaded.tax_rates.commercial, 0.15);
+        assert_eq!(loaded.map_size, (24, 32));
+        assert_eq!(loaded.plots, save.plots);
+        assert_eq!(loaded.plots_bought, 1);
+        assert_eq!((loaded.population, loaded.happiness), (120, 0.65));
+        assert_eq!(loaded.demographics.workers, 80.5);
+        assert_eq!(loaded.milestones_reached, 3);
+        assert_eq!(loaded.scenario.as_deref(), Some("First Town"));
+        assert_eq!(loaded.objectives_done, vec![0]);
+        assert_eq!(loaded.buildings, save.buildings);
+        assert_eq!(loaded.bus_lines, save.bus_lines);
+    }
+
+    #[test]
+    fn rejects_broken_saves() {
+        assert!(parse_save("budget: 100\n").is_err());
+        assert!(parse_save("map: 8 8\nmoney: 100\n").is_err());
+        assert!(parse_save("map: 8 8\nbuilding: Castle 0 1 1 North 1 8\n").is_err());
+        // a 3x2 stadium at the right edge of an 8x8 map hangs off it
+        let err = parse_save("map: 8 8\nbuilding: Stadium 0 6 0 North 1 8\n").unwrap_err();
+        assert!(err.contains("outside"), "{}", err);
+    }
+}
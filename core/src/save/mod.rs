@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+mod loader;
+mod resources;
+mod systems;
+
+pub use loader::{has_saved_city, load_city};
+pub use resources::{LoadedCity, SaveCity};
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<SaveCity>().add_systems(
+            Update,
+            (
+                systems::save_city_on_request,
+                systems::restore_loaded_city.run_if(resource_exists::<LoadedCity>),
+            ),
+        );
+    }
+}
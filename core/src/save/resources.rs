@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+
+use crate::budget::TaxRates;
+use crate::city::resources::Demographics;
+use crate::map::PlacedBuilding;
+
+/// Sent by the pause screen to write the running city to disk
+#[derive(Message)]
+pub struct SaveCity;
+
+/// A saved city waiting to be rebuilt. The main menu inserts it when loading;
+/// `setup_map` sizes the map from it and `restore_loaded_city` puts the rest back.
+#[derive(Resource, Debug)]
+pub struct LoadedCity(pub CitySave);
+
+/// A building as it stood when the city was saved
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedBuilding {
+    pub building: PlacedBuilding,
+    /// Day the building was abandoned, if it is a derelict lot
+    pub derelict_since: Option<u32>,
+}
+
+/// Everything needed to rebuild a city: the money, clock and land, the buildings
+/// standing on it and the people living there
+#[derive(Debug, Clone, Default)]
+pub struct CitySave {
+    /// `GameTime::elapsed_seconds`, which the clock is derived from
+    pub elapsed_seconds: u64,
+    pub money: i64,
+    pub tax_rates: TaxRates,
+    pub map_size: (u32, u32),
+    /// Owned plots, in plot coordinates
+    pub plots: Vec<(u32, u32)>,
+    /// Plots bought by the player, which sets the price of the next one
+    pub plots_bought: u32,
+    pub population: i64,
+    pub happiness: f32,
+    pub demographics: Demographics,
+    /// How many milestones the city has reached
+    pub milestones_reached: usize,
+    /// Name of the scenario the city is playing, if any
+    pub scenario: Option<String>,
+    /// Indices of the scenario objectives already met
+    pub objectives_done: Vec<usize>,
+    pub buildings: Vec<SavedBuilding>,
+    /// Stops of every bus line, in order
+    pub bus_lines: Vec<Vec<TilePos>>,
+}
@@ -0,0 +1,225 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::budget::{Budget, TaxRates};
+use crate::city::resources::{CityInfrastructure, CityPopulation, CityServices, Demographics};
+use crate::map::{
+    Derelict, LandPlots, PlacedBuilding, PlacementAtlasResources, TileState, abandon_building,
+    spawn_building,
+};
+use crate::notifications::Notify;
+use crate::progression::Progression;
+use crate::scenario::{ActiveScenario, ScenarioTracker};
+use crate::spatial::SpatialGrid;
+use crate::time::{GameClock, GameTime};
+use crate::transit::TransitNetwork;
+
+use super::loader;
+use super::resources::{CitySave, LoadedCity, SaveCity, SavedBuilding};
+
+#[derive(SystemParam)]
+pub struct SaveSources<'w, 's> {
+    game_time: Res<'w, GameTime>,
+    budget: Res<'w, Budget>,
+    tax_rates: Res<'w, TaxRates>,
+    plots: Res<'w, LandPlots>,
+    population: Res<'w, CityPopulation>,
+    demographics: Res<'w, Demographics>,
+    progression: Res<'w, Progression>,
+    transit: Res<'w, TransitNetwork>,
+    scenario: Option<Res<'w, ActiveScenario>>,
+    tracker: Res<'w, ScenarioTracker>,
+    buildings_q: Query<'w, 's, (&'static PlacedBuilding, Option<&'static Derelict>)>,
+    map_q: Query<'w, 's, &'static TilemapSize>,
+}
+
+impl SaveSources<'_, '_> {
+    fn city_save(&self) -> Option<CitySave> {
+        let map_size = self.map_q.iter().next()?;
+
+        let mut plots: Vec<(u32, u32)> = self.plots.owned.iter().map(|p| (p.x, p.y)).collect();
+        plots.sort_unstable();
+
+        let mut buildings: Vec<SavedBuilding> = self
+            .buildings_q
+            .iter()
+            .map(|(building, derelict)| SavedBuilding {
+                building: *building,
+                derelict_since: derelict.map(|derelict| derelict.since_day),
+            })
+            .collect();
+        buildings.sort_by_key(|saved| {
+            let building = &saved.building;
+            (
+                building.placed_hour,
+                building.tile_pos.x,
+                building.tile_pos.y,
+            )
+        });
+
+        Some(CitySave {
+            elapsed_seconds: self.game_time.elapsed_seconds as u64,
+            money: self.budget.money,
+            tax_rates: *self.tax_rates,
+            map_size: (map_size.x, map_size.y),
+            plots,
+            plots_bought: self.plots.purchased,
+            population: self.population.population,
+            happiness: self.population.happiness,
+            demographics: self.demographics.clone(),
+            milestones_reached: self.progression.reached,
+            scenario: self
+                .scenario
+                .as_ref()
+                .map(|scenario| scenario.0.name.clone()),
+            objectives_done: self
+                .tracker
+                .completed
+                .iter()
+                .enumerate()
+                .filter_map(|(index, done)| done.then_some(index))
+                .collect(),
+            buildings,
+            bus_lines: self
+                .transit
+                .lines
+                .iter()
+                .map(|line| line.stops.clone())
+                .collect(),
+        })
+    }
+}
+
+/// Write the running city to disk when the pause screen asks for it
+pub fn save_city_on_request(
+    mut requests: MessageReader<SaveCity>,
+    city: SaveSources,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+    let Some(save) = city.city_save() else {
+        return;
+    };
+
+    match loader::save_city(&save) {
+        Ok(()) => {
+            info!("Saved the city with {} buildings", save.buildings.len());
+            notify_writer.write(Notify::info("City saved"));
+        }
+        Err(err) => {
+            warn!("Could not save the city: {}", err);
+            notify_writer.write(Notify::warning(format!("Could not save the city: {}", err)));
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct RestoreTargets<'w> {
+    game_time: ResMut<'w, GameTime>,
+    clock: ResMut<'w, GameClock>,
+    budget: ResMut<'w, Budget>,
+    tax_rates: ResMut<'w, TaxRates>,
+    population: ResMut<'w, CityPopulation>,
+    demographics: ResMut<'w, Demographics>,
+    progression: ResMut<'w, Progression>,
+    services: ResMut<'w, CityServices>,
+    infra: ResMut<'w, CityInfrastructure>,
+    spatial_grid: ResMut<'w, SpatialGrid>,
+    transit: ResMut<'w, TransitNetwork>,
+    tracker: ResMut<'w, ScenarioTracker>,
+}
+
+/// Rebuild a loaded city on the fresh map `setup_map` made for it. Buildings go
+/// straight into the city's counts instead of through `BuildingPlaced`, which
+/// would charge the placement happiness for them a second time.
+pub fn restore_loaded_city(
+    mut commands: Commands,
+    loaded: Res<LoadedCity>,
+    mut city: RestoreTargets,
+    mut atlases: PlacementAtlasResources,
+    map_q: Query<(
+        &TilemapSize,
+        &TilemapGridSize,
+        &TilemapType,
+        &TileStorage,
+        &Transform,
+    )>,
+    mut tile_q: Query<&mut TileState>,
+) {
+    let Some((map_size, grid_size, map_type, tile_storage, map_transform)) = map_q.iter().next()
+    else {
+        return;
+    };
+    let save = &loaded.0;
+
+    for saved in &save.buildings {
+        let building = saved.building;
+        let entity = spawn_building(
+            &mut commands,
+            &mut atlases,
+            building,
+            map_size,
+            grid_size,
+            map_type,
+            map_transform,
+        );
+
+        if let Some(since_day) = saved.derelict_since {
+            abandon_building(
+                &mut commands,
+                &mut city.spatial_grid,
+                tile_storage,
+                &mut tile_q,
+                entity,
+                &building,
+                since_day,
+            );
+            continue;
+        }
+
+        for tile_pos in building.tiles() {
+            if let Some(tile_entity) = tile_storage.checked_get(&tile_pos)
+                && let Ok(mut tile_state) = tile_q.get_mut(tile_entity)
+            {
+                *tile_state = TileState::Occupied(entity);
+            }
+        }
+        city.spatial_grid.insert(
+            building.tile_pos,
+            building.footprint(),
+            building.building_type,
+        );
+        city.services.count_building(building.building_type, 1);
+        city.infra.count_building(building.building_type, 1);
+    }
+
+    for stops in &save.bus_lines {
+        city.transit.add_line(stops.clone());
+    }
+
+    city.game_time.elapsed_seconds = save.elapsed_seconds as f64;
+    *city.clock = GameClock::at(city.game_time.elapsed_seconds);
+    city.budget.money = save.money;
+    *city.tax_rates = save.tax_rates;
+    city.population.population = save.population;
+    city.population.happiness = save.happiness;
+    *city.demographics = save.demographics.clone();
+    city.progression.reached = save.milestones_reached;
+    for &index in &save.objectives_done {
+        if city.tracker.completed.len() <= index {
+            city.tracker.completed.resize(index + 1, false);
+        }
+        city.tracker.completed[index] = true;
+    }
+
+    info!(
+        "Loaded a city of {} on day {} with {} buildings",
+        save.population,
+        city.clock.day,
+        save.buildings.len()
+    );
+    commands.remove_resource::<LoadedCity>();
+}
@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+
+use crate::map::UiClickBlocker;
+use crate::menu::GameState;
+
+use super::resources::{ActiveScenario, RetryScenario, ScenarioOutcome, ScenarioTracker};
+use super::systems::ScenarioCityInputs;
+
+const DONE_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+#[derive(Component)]
+pub struct ScenarioPanelText;
+
+#[derive(Component)]
+pub struct ScenarioPanel;
+
+/// What a button on the results screen does
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum ResultsButton {
+    KeepBuilding,
+    /// Start the lost scenario over on a fresh map
+    Retry,
+    MainMenu,
+}
+
+#[derive(Resource)]
+pub struct ScenarioFont(Handle<Font>);
+
+pub fn setup_scenario_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    scenario: Option<Res<ActiveScenario>>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+    commands.insert_resource(ScenarioFont(font.clone()));
+
+    if scenario.is_none() {
+        return;
+    }
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(420.0),
+            right: Val::Px(10.0),
+            max_width: Val::Px(360.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ScenarioPanel,
+        children![(
+            Text::new(""),
+            TextFont {
+                font,
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            ScenarioPanelText,
+        )],
+    ));
+}
+
+/// List the scenario's goals, ticking off the ones already reached
+pub fn update_scenario_panel(
+    scenario: Option<Res<ActiveScenario>>,
+    tracker: Res<ScenarioTracker>,
+    mut text_q: Query<(&mut Text, &mut TextColor), With<ScenarioPanelText>>,
+) {
+    let Some(scenario) = scenario else {
+        return;
+    };
+    if !tracker.is_changed() {
+        return;
+    }
+    let Ok((mut text, mut color)) = text_q.single_mut() else {
+        return;
+    };
+
+    let mut lines = vec![scenario.0.name.clone()];
+    for (index, objective) in scenario.0.objectives.iter().enumerate() {
+        let done = tracker.completed.get(index).copied().unwrap_or(false);
+        let mark = if done { "[x]" } else { "[ ]" };
+        lines.push(format!("{} {}", mark, objective.describe()));
+    }
+    for condition in &scenario.0.fail_conditions {
+        lines.push(condition.describe());
+    }
+
+    text.0 = lines.join("\n");
+    color.0 = if tracker.outcome == Some(ScenarioOutcome::Won) {
+        DONE_COLOR
+    } else {
+        Color::WHITE
+    };
+}
+
+/// Put up the results card when a scenario is won or lost
+pub fn show_results_screen(
+    mut commands: Commands,
+    font: Res<ScenarioFont>,
+    scenario: Option<Res<ActiveScenario>>,
+    tracker: Res<ScenarioTracker>,
+    city: ScenarioCityInputs,
+) {
+    let (Some(scenario), Some(outcome)) = (scenario, tracker.outcome.as_ref()) else {
+        return;
+    };
+
+    let (title, summary) = match outcome {
+        ScenarioOutcome::Won => (
+            "SCENARIO COMPLETE".to_string(),
+            format!("{} is a success!", scenario.0.name),
+        ),
+        ScenarioOutcome::Lost(reason) => ("SCENARIO FAILED".to_string(), reason.clone()),
+    };
+    let stats = format!(
+        "Day {}\nPopulation: {}\nHappiness: {:.0}%\nBudget: ${}",
+        city.clock.day,
+        city.population.population,
+        city.population.happiness * 100.0,
+        city.budget.money
+    );
+
+    let text = |content: String| {
+        (
+            Text::new(content),
+            TextFont {
+                font: font.0.clone(),
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )
+    };
+    let button = |label: &str, action: ResultsButton| {
+        (
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.8, 0.8, 0.8, 1.0)),
+            action,
+            children![(
+                Text::new(label),
+                TextFont {
+                    font: font.0.clone(),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::BLACK),
+            )],
+        )
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                bottom: Val::Px(0.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            GlobalZIndex(20),
+            DespawnOnExit(GameState::GameOver),
+        ))
+        .with_children(|page| {
+            page.spawn((
+                Node {
+                    width: Val::Px(520.0),
+                    padding: UiRect::all(Val::Px(24.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(16.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            ))
+            .with_children(|card| {
+                card.spawn(text(title));
+                card.spawn(text(summary));
+                card.spawn(text(stats));
+                card.spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(button("main menu", ResultsButton::MainMenu));
+                    match outcome {
+                        ScenarioOutcome::Won => {
+                            row.spawn(button("keep building", ResultsButton::KeepBuilding))
+                        }
+                        ScenarioOutcome::Lost(_) => {
+                            row.spawn(button("retry", ResultsButton::Retry))
+                        }
+                    };
+                });
+            });
+        });
+}
+
+/// A won scenario can keep building; a lost one can only be retried or left.
+/// Both of those leave through the main menu so the city is torn down first.
+pub fn handle_results_buttons(
+    mut commands: Commands,
+    scenario: Option<Res<ActiveScenario>>,
+    interaction_q: Query<(&Interaction, &ResultsButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut ui_click_blocker: ResMut<UiClickBlocker>,
+) {
+    for (interaction, button) in interaction_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        ui_click_blocker.just_clicked_ui = true;
+        match *button {
+            ResultsButton::KeepBuilding => next_state.set(GameState::InGame),
+            ResultsButton::Retry => {
+                if let Some(scenario) = &scenario {
+                    commands.insert_resource(RetryScenario(scenario.0.clone()));
+                }
+                next_state.set(GameState::MainMenu);
+            }
+            ResultsButton::MainMenu => next_state.set(GameState::MainMenu),
+        }
+    }
+}
+
+/// Forget the scenario of the city being left and take its panel down
+pub fn clear_scenario(mut commands: Commands, panel_q: Query<Entity, With<ScenarioPanel>>) {
+    commands.remove_resource::<ActiveScenario>();
+    for entity in panel_q.iter() {
+        commands.entity(entity).despawn();
+    }
+}
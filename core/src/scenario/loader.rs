@@ -0,0 +1,279 @@
+use std::fs;
+use std::path::Path;
+
+use crate::budget::BuildingType;
+use crate::map::PLOT_SIZE;
+
+use super::resources::{FailCondition, Objective, Scenario};
+
+/// Command line flag that picks a scenario file, e.g. `--scenario scenarios/first_town.txt`
+const SCENARIO_FLAG: &str = "--scenario";
+/// Folder the new-game screen looks in for scenario files
+pub const SCENARIO_DIR: &str = "scenarios";
+/// Largest map a scenario may ask for along either side, in tiles
+const MAX_MAP_SIZE: u32 = 256;
+
+pub fn scenario_path_from_args() -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != SCENARIO_FLAG);
+    args.next()?;
+    args.next()
+}
+
+pub fn load_scenario(path: impl AsRef<Path>) -> Result<Scenario, String> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+    parse_scenario(&contents).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Load every `.txt` scenario in `dir`, sorted by file name
+pub fn list_scenarios(dir: impl AsRef<Path>) -> Vec<Result<Scenario, String>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+
+    paths.into_iter().map(load_scenario).collect()
+}
+
+/// Parse the `key: value` scenario format. Everything after a `#` is a comment;
+/// `plot`, `objective` and `fail` may repeat.
+pub fn parse_scenario(contents: &str) -> Result<Scenario, String> {
+    let mut scenario = Scenario::default();
+    // plots are checked once the map size is known, which may come later in the file
+    let mut plot_lines = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: String| format!("line {}: {}", index + 1, message);
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(error(format!("expected `key: value`, got `{}`", line)));
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "name" => scenario.name = value.to_string(),
+            "description" => {
+                if !scenario.description.is_empty() {
+                    scenario.description.push('\n');
+                }
+                scenario.description.push_str(value);
+            }
+            "budget" => scenario.starting_budget = parse_number(value).map_err(error)?,
+            "map" => scenario.map_size = parse_pair(value).map_err(error)?,
+            "plot" => {
+                scenario
+                    .starting_plots
+                    .push(parse_pair(value).map_err(error)?);
+                plot_lines.push(index + 1);
+            }
+            "allow" => {
+                let allowed = value
+                    .split_whitespace()
+                    .map(parse_building_type)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(error)?;
+                scenario.allowed = Some(allowed);
+            }
+            "objective" => scenario
+                .objectives
+                .push(parse_objective(value).map_err(error)?),
+            "fail" => scenario
+                .fail_conditions
+                .push(parse_fail_condition(value).map_err(error)?),
+            other => return Err(error(format!("unknown key `{}`", other))),
+        }
+    }
+
+    let (width, height) = scenario.map_size;
+    if width == 0 || height == 0 || width > MAX_MAP_SIZE || height > MAX_MAP_SIZE {
+        return Err(format!(
+            "map size must be between 1x1 and {0}x{0}, got {1}x{2}",
+            MAX_MAP_SIZE, width, height
+        ));
+    }
+
+    let (plots_x, plots_y) = (width.div_ceil(PLOT_SIZE), height.div_ceil(PLOT_SIZE));
+    for (line, &(x, y)) in plot_lines.iter().zip(&scenario.starting_plots) {
+        if x >= plots_x || y >= plots_y {
+            return Err(format!(
+                "line {}: plot {} {} is outside the map, which is {}x{} plots",
+                line, x, y, plots_x, plots_y
+            ));
+        }
+    }
+
+    Ok(scenario)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("`{}` is not a valid number", value))
+}
+
+fn parse_pair(value: &str) -> Result<(u32, u32), String> {
+    match value.split_whitespace().collect::<Vec<_>>()[..] {
+        [x, y] => Ok((parse_number(x)?, parse_number(y)?)),
+        _ => Err(format!("expected two numbers, got `{}`", value)),
+    }
+}
+
+pub fn parse_building_type(name: &str) -> Result<BuildingType, String> {
+    match name.to_lowercase().as_str() {
+        "residential" => Ok(BuildingType::Residential),
+        "commercial" => Ok(BuildingType::Commercial),
+        "industry" | "industrial" => Ok(BuildingType::Industry),
+        "road" => Ok(BuildingType::Road),
+        "decorative" | "park" => Ok(BuildingType::Decorative),
+        "busstop" | "bus_stop" => Ok(BuildingType::BusStop),
+        "school" => Ok(BuildingType::School),
+        "stadium" => Ok(BuildingType::Stadium),
+        _ => Err(format!("unknown building type `{}`", name)),
+    }
+}
+
+/// Named values such as `population 500 happiness 0.8 by_day 120`
+fn parse_fields(value: &str) -> Result<Vec<(&str, &str)>, String> {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    if !words.len().is_multiple_of(2) {
+        return Err(format!("expected `name value` pairs, got `{}`", value));
+    }
+    Ok(words.chunks(2).map(|pair| (pair[0], pair[1])).collect())
+}
+
+fn parse_objective(value: &str) -> Result<Objective, String> {
+    let mut objective = Objective {
+        population: 0,
+        happiness: 0.0,
+        by_day: None,
+    };
+
+    for (name, number) in parse_fields(value)? {
+        match name {
+            "population" => objective.population = parse_number(number)?,
+            "happiness" => objective.happiness = parse_number(number)?,
+            "by_day" => objective.by_day = Some(parse_number(number)?),
+            _ => return Err(format!("unknown objective field `{}`", name)),
+        }
+    }
+
+    Ok(objective)
+}
+
+fn parse_fail_condition(value: &str) -> Result<FailCondition, String> {
+    let (kind, rest) = value.split_once(' ').unwrap_or((value, ""));
+    let fields = parse_fields(rest)?;
+    let field = |wanted: &str| {
+        fields
+            .iter()
+            .find(|(name, _)| *name == wanted)
+            .map(|(_, number)| *number)
+            .ok_or_else(|| format!("`{}` needs a `{}` value", kind, wanted))
+    };
+
+    match kind {
+        "bankruptcy" => Ok(FailCondition::Bankruptcy {
+            days: parse_number(field("days")?)?,
+        }),
+        "low_happiness" => Ok(FailCondition::LowHappiness {
+            below: parse_number(field("below")?)?,
+            days: parse_number(field("days")?)?,
+        }),
+        _ => Err(format!("unknown fail condition `{}`", kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_full_scenario() {
+        let scenario = parse_scenario(
+            "# a test\n\
+             name: River Town\n\
+             budget: 20000\n\
+             map: 24 24\n\
+             plot: 1 1 # the middle\n\
+             allow: Residential Commercial Road\n\
+             objective: population 500 happiness 0.8 by_day 120\n\
+             fail: bankruptcy days 3\n\
+             fail: low_happiness below 0.2 days 10\n",
+        )
+        .unwrap();
+
+        assert_eq!(scenario.name, "River Town");
+        assert_eq!(scenario.starting_budget, 20_000);
+        assert_eq!(scenario.map_size, (24, 24));
+        assert_eq!(scenario.starting_plots, vec![(1, 1)]);
+        assert_eq!(scenario.allowed.as_ref().map(Vec::len), Some(3));
+        assert_eq!(scenario.objectives[0].by_day, Some(120));
+        assert_eq!(
+            scenario.fail_conditions[1],
+            FailCondition::LowHappiness {
+                below: 0.2,
+                days: 10
+            }
+        );
+    }
+
+    #[test]
+    fn scenario_folder_is_listed_in_file_order() {
+        let scenarios = list_scenarios(SCENARIO_DIR);
+
+        assert_eq!(scenarios.len(), 2);
+        assert_eq!(
+            scenarios[0].as_ref().map(|scenario| scenario.name.as_str()),
+            Ok("First Town")
+        );
+    }
+
+    #[test]
+    fn bundled_scenarios_load() {
+        for contents in [
+            include_str!("../../scenarios/first_town.txt"),
+            include_str!("../../scenarios/small_island.txt"),
+        ] {
+            assert!(parse_scenario(contents).is_ok());
+        }
+    }
+
+    #[test]
+    fn reports_the_offending_line() {
+        let err = parse_scenario("name: x\nbudget: lots\n").unwrap_err();
+        assert!(err.starts_with("line 2"));
+
+        assert!(parse_scenario("allow: Skyscraper").is_err());
+        assert!(parse_scenario("fail: bankruptcy").is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_maps() {
+        assert!(parse_scenario("map: 256 256").is_ok());
+        assert!(parse_scenario("map: 257 32").is_err());
+        assert!(parse_scenario("map: 32 100000").is_err());
+        assert!(parse_scenario("map: 0 32").is_err());
+    }
+
+    #[test]
+    fn rejects_plots_off_the_map() {
+        // 24 tiles make three plots a side, numbered 0 to 2
+        assert!(parse_scenario("map: 24 24\nplot: 2 2").is_ok());
+
+        let err = parse_scenario("map: 24 24\nplot: 1 1\nplot: 3 0").unwrap_err();
+        assert!(err.starts_with("line 3"));
+
+        // the map line may come after the plots it bounds
+        assert!(parse_scenario("plot: 4 1\nmap: 24 24").is_err());
+        assert!(parse_scenario("plot: 3 3").is_ok());
+    }
+}
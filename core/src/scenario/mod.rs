@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::menu::{GAME_EXIT, GAME_START, GameState, reset_resource};
+
+mod display;
+mod loader;
+mod resources;
+mod systems;
+
+pub use loader::parse_building_type;
+pub use resources::{ActiveScenario, RetryScenario, ScenarioCatalog, ScenarioTracker};
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        let mut catalog = ScenarioCatalog::default();
+        for scenario in loader::list_scenarios(loader::SCENARIO_DIR) {
+            match scenario {
+                Ok(scenario) => catalog.scenarios.push(scenario),
+                Err(err) => error!("Skipping scenario: {}", err),
+            }
+        }
+
+        // `--scenario <file>` preselects that scenario on the new-game screen
+        if let Some(path) = loader::scenario_path_from_args() {
+            match loader::load_scenario(&path) {
+                Ok(scenario) => catalog.select_or_add(scenario),
+                Err(err) => error!("Could not load scenario: {}", err),
+            }
+        }
+
+        app.insert_resource(catalog)
+            .init_resource::<resources::ScenarioTracker>()
+            .add_systems(
+                Update,
+                (
+                    systems::evaluate_scenario.run_if(in_state(GameState::InGame)),
+                    display::update_scenario_panel,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(GameState::GameOver), display::show_results_screen)
+            .add_systems(OnEnter(GameState::MainMenu), systems::restart_scenario)
+            .add_systems(
+                Update,
+                display::handle_results_buttons.run_if(in_state(GameState::GameOver)),
+            );
+
+        for start in GAME_START {
+            app.add_systems(
+                start,
+                (systems::apply_scenario, display::setup_scenario_panel),
+            );
+        }
+        for exit in GAME_EXIT {
+            app.add_systems(
+                exit,
+                (
+                    display::clear_scenario,
+                    reset_resource::<resources::ScenarioTracker>,
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use bevy::prelude::*;
+
+use crate::budget::BuildingType;
+
+/// A goal the city has to reach, optionally before a deadline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Objective {
+    pub population: i64,
+    pub happiness: f32,
+    pub by_day: Option<u32>,
+}
+
+impl Objective {
+    pub fn describe(&self) -> String {
+        let mut text = format!(
+            "{} citizens at {:.0}% happiness",
+            self.population,
+            self.happiness * 100.0
+        );
+        if let Some(day) = self.by_day {
+            text.push_str(&format!(" by day {}", day));
+        }
+        text
+    }
+}
+
+/// Ways to lose a scenario
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailCondition {
+    /// The budget stays below zero for this many days in a row
+    Bankruptcy { days: u32 },
+    /// Happiness stays below `below` for this many days in a row
+    LowHappiness { below: f32, days: u32 },
+}
+
+impl FailCondition {
+    pub fn describe(&self) -> String {
+        match self {
+            FailCondition::Bankruptcy { days } => {
+                format!("Lose if the budget is negative for {} days", days)
+            }
+            FailCondition::LowHappiness { below, days } => format!(
+                "Lose if happiness stays below {:.0}% for {} days",
+                below * 100.0,
+                days
+            ),
+        }
+    }
+}
+
+/// Everything a scenario file sets up
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub description: String,
+    pub starting_budget: i64,
+    pub map_size: (u32, u32),
+    /// Plots the city owns at the start, in plot coordinates; empty means the usual centre plots
+    pub starting_plots: Vec<(u32, u32)>,
+    /// Building types the player may use; `None` keeps the milestone unlocks
+    pub allowed: Option<Vec<BuildingType>>,
+    pub objectives: Vec<Objective>,
+    pub fail_conditions: Vec<FailCondition>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "Untitled scenario".to_string(),
+            description: String::new(),
+            starting_budget: 50_000,
+            map_size: (32, 32),
+            starting_plots: Vec::new(),
+            allowed: None,
+            objectives: Vec::new(),
+            fail_conditions: Vec::new(),
+        }
+    }
+}
+
+/// The scenario being played; absent in the sandbox
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveScenario(pub Scenario);
+
+/// Set by "retry" on the results screen. The lost city is torn down on the way
+/// to the main menu, which then starts this scenario again instead of showing up.
+#[derive(Resource, Debug)]
+pub struct RetryScenario(pub Scenario);
+
+/// Scenarios the new-game screen offers
+#[derive(Resource, Debug, Default)]
+pub struct ScenarioCatalog {
+    pub scenarios: Vec<Scenario>,
+    /// The scenario picked on the new-game screen; `None` plays the sandbox
+    pub selected: Option<usize>,
+}
+
+impl ScenarioCatalog {
+    /// Add a scenario unless an identical one is listed, and pick it
+    pub fn select_or_add(&mut self, scenario: Scenario) {
+        let index = match self.scenarios.iter().position(|known| *known == scenario) {
+            Some(index) => index,
+            None => {
+                self.scenarios.push(scenario);
+                self.scenarios.len() - 1
+            }
+        };
+        self.selected = Some(index);
+    }
+
+    pub fn selected_scenario(&self) -> Option<&Scenario> {
+        self.selected.and_then(|index| self.scenarios.get(index))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioOutcome {
+    Won,
+    Lost(String),
+}
+
+/// What the daily check looks at
+#[derive(Debug, Clone, Copy)]
+pub struct CitySnapshot {
+    pub day: u32,
+    pub population: i64,
+    pub happiness: f32,
+    pub money: i64,
+}
+
+/// Progress through the active scenario
+#[derive(Resource, Debug, Default)]
+pub struct ScenarioTracker {
+    pub completed: Vec<bool>,
+    pub outcome: Option<ScenarioOutcome>,
+    /// Days in a row each fail condition has held
+    streaks: Vec<u32>,
+}
+
+impl ScenarioTracker {
+    /// Record one day of the city and decide whether the scenario just ended
+    pub fn advance_day(
+        &mut self,
+        scenario: &Scenario,
+        city: &CitySnapshot,
+    ) -> Option<ScenarioOutcome> {
+        if self.outcome.is_some() {
+            return None;
+        }
+
+        self.completed.resize(scenario.objectives.len(), false);
+        for (objective, completed) in scenario.objectives.iter().zip(self.completed.iter_mut()) {
+            let in_time = objective.by_day.is_none_or(|day| city.day <= day);
+            if in_time
+                && city.population >= objective.population
+                && city.happiness >= objective.happiness
+            {
+                *completed = true;
+            }
+        }
+
+        self.streaks.resize(scenario.fail_conditions.len(), 0);
+        for (condition, streak) in scenario.fail_conditions.iter().zip(self.streaks.iter_mut()) {
+            let failing = match condition {
+                FailCondition::Bankruptcy { .. } => city.money < 0,
+                FailCondition::LowHappiness { below, .. } => city.happiness < *below,
+            };
+            *streak = if failing { *streak + 1 } else { 0 };
+        }
+
+        let outcome = self.check_failure(scenario, city).or_else(|| {
+            (!self.completed.is_empty() && self.completed.iter().all(|done| *done))
+                .then_some(ScenarioOutcome::Won)
+        });
+
+        self.outcome.clone_from(&outcome);
+        outcome
+    }
+
+    fn check_failure(&self, scenario: &Scenario, city: &CitySnapshot) -> Option<ScenarioOutcome> {
+        let missed =
+            scenario
+                .objectives
+                .iter()
+                .zip(&self.completed)
+                .find(|(objective, completed)| {
+                    !**completed && objective.by_day.is_some_and(|day| city.day > day)
+                });
+        if let Some((objective, _)) = missed {
+            return Some(ScenarioOutcome::Lost(format!(
+                "Missed the goal of {}",
+                objective.describe()
+            )));
+        }
+
+        scenario
+            .fail_conditions
+            .iter()
+            .zip(&self.streaks)
+            .find_map(|(condition, streak)| match condition {
+                FailCondition::Bankruptcy { days } if streak >= days => Some(
+                    ScenarioOutcome::Lost(format!("The city was bankrupt for {} days", streak)),
+                ),
+                FailCondition::LowHappiness { below, days } if streak >= days => {
+                    Some(ScenarioOutcome::Lost(format!(
+                        "Happiness stayed below {:.0}% for {} days",
+                        below * 100.0,
+                        streak
+                    )))
+                }
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> Scenario {
+        Scenario {
+            objectives: vec![Objective {
+                population: 500,
+                happiness: 0.8,
+                by_day: Some(120),
+            }],
+            fail_conditions: vec![
+                FailCondition::Bankruptcy { days: 3 },
+                FailCondition::LowHappiness {
+                    below: 0.2,
+                    days: 10,
+                },
+            ],
+            ..Scenario::default()
+        }
+    }
+
+    fn city(day: u32, population: i64, happiness: f32, money: i64) -> CitySnapshot {
+        CitySnapshot {
+            day,
+            population,
+            happiness,
+            money,
+        }
+    }
+
+    #[test]
+    fn reaching_every_objective_wins() {
+        let mut tracker = ScenarioTracker::default();
+
+        assert_eq!(
+            tracker.advance_day(&scenario(), &city(10, 100, 0.9, 100)),
+            None
+        );
+        assert_eq!(
+            tracker.advance_day(&scenario(), &city(90, 520, 0.85, 100)),
+            Some(ScenarioOutcome::Won)
+        );
+        // a finished scenario stays finished
+        assert_eq!(
+            tracker.advance_day(&scenario(), &city(91, 0, 0.0, -5)),
+            None
+        );
+    }
+
+    #[test]
+    fn deadlines_and_streaks_lose() {
+        let mut late = ScenarioTracker::default();
+        assert!(matches!(
+            late.advance_day(&scenario(), &city(121, 520, 0.85, 100)),
+            Some(ScenarioOutcome::Lost(_))
+        ));
+
+        let mut broke = ScenarioTracker::default();
+        assert_eq!(broke.advance_day(&scenario(), &city(1, 0, 1.0, -1)), None);
+        assert_eq!(broke.advance_day(&scenario(), &city(2, 0, 1.0, -1)), None);
+        assert!(matches!(
+            broke.advance_day(&scenario(), &city(3, 0, 1.0, -1)),
+            Some(ScenarioOutcome::Lost(_))
+        ));
+
+        let mut gloomy = ScenarioTracker::default();
+        for day in 1..10 {
+            assert_eq!(gloomy.advance_day(&scenario(), &city(day, 0, 0.1, 0)), None);
+        }
+        assert!(
+            gloomy
+                .advance_day(&scenario(), &city(10, 0, 0.1, 0))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn catalog_picks_known_scenarios_without_duplicating() {
+        let mut catalog = ScenarioCatalog {
+            scenarios: vec![Scenario::default(), scenario()],
+            selected: None,
+        };
+
+        catalog.select_or_add(scenario());
+        assert_eq!(catalog.scenarios.len(), 2);
+        assert_eq!(catalog.selected, Some(1));
+
+        let custom = Scenario {
+            name: "Custom".to_string(),
+            ..Scenario::default()
+        };
+        catalog.select_or_add(custom.clone());
+        assert_eq!(catalog.scenarios.len(), 3);
+        assert_eq!(catalog.selected_scenario(), Some(&custom));
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::budget::Budget;
+use crate::city::resources::CityPopulation;
+use crate::menu::GameState;
+use crate::notifications::Notify;
+use crate::progression::Progression;
+use crate::time::GameClock;
+
+use super::resources::{
+    ActiveScenario, CitySnapshot, RetryScenario, ScenarioOutcome, ScenarioTracker,
+};
+
+/// Set the budget and building list the scenario asks for
+pub fn apply_scenario(
+    scenario: Option<Res<ActiveScenario>>,
+    mut budget: ResMut<Budget>,
+    mut progression: ResMut<Progression>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    let Some(scenario) = scenario else {
+        return;
+    };
+    let scenario = &scenario.0;
+
+    budget.money = scenario.starting_budget;
+    progression.allowed.clone_from(&scenario.allowed);
+
+    info!("Starting scenario {}", scenario.name);
+    notify_writer.write(Notify::info(format!("Scenario: {}", scenario.name)));
+}
+
+/// Start the scenario the player asked to retry as soon as the old city is gone
+pub fn restart_scenario(
+    mut commands: Commands,
+    retry: Option<Res<RetryScenario>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(retry) = retry else {
+        return;
+    };
+
+    commands.insert_resource(ActiveScenario(retry.0.clone()));
+    commands.remove_resource::<RetryScenario>();
+    next_state.set(GameState::InGame);
+}
+
+#[derive(SystemParam)]
+pub struct ScenarioCityInputs<'w> {
+    pub clock: Res<'w, GameClock>,
+    pub population: Res<'w, CityPopulation>,
+    pub budget: Res<'w, Budget>,
+}
+
+/// Once per in‑game day, check the scenario's objectives and fail conditions
+pub fn evaluate_scenario(
+    city: ScenarioCityInputs,
+    scenario: Option<Res<ActiveScenario>>,
+    mut tracker: ResMut<ScenarioTracker>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut notify_writer: MessageWriter<Notify>,
+    mut last_processed_day: Local<u32>,
+) {
+    let Some(scenario) = scenario else {
+        return;
+    };
+    let day = city.clock.day;
+    if *last_processed_day == day {
+        return;
+    }
+    *last_processed_day = day;
+
+    let snapshot = CitySnapshot {
+        day,
+        population: city.population.population,
+        happiness: city.population.happiness,
+        money: city.budget.money,
+    };
+
+    let Some(outcome) = tracker.advance_day(&scenario.0, &snapshot) else {
+        return;
+    };
+
+    next_state.set(GameState::GameOver);
+    match outcome {
+        ScenarioOutcome::Won => {
+            info!("Scenario {} won on day {}", scenario.0.name, day);
+            notify_writer.write(Notify::info(format!(
+                "Scenario complete: {}",
+                scenario.0.name
+            )));
+        }
+        ScenarioOutcome::Lost(reason) => {
+            info!(
+                "Scenario {} lost on day {}: {}",
+                scenario.0.name, day, reason
+            );
+            notify_writer.write(Notify::critical(format!("Scenario failed: {}", reason)));
+        }
+    }
+}
@@ -0,0 +1,393 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+
+/// File with the player's key overrides, e.g. `select_road: O R`
+pub const BINDINGS_FILE: &str = "config/keybindings.txt";
+
+/// Everything the player can do from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    SelectResidential,
+    SelectCommercial,
+    SelectIndustry,
+    SelectRoad,
+    SelectDecorative,
+    SelectBusStop,
+    SelectSchool,
+    SelectStadium,
+    PreviousVariant,
+    NextVariant,
+    /// Turn the selected building a quarter clockwise
+    Rotate,
+    /// Deselect the tool, close the inspector or cancel a bus line
+    Cancel,
+    /// Held while clicking to demolish
+    Demolish,
+    /// Pick up the bulldozer to clear an area
+    Bulldoze,
+    BuyLand,
+    EditBusLine,
+    FinishBusLine,
+    ToggleEventLog,
+    ToggleMilestones,
+    TogglePause,
+    SpeedNormal,
+    SpeedFast,
+    SpeedUltraFast,
+    CameraUp,
+    CameraDown,
+    CameraLeft,
+    CameraRight,
+    ZoomIn,
+    ZoomOut,
+    ResetCamera,
+}
+
+impl Action {
+    pub const ALL: [Action; 30] = [
+        Action::SelectResidential,
+        Action::SelectCommercial,
+        Action::SelectIndustry,
+        Action::SelectRoad,
+        Action::SelectDecorative,
+        Action::SelectBusStop,
+        Action::SelectSchool,
+        Action::SelectStadium,
+        Action::PreviousVariant,
+        Action::NextVariant,
+        Action::Rotate,
+        Action::Cancel,
+        Action::Demolish,
+        Action::Bulldoze,
+        Action::BuyLand,
+        Action::EditBusLine,
+        Action::FinishBusLine,
+        Action::ToggleEventLog,
+        Action::ToggleMilestones,
+        Action::TogglePause,
+        Action::SpeedNormal,
+        Action::SpeedFast,
+        Action::SpeedUltraFast,
+        Action::CameraUp,
+        Action::CameraDown,
+        Action::CameraLeft,
+        Action::CameraRight,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ResetCamera,
+    ];
+
+    /// Name used in the bindings file
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::SelectResidential => "select_residential",
+            Action::SelectCommercial => "select_commercial",
+            Action::SelectIndustry => "select_industry",
+            Action::SelectRoad => "select_road",
+            Action::SelectDecorative => "select_decorative",
+            Action::SelectBusStop => "select_bus_stop",
+            Action::SelectSchool => "select_school",
+            Action::SelectStadium => "select_stadium",
+            Action::PreviousVariant => "previous_variant",
+            Action::NextVariant => "next_variant",
+            Action::Rotate => "rotate",
+            Action::Cancel => "cancel",
+            Action::Demolish => "demolish",
+            Action::Bulldoze => "bulldoze",
+            Action::BuyLand => "buy_land",
+            Action::EditBusLine => "edit_bus_line",
+            Action::FinishBusLine => "finish_bus_line",
+            Action::ToggleEventLog => "toggle_event_log",
+            Action::ToggleMilestones => "toggle_milestones",
+            Action::TogglePause => "toggle_pause",
+            Action::SpeedNormal => "speed_normal",
+            Action::SpeedFast => "speed_fast",
+            Action::SpeedUltraFast => "speed_ultra_fast",
+            Action::CameraUp => "camera_up",
+            Action::CameraDown => "camera_down",
+            Action::CameraLeft => "camera_left",
+            Action::CameraRight => "camera_right",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::ResetCamera => "reset_camera",
+        }
+    }
+
+    fn default_keys(self) -> &'static [KeyCode] {
+        match self {
+            Action::SelectResidential => &[KeyCode::KeyR],
+            Action::SelectCommercial => &[KeyCode::KeyC],
+            Action::SelectIndustry => &[KeyCode::KeyI],
+            Action::SelectRoad => &[KeyCode::KeyO],
+            Action::SelectDecorative => &[KeyCode::KeyB],
+            Action::SelectBusStop => &[KeyCode::KeyT],
+            Action::SelectSchool => &[KeyCode::KeyE],
+            Action::SelectStadium => &[KeyCode::KeyG],
+            Action::PreviousVariant => &[KeyCode::Period],
+            Action::NextVariant => &[KeyCode::Comma],
+            Action::Rotate => &[KeyCode::KeyQ],
+            Action::Cancel => &[KeyCode::Escape],
+            Action::Demolish => &[KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            Action::Bulldoze => &[KeyCode::KeyX],
+            Action::BuyLand => &[KeyCode::KeyP],
+            Action::EditBusLine => &[KeyCode::KeyL],
+            Action::FinishBusLine => &[KeyCode::Enter],
+            Action::ToggleEventLog => &[KeyCode::KeyN],
+            Action::ToggleMilestones => &[KeyCode::KeyM],
+            Action::TogglePause => &[KeyCode::Space],
+            Action::SpeedNormal => &[KeyCode::Digit1],
+            Action::SpeedFast => &[KeyCode::Digit2],
+            Action::SpeedUltraFast => &[KeyCode::Digit3],
+            Action::CameraUp => &[KeyCode::KeyW, KeyCode::ArrowUp],
+            Action::CameraDown => &[KeyCode::KeyS, KeyCode::ArrowDown],
+            Action::CameraLeft => &[KeyCode::KeyA, KeyCode::ArrowLeft],
+            Action::CameraRight => &[KeyCode::KeyD, KeyCode::ArrowRight],
+            Action::ZoomIn => &[KeyCode::Equal, KeyCode::NumpadAdd],
+            Action::ZoomOut => &[KeyCode::Minus, KeyCode::NumpadSubtract],
+            Action::ResetCamera => &[KeyCode::Home],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// Key names accepted in the bindings file, and shown in the UI
+const KEY_NAMES: &[(KeyCode, &str)] = &[
+    (KeyCode::KeyA, "A"),
+    (KeyCode::KeyB, "B"),
+    (KeyCode::KeyC, "C"),
+    (KeyCode::KeyD, "D"),
+    (KeyCode::KeyE, "E"),
+    (KeyCode::KeyF, "F"),
+    (KeyCode::KeyG, "G"),
+    (KeyCode::KeyH, "H"),
+    (KeyCode::KeyI, "I"),
+    (KeyCode::KeyJ, "J"),
+    (KeyCode::KeyK, "K"),
+    (KeyCode::KeyL, "L"),
+    (KeyCode::KeyM, "M"),
+    (KeyCode::KeyN, "N"),
+    (KeyCode::KeyO, "O"),
+    (KeyCode::KeyP, "P"),
+    (KeyCode::KeyQ, "Q"),
+    (KeyCode::KeyR, "R"),
+    (KeyCode::KeyS, "S"),
+    (KeyCode::KeyT, "T"),
+    (KeyCode::KeyU, "U"),
+    (KeyCode::KeyV, "V"),
+    (KeyCode::KeyW, "W"),
+    (KeyCode::KeyX, "X"),
+    (KeyCode::KeyY, "Y"),
+    (KeyCode::KeyZ, "Z"),
+    (KeyCode::Digit0, "0"),
+    (KeyCode::Digit1, "1"),
+    (KeyCode::Digit2, "2"),
+    (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"),
+    (KeyCode::Digit5, "5"),
+    (KeyCode::Digit6, "6"),
+    (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"),
+    (KeyCode::Digit9, "9"),
+    (KeyCode::Space, "Space"),
+    (KeyCode::Escape, "Esc"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::PageDown, "PageDown"),
+    (KeyCode::ArrowUp, "Up"),
+    (KeyCode::ArrowDown, "Down"),
+    (KeyCode::ArrowLeft, "Left"),
+    (KeyCode::ArrowRight, "Right"),
+    (KeyCode::Comma, ","),
+    (KeyCode::Period, "."),
+    (KeyCode::Minus, "-"),
+    (KeyCode::Equal, "="),
+    (KeyCode::Slash, "/"),
+    (KeyCode::Semicolon, ";"),
+    (KeyCode::BracketLeft, "["),
+    (KeyCode::BracketRight, "]"),
+    (KeyCode::ShiftLeft, "LShift"),
+    (KeyCode::ShiftRight, "RShift"),
+    (KeyCode::ControlLeft, "LCtrl"),
+    (KeyCode::ControlRight, "RCtrl"),
+    (KeyCode::AltLeft, "LAlt"),
+    (KeyCode::AltRight, "RAlt"),
+    (KeyCode::NumpadAdd, "Num+"),
+    (KeyCode::NumpadSubtract, "Num-"),
+];
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(_, known)| known.eq_ignore_ascii_case(name))
+        .map(|(key, _)| *key)
+}
+
+fn key_name(key: KeyCode) -> String {
+    KEY_NAMES
+        .iter()
+        .find(|(known, _)| *known == key)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("{:?}", key))
+}
+
+/// The keys bound to every action: the defaults with the player's overrides applied
+#[derive(Resource, Debug, Clone)]
+pub struct KeyBindings {
+    keys: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_keys().to_vec()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keys
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn pressed(&self, action: Action, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.any_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn just_pressed(&self, action: Action, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.any_just_pressed(self.keys(action).iter().copied())
+    }
+
+    /// Keys for an action as the player reads them, e.g. `W/Up`
+    pub fn label(&self, action: Action) -> String {
+        let names: Vec<String> = self.keys(action).iter().copied().map(key_name).collect();
+        if names.is_empty() {
+            "unbound".to_string()
+        } else {
+            names.join("/")
+        }
+    }
+
+    /// The first key of an action, for buttons too small for the full label
+    pub fn short_label(&self, action: Action) -> String {
+        self.keys(action)
+            .first()
+            .map(|key| key_name(*key))
+            .unwrap_or_default()
+    }
+
+    /// Defaults, overridden by the bindings file when there is one
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(BINDINGS_FILE) {
+            Ok(contents) => {
+                parse_bindings(&contents).map_err(|err| format!("{}: {}", BINDINGS_FILE, err))
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+}
+
+/// Keyboard state read through the active bindings
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    bindings: Res<'w, KeyBindings>,
+}
+
+impl ActionInput<'_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.bindings.pressed(action, &self.keyboard)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.bindings.just_pressed(action, &self.keyboard)
+    }
+}
+
+/// Parse `action: Key Key` lines on top of the defaults. Everything after a `#`
+/// is a comment; an action with no keys is unbound.
+pub fn parse_bindings(contents: &str) -> Result<KeyBindings, String> {
+    let mut bindings = KeyBindings::default();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: String| format!("line {}: {}", index + 1, message);
+        let Some((name, keys)) = line.split_once(':') else {
+            return Err(error(format!("expected `action: keys`, got `{}`", line)));
+        };
+        let Some(action) = Action::from_name(name.trim()) else {
+            return Err(error(format!("unknown action `{}`", name.trim())));
+        };
+        let keys = keys
+            .split_whitespace()
+            .map(|key| key_from_name(key).ok_or_else(|| format!("unknown key `{}`", key)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(error)?;
+
+        bindings.keys.insert(action, keys);
+    }
+
+    Ok(bindings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_replace_only_their_action() {
+        let bindings = parse_bindings("select_road: r Q # swap\ntoggle_pause: P\n").unwrap();
+
+        assert_eq!(
+            bindings.keys(Action::SelectRoad),
+            [KeyCode::KeyR, KeyCode::KeyQ]
+        );
+        assert_eq!(bindings.label(Action::TogglePause), "P");
+        assert_eq!(bindings.label(Action::CameraUp), "W/Up");
+    }
+
+    #[test]
+    fn rejects_unknown_actions_and_keys() {
+        assert!(parse_bindings("fly: W").is_err());
+        assert!(parse_bindings("camera_up: Hyperspace").is_err());
+        assert!(
+            parse_bindings("reset_camera:")
+                .unwrap()
+                .keys(Action::ResetCamera)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn every_default_key_has_a_name() {
+        for action in Action::ALL {
+            for key in action.default_keys() {
+                assert!(key_from_name(&key_name(*key)).is_some(), "{:?}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn bundled_bindings_file_parses() {
+        assert!(parse_bindings(include_str!("../../config/keybindings.txt")).is_ok());
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+mod bindings;
+
+pub use bindings::{Action, ActionInput, KeyBindings};
+
+/// Player-facing game options
+#[derive(Resource)]
+pub struct GameSettings {
+    /// Whether the city event scheduler may fire disasters, booms and festivals
+    pub random_events: bool,
+    /// Seed for the city event scheduler so a run can be replayed
+    pub event_seed: u64,
+    /// Lay new maps out as an isometric diamond instead of a top-down grid
+    pub isometric: bool,
+    /// Pan the camera when the cursor rests against the edge of the window
+    pub edge_panning: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            random_events: true,
+            event_seed: 0x60_0D,
+            isometric: false,
+            edge_panning: false,
+        }
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let bindings = KeyBindings::load().unwrap_or_else(|err| {
+            error!("Could not load key bindings, using the defaults: {}", err);
+            KeyBindings::default()
+        });
+
+        app.init_resource::<GameSettings>()
+            .insert_resource(bindings);
+    }
+}
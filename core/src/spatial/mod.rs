@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::TilePos;
 use std::collections::{HashMap, HashSet};
 
-use crate::budget::{BuildingDemolished, BuildingPlaced, BuildingType};
+use crate::budget::{BuildingDemolished, BuildingPlaced, BuildingType, Footprint};
 
 const CELL_SIZE: i32 = 8;
 
@@ -20,30 +20,55 @@ fn cell_keys_in_radius(center: &TilePos, radius: i32) -> impl Iterator<Item = (i
     keys.into_iter()
 }
 
+/// Buildings of one kind, indexed by every tile they cover. Queries answer with
+/// the origin tile of each building, so a large building is found once however
+/// many of its tiles are in range.
 #[derive(Default)]
 struct TypedSpatialGrid {
     cells: HashMap<(i32, i32), HashSet<TilePos>>,
+    /// Origin of the building covering each tile of a multi-tile building
+    origins: HashMap<TilePos, TilePos>,
 }
 
 impl TypedSpatialGrid {
-    fn insert(&mut self, pos: TilePos) {
-        let key = cell_key(&pos);
-        self.cells.entry(key).or_default().insert(pos);
+    fn insert(&mut self, origin: TilePos, footprint: Footprint) {
+        for pos in footprint.tiles(origin) {
+            self.cells.entry(cell_key(&pos)).or_default().insert(pos);
+            if !footprint.is_single() {
+                self.origins.insert(pos, origin);
+            }
+        }
     }
 
-    fn remove(&mut self, pos: &TilePos) {
-        let key = cell_key(pos);
-        if let Some(set) = self.cells.get_mut(&key) {
-            set.remove(pos);
-            if set.is_empty() {
-                self.cells.remove(&key);
+    fn remove(&mut self, origin: &TilePos, footprint: Footprint) {
+        for pos in footprint.tiles(*origin) {
+            let key = cell_key(&pos);
+            if let Some(set) = self.cells.get_mut(&key) {
+                set.remove(&pos);
+                if set.is_empty() {
+                    self.cells.remove(&key);
+                }
             }
+            self.origins.remove(&pos);
         }
     }
 
-    fn query_chebyshev(&self, center: &TilePos, radius: i32) -> impl Iterator<Item = &TilePos> {
+    fn origin_of(&self, pos: &TilePos) -> TilePos {
+        self.origins.get(pos).copied().unwrap_or(*pos)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = TilePos> {
+        self.cells
+            .values()
+            .flatten()
+            .filter(|pos| self.origin_of(pos) == **pos)
+            .copied()
+    }
+
+    fn query_chebyshev(&self, center: &TilePos, radius: i32) -> impl Iterator<Item = TilePos> {
         let center_x = center.x as i32;
         let center_y = center.y as i32;
+        let mut seen = HashSet::new();
 
         cell_keys_in_radius(center, radius)
             .filter_map(|key| self.cells.get(&key))
@@ -53,6 +78,8 @@ impl TypedSpatialGrid {
                 let dy = (pos.y as i32 - center_y).abs();
                 dx <= radius && dy <= radius
             })
+            .map(|pos| self.origin_of(pos))
+            .filter(move |origin| seen.insert(*origin))
     }
 }
 
@@ -62,55 +89,98 @@ pub struct SpatialGrid {
     commercial: TypedSpatialGrid,
     industry: TypedSpatialGrid,
     roads: TypedSpatialGrid,
-    all_buildings: TypedSpatialGrid,
+    bus_stops: TypedSpatialGrid,
+    decorative: TypedSpatialGrid,
+    schools: TypedSpatialGrid,
+    stadiums: TypedSpatialGrid,
+    /// Abandoned lots; they no longer count as buildings of any type
+    derelicts: TypedSpatialGrid,
 }
 
 impl SpatialGrid {
-    pub fn insert(&mut self, pos: TilePos, building_type: BuildingType) {
-        self.all_buildings.insert(pos);
-        match building_type {
-            BuildingType::Residential => self.residential.insert(pos),
-            BuildingType::Commercial => self.commercial.insert(pos),
-            BuildingType::Industry => self.industry.insert(pos),
-            BuildingType::Road => self.roads.insert(pos),
-            BuildingType::Decorative => {}
-        }
+    pub fn insert(&mut self, pos: TilePos, footprint: Footprint, building_type: BuildingType) {
+        self.grid_for_mut(building_type).insert(pos, footprint);
     }
 
-    pub fn remove(&mut self, pos: &TilePos, building_type: BuildingType) {
-        self.all_buildings.remove(pos);
-        match building_type {
-            BuildingType::Residential => self.residential.remove(pos),
-            BuildingType::Commercial => self.commercial.remove(pos),
-            BuildingType::Industry => self.industry.remove(pos),
-            BuildingType::Road => self.roads.remove(pos),
-            BuildingType::Decorative => {}
-        }
+    pub fn remove(&mut self, pos: &TilePos, footprint: Footprint, building_type: BuildingType) {
+        self.grid_for_mut(building_type).remove(pos, footprint);
+    }
+
+    pub fn insert_derelict(&mut self, pos: TilePos, footprint: Footprint) {
+        self.derelicts.insert(pos, footprint);
+    }
+
+    pub fn remove_derelict(&mut self, pos: &TilePos, footprint: Footprint) {
+        self.derelicts.remove(pos, footprint);
+    }
+
+    pub fn count_derelicts_in_radius(&self, center: &TilePos, radius: i32) -> u32 {
+        self.derelicts
+            .query_chebyshev(center, radius)
+            .filter(|pos| *pos != *center)
+            .count() as u32
     }
 
     pub fn count_residential_in_radius(&self, center: &TilePos, radius: i32) -> u32 {
         self.residential
             .query_chebyshev(center, radius)
-            .filter(|pos| **pos != *center)
+            .filter(|pos| *pos != *center)
             .count() as u32
     }
 
-    pub fn has_road_in_radius(&self, center: &TilePos, radius: i32) -> bool {
-        self.roads.query_chebyshev(center, radius).next().is_some()
+    fn grid_for(&self, building_type: BuildingType) -> &TypedSpatialGrid {
+        match building_type {
+            BuildingType::Residential => &self.residential,
+            BuildingType::Commercial => &self.commercial,
+            BuildingType::Industry => &self.industry,
+            BuildingType::Road => &self.roads,
+            BuildingType::BusStop => &self.bus_stops,
+            BuildingType::Decorative => &self.decorative,
+            BuildingType::School => &self.schools,
+            BuildingType::Stadium => &self.stadiums,
+        }
     }
 
-    pub fn has_building_in_radius(&self, center: &TilePos, radius: i32) -> bool {
-        self.all_buildings
-            .query_chebyshev(center, radius)
-            .any(|pos| *pos != *center)
+    fn grid_for_mut(&mut self, building_type: BuildingType) -> &mut TypedSpatialGrid {
+        match building_type {
+            BuildingType::Residential => &mut self.residential,
+            BuildingType::Commercial => &mut self.commercial,
+            BuildingType::Industry => &mut self.industry,
+            BuildingType::Road => &mut self.roads,
+            BuildingType::BusStop => &mut self.bus_stops,
+            BuildingType::Decorative => &mut self.decorative,
+            BuildingType::School => &mut self.schools,
+            BuildingType::Stadium => &mut self.stadiums,
+        }
     }
 
-    pub fn buildings_in_radius(&self, center: &TilePos, radius: i32) -> Vec<TilePos> {
-        self.all_buildings
+    pub fn buildings_of_type_in_radius(
+        &self,
+        center: &TilePos,
+        radius: i32,
+        building_type: BuildingType,
+    ) -> Vec<TilePos> {
+        self.grid_for(building_type)
             .query_chebyshev(center, radius)
-            .copied()
             .collect()
     }
+
+    /// Every live building of `building_type` on the map, in no particular order
+    pub fn buildings_of_type(&self, building_type: BuildingType) -> Vec<TilePos> {
+        self.grid_for(building_type).iter().collect()
+    }
+
+    pub fn has_road_at(&self, pos: &TilePos) -> bool {
+        self.roads.query_chebyshev(pos, 0).next().is_some()
+    }
+
+    pub fn has_bus_stop_at(&self, pos: &TilePos) -> bool {
+        self.bus_stops.query_chebyshev(pos, 0).next().is_some()
+    }
+
+    pub fn has_road_in_radius(&self, center: &TilePos, radius: i32) -> bool {
+        self.roads.query_chebyshev(center, radius).next().is_some()
+    }
 }
 
 pub fn sync_spatial_grid_on_placement(
@@ -118,7 +188,7 @@ pub fn sync_spatial_grid_on_placement(
     mut placed_reader: MessageReader<BuildingPlaced>,
 ) {
     for event in placed_reader.read() {
-        spatial_grid.insert(event.tile_pos, event.building_type);
+        spatial_grid.insert(event.tile_pos, event.footprint, event.building_type);
     }
 }
 
@@ -127,7 +197,7 @@ pub fn sync_spatial_grid_on_demolition(
     mut demolished_reader: MessageReader<BuildingDemolished>,
 ) {
     for event in demolished_reader.read() {
-        spatial_grid.remove(&event.tile_pos, event.building_type);
+        spatial_grid.remove(&event.tile_pos, event.footprint, event.building_type);
     }
 }
 
@@ -154,16 +224,16 @@ mod tests {
     fn typed_grid_insert_query_remove() {
         let mut grid = TypedSpatialGrid::default();
 
-        grid.insert(tile(10, 10));
-        grid.insert(tile(11, 10));
-        grid.insert(tile(20, 20)); // Far away
+        grid.insert(tile(10, 10), Footprint::SINGLE);
+        grid.insert(tile(11, 10), Footprint::SINGLE);
+        grid.insert(tile(20, 20), Footprint::SINGLE); // Far away
 
         // Query within radius 2 should find 2 positions
         let results: Vec<_> = grid.query_chebyshev(&tile(10, 10), 2).collect();
         assert_eq!(results.len(), 2);
 
         // After removal, only 1 remains
-        grid.remove(&tile(10, 10));
+        grid.remove(&tile(10, 10), Footprint::SINGLE);
         let results: Vec<_> = grid.query_chebyshev(&tile(10, 10), 2).collect();
         assert_eq!(results.len(), 1);
     }
@@ -172,13 +242,58 @@ mod tests {
     fn spatial_grid_tracks_building_types_separately() {
         let mut grid = SpatialGrid::default();
 
-        grid.insert(tile(5, 5), BuildingType::Residential);
-        grid.insert(tile(6, 6), BuildingType::Road);
-        grid.insert(tile(7, 7), BuildingType::Commercial);
+        grid.insert(tile(5, 5), Footprint::SINGLE, BuildingType::Residential);
+        grid.insert(tile(6, 6), Footprint::SINGLE, BuildingType::Road);
+        grid.insert(tile(7, 7), Footprint::SINGLE, BuildingType::Commercial);
 
         assert_eq!(grid.count_residential_in_radius(&tile(10, 10), 10), 1);
         assert!(grid.has_road_in_radius(&tile(10, 10), 10));
-        assert_eq!(grid.buildings_in_radius(&tile(6, 6), 5).len(), 3);
+        assert_eq!(
+            grid.buildings_of_type_in_radius(&tile(6, 6), 5, BuildingType::Commercial),
+            vec![tile(7, 7)]
+        );
+    }
+
+    #[test]
+    fn typed_radius_query_filters_by_building_type() {
+        let mut grid = SpatialGrid::default();
+
+        grid.insert(tile(5, 5), Footprint::SINGLE, BuildingType::Commercial);
+        grid.insert(tile(6, 5), Footprint::SINGLE, BuildingType::Industry);
+        grid.insert(tile(20, 20), Footprint::SINGLE, BuildingType::Industry);
+
+        let commercial = grid.buildings_of_type_in_radius(&tile(5, 5), 1, BuildingType::Commercial);
+        assert_eq!(commercial, vec![tile(5, 5)]);
+        let industry = grid.buildings_of_type_in_radius(&tile(5, 5), 20, BuildingType::Industry);
+        assert_eq!(industry.len(), 2);
+    }
+
+    #[test]
+    fn bus_stops_are_tracked_by_position() {
+        let mut grid = SpatialGrid::default();
+
+        grid.insert(tile(3, 3), Footprint::SINGLE, BuildingType::BusStop);
+        assert!(grid.has_bus_stop_at(&tile(3, 3)));
+        assert!(!grid.has_bus_stop_at(&tile(3, 4)));
+
+        grid.remove(&tile(3, 3), Footprint::SINGLE, BuildingType::BusStop);
+        assert!(!grid.has_bus_stop_at(&tile(3, 3)));
+    }
+
+    #[test]
+    fn derelicts_are_tracked_apart_from_buildings() {
+        let mut grid = SpatialGrid::default();
+
+        grid.insert_derelict(tile(4, 4), Footprint::SINGLE);
+        assert_eq!(grid.count_derelicts_in_radius(&tile(5, 5), 1), 1);
+        assert_eq!(grid.count_derelicts_in_radius(&tile(4, 4), 1), 0);
+        assert!(
+            grid.buildings_of_type_in_radius(&tile(4, 4), 0, BuildingType::Residential)
+                .is_empty()
+        );
+
+        grid.remove_derelict(&tile(4, 4), Footprint::SINGLE);
+        assert_eq!(grid.count_derelicts_in_radius(&tile(5, 5), 1), 0);
     }
 
     #[test]
@@ -186,13 +301,12 @@ mod tests {
         let mut grid = SpatialGrid::default();
         let center = tile(10, 10);
 
-        grid.insert(center, BuildingType::Residential);
-        grid.insert(tile(11, 10), BuildingType::Residential);
+        grid.insert(center, Footprint::SINGLE, BuildingType::Residential);
+        grid.insert(tile(11, 10), Footprint::SINGLE, BuildingType::Residential);
 
         // count_residential excludes center
         assert_eq!(grid.count_residential_in_radius(&center, 5), 1);
-        // has_building excludes center
-        assert!(!grid.has_building_in_radius(&center, 0));
+        assert_eq!(grid.count_residential_in_radius(&center, 0), 0);
     }
 
     #[test]
@@ -200,10 +314,40 @@ mod tests {
         let mut grid = SpatialGrid::default();
         let pos = tile(5, 5);
 
-        grid.insert(pos, BuildingType::Residential);
-        grid.remove(&pos, BuildingType::Residential);
+        grid.insert(pos, Footprint::SINGLE, BuildingType::Residential);
+        grid.remove(&pos, Footprint::SINGLE, BuildingType::Residential);
 
         assert_eq!(grid.count_residential_in_radius(&tile(6, 6), 5), 0);
-        assert!(!grid.has_building_in_radius(&tile(6, 6), 5));
+        assert!(
+            grid.buildings_of_type_in_radius(&tile(6, 6), 5, BuildingType::Residential)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn multi_tile_buildings_are_found_once_from_any_tile() {
+        let mut grid = SpatialGrid::default();
+        let stadium = Footprint {
+            width: 3,
+            height: 2,
+        };
+
+        grid.insert(tile(10, 10), stadium, BuildingType::Stadium);
+
+        // only the far corner is in range, and the answer is the origin
+        let found = grid.buildings_of_type_in_radius(&tile(14, 13), 2, BuildingType::Stadium);
+        assert_eq!(found, vec![tile(10, 10)]);
+        assert_eq!(
+            grid.buildings_of_type_in_radius(&tile(11, 10), 5, BuildingType::Stadium)
+                .len(),
+            1
+        );
+        assert_eq!(grid.buildings_of_type(BuildingType::Stadium).len(), 1);
+
+        grid.remove(&tile(10, 10), stadium, BuildingType::Stadium);
+        assert!(
+            grid.buildings_of_type_in_radius(&tile(11, 10), 5, BuildingType::Stadium)
+                .is_empty()
+        );
     }
 }
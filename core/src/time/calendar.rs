@@ -0,0 +1,107 @@
+use super::resources::GameClock;
+
+/// Months are short so the seasons turn within a play session
+pub const DAYS_PER_MONTH: u32 = 10;
+const MONTHS_PER_YEAR: u32 = 12;
+/// A new city is founded at the start of March
+const FIRST_MONTH: u32 = 2;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Season of a month counted from January = 0
+    pub fn of_month(month: u32) -> Self {
+        match month % MONTHS_PER_YEAR {
+            2..=4 => Season::Spring,
+            5..=7 => Season::Summer,
+            8..=10 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+impl GameClock {
+    /// Months since January of the founding year
+    fn months_elapsed(&self) -> u32 {
+        FIRST_MONTH + self.day.saturating_sub(1) / DAYS_PER_MONTH
+    }
+
+    /// Month of the year, January = 0
+    pub fn month(&self) -> u32 {
+        self.months_elapsed() % MONTHS_PER_YEAR
+    }
+
+    /// Day within the month, starting at 1
+    pub fn day_of_month(&self) -> u32 {
+        self.day.saturating_sub(1) % DAYS_PER_MONTH + 1
+    }
+
+    /// Year of the city, starting at 1
+    pub fn year(&self) -> u32 {
+        self.months_elapsed() / MONTHS_PER_YEAR + 1
+    }
+
+    pub fn season(&self) -> Season {
+        Season::of_month(self.month())
+    }
+
+    /// Date as shown on screen, e.g. "Mar 4, year 1"
+    pub fn date_label(&self) -> String {
+        format!(
+            "{} {}, year {}",
+            MONTH_NAMES[self.month() as usize],
+            self.day_of_month(),
+            self.year()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on_day(day: u32) -> GameClock {
+        GameClock {
+            day,
+            ..GameClock::default()
+        }
+    }
+
+    #[test]
+    fn a_city_is_founded_in_early_spring() {
+        let clock = on_day(1);
+        assert_eq!(clock.date_label(), "Mar 1, year 1");
+        assert_eq!(clock.season(), Season::Spring);
+    }
+
+    #[test]
+    fn days_roll_over_into_months_seasons_and_years() {
+        assert_eq!(on_day(DAYS_PER_MONTH + 1).date_label(), "Apr 1, year 1");
+        assert_eq!(on_day(3 * DAYS_PER_MONTH + 1).season(), Season::Summer);
+        assert_eq!(on_day(9 * DAYS_PER_MONTH + 1).season(), Season::Winter);
+        assert_eq!(
+            on_day(10 * DAYS_PER_MONTH + 5).date_label(),
+            "Jan 5, year 2"
+        );
+        assert_eq!(on_day(12 * DAYS_PER_MONTH + 1).season(), Season::Spring);
+    }
+}
@@ -0,0 +1,172 @@
+use super::resources::GameClock;
+
+/// Hour the sun is fully up, and the hour it starts to set
+const SUNRISE_END: f32 = 7.0;
+const SUNSET_START: f32 = 17.0;
+/// Hour it is fully dark, and the hour dawn begins
+const NIGHT_START: f32 = 20.0;
+const NIGHT_END: f32 = 5.0;
+
+/// How many of the day's commuting trips start in each hour, relative to each other.
+/// Two peaks: the morning run to work and the evening run home.
+const COMMUTE_WEIGHTS: [u32; 24] = [
+    1, 1, 1, 1, 2, 4, 10, 18, 20, 10, 5, 4, 5, 5, 4, 5, 10, 18, 20, 10, 5, 3, 2, 1,
+];
+
+/// How much of the day's shop sales happen in each hour; most people shop after work
+const SHOPPING_WEIGHTS: [u32; 24] = [
+    1, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 8, 7, 6, 6, 8, 12, 16, 18, 16, 12, 6, 3,
+];
+
+/// Hours whose commuting share is at least this many times the daily average count as rush hour
+const RUSH_HOUR_FACTOR: f32 = 2.0;
+
+impl GameClock {
+    /// Hour of the day with minutes as a fraction, e.g. 18.5 at half past six
+    pub fn hour_of_day(&self) -> f32 {
+        self.hour as f32 + self.minute as f32 / 60.0
+    }
+
+    /// Hours since the game began, for systems that act once per in-game hour
+    pub fn hour_index(&self) -> u64 {
+        (self.day.saturating_sub(1) as u64) * 24 + self.hour as u64
+    }
+}
+
+/// Most hours one frame can skip, even at the fastest game speed. A bigger jump
+/// means another city was started or loaded, whose past hours are not ours to replay.
+const MAX_CATCH_UP_HOURS: u64 = 24;
+
+/// Hours of the day that began since `last_hour` was recorded, oldest first, so
+/// systems that act once an hour don't skip any when the game runs fast
+pub fn hours_since(last_hour: &mut Option<u64>, clock: &GameClock) -> impl Iterator<Item = u8> {
+    let now = clock.hour_index();
+    let first = last_hour
+        .filter(|last| now.saturating_sub(*last) <= MAX_CATCH_UP_HOURS)
+        .map_or(now, |last| last + 1);
+    *last_hour = Some(now);
+    (first..=now).map(|index| (index % 24) as u8)
+}
+
+/// 0.0 in full daylight, 1.0 in the middle of the night, easing through dusk and dawn
+pub fn darkness(hour: f32) -> f32 {
+    let hour = hour.rem_euclid(24.0);
+    let t = if !(NIGHT_END..NIGHT_START).contains(&hour) {
+        1.0
+    } else if hour < SUNRISE_END {
+        1.0 - (hour - NIGHT_END) / (SUNRISE_END - NIGHT_END)
+    } else if hour < SUNSET_START {
+        0.0
+    } else {
+        (hour - SUNSET_START) / (NIGHT_START - SUNSET_START)
+    };
+    // smoothstep so the light doesn't change at a constant, mechanical rate
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn share(weights: &[u32; 24], hour: u8) -> f32 {
+    let total: u32 = weights.iter().sum();
+    weights[hour as usize % 24] as f32 / total as f32
+}
+
+/// Part of `daily_total` that falls in `hour` according to `weights`. Summed over a
+/// whole day the parts add up to exactly `daily_total`.
+fn split(daily_total: i64, weights: &[u32; 24], hour: u8) -> i64 {
+    let total: u32 = weights.iter().sum();
+    let hour = hour as usize % 24;
+    let before: u32 = weights[..hour].iter().sum();
+    let through = before + weights[hour];
+    let rounded = |weight: u32| (daily_total as f64 * weight as f64 / total as f64).round() as i64;
+    rounded(through) - rounded(before)
+}
+
+/// Share of the day's commuting trips made in `hour`
+pub fn commute_share(hour: u8) -> f32 {
+    share(&COMMUTE_WEIGHTS, hour)
+}
+
+/// Morning and evening commute peaks
+pub fn is_rush_hour(hour: u8) -> bool {
+    commute_share(hour) * 24.0 >= RUSH_HOUR_FACTOR
+}
+
+/// Part of a day's bus fares collected in `hour`; riders pay as they board
+pub fn fares_in_hour(daily_fares: i64, hour: u8) -> i64 {
+    split(daily_fares, &COMMUTE_WEIGHTS, hour)
+}
+
+/// Part of a day's shop takings made in `hour`
+pub fn sales_in_hour(daily_sales: i64, hour: u8) -> i64 {
+    split(daily_sales, &SHOPPING_WEIGHTS, hour)
+}
+
+/// Whether shops are busy enough to keep their lights on
+pub fn shops_open(hour: u8) -> bool {
+    SHOPPING_WEIGHTS[hour as usize % 24] > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nights_are_dark_and_days_are_bright() {
+        assert_eq!(darkness(0.0), 1.0);
+        assert_eq!(darkness(12.0), 0.0);
+        assert_eq!(darkness(22.5), 1.0);
+        assert!(darkness(18.5) > darkness(17.5));
+        assert!(darkness(5.5) > darkness(6.5));
+    }
+
+    #[test]
+    fn hourly_splits_add_up_to_the_daily_total() {
+        for total in [0, 7, 1_234, -95] {
+            assert_eq!(
+                (0..24).map(|hour| fares_in_hour(total, hour)).sum::<i64>(),
+                total
+            );
+            assert_eq!(
+                (0..24).map(|hour| sales_in_hour(total, hour)).sum::<i64>(),
+                total
+            );
+        }
+        assert!(sales_in_hour(1_000, 19) > sales_in_hour(1_000, 10));
+    }
+
+    #[test]
+    fn hourly_systems_catch_up_on_skipped_hours() {
+        let mut last = None;
+        let mut clock = GameClock {
+            day: 2,
+            hour: 22,
+            minute: 0,
+            second: 0,
+        };
+        assert_eq!(hours_since(&mut last, &clock).collect::<Vec<_>>(), [22]);
+        assert_eq!(hours_since(&mut last, &clock).count(), 0);
+
+        clock.day = 3;
+        clock.hour = 1;
+        assert_eq!(
+            hours_since(&mut last, &clock).collect::<Vec<_>>(),
+            [23, 0, 1]
+        );
+
+        // a loaded city weeks ahead starts counting from its own clock
+        clock.day = 40;
+        assert_eq!(hours_since(&mut last, &clock).collect::<Vec<_>>(), [1]);
+        // and a new city back on day one skips nothing twice
+        clock.day = 1;
+        assert_eq!(hours_since(&mut last, &clock).count(), 0);
+        clock.hour = 2;
+        assert_eq!(hours_since(&mut last, &clock).collect::<Vec<_>>(), [2]);
+    }
+
+    #[test]
+    fn rush_hour_is_morning_and_evening() {
+        assert!(is_rush_hour(8));
+        assert!(is_rush_hour(18));
+        assert!(!is_rush_hour(3));
+        assert!(!is_rush_hour(13));
+    }
+}
@@ -1,4 +1,8 @@
 use super::resources::{GameClock, GameTime, HelpOverlayState, HelpPage, TimeSpeed};
+use super::weather::WeatherState;
+use crate::map::REFUND_WINDOW_HOURS;
+use crate::menu::GameState;
+use crate::settings::{Action, KeyBindings};
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -22,10 +26,76 @@ pub struct HelpPageOne;
 #[derive(Component)]
 pub struct HelpPageTwo;
 
+/// Second help page, written with whatever keys the player has bound
+fn controls_help(bindings: &KeyBindings) -> String {
+    let key = |action| bindings.label(action);
+    format!(
+        "You start with the land in the middle of the map. Press {buy_land} and click a\n\
+plot next to it to buy more land.\n\
+\n\
+Press {r}/{c}/{i}/{b}/{o}/{e}/{g} or use the buttons to select Residential, Commercial,\n\
+Industry, Decorative, Roads, Schools or Stadiums.\n\
+A stadium covers 3x2 tiles; press {rotate} to turn it before placing.\n\
+\n\
+Use '{next}' and '{previous}' to change building or road variants.\n\
+\n\
+Build a road out to one of the HWY tiles at the map edge, otherwise\n\
+nobody can move in.\n\
+\n\
+Press {t} to place bus stops next to roads, then {l} to link them into a\n\
+bus line (click stops in order, {finish} to finish).\n\
+\n\
+Use Click+{demolish} to demolish the building, or press {bulldoze} and drag\n\
+to clear an area. Buildings torn down within {refund_hours} hours of going up\n\
+refund half their cost; older ones cost a tenth of it to demolish.\n\
+With no tool selected ({cancel}), click a building to inspect it.\n\
+\n\
+Press {log} to open the event log, click an entry to jump to it.\n\
+Press {milestones} to see the milestones that unlock new buildings.\n\
+\n\
+{up},{left},{down},{right} or middle-drag to move, {zoom_in} {zoom_out} or the wheel to zoom.\n\
+Click the minimap to jump there.\n\
+\n\
+{pause} pauses time, {normal}/{fast}/{ultra} change game speed.\n\
+\n\
+If you want to see this window again, press the \"?\" button.",
+        buy_land = key(Action::BuyLand),
+        r = key(Action::SelectResidential),
+        c = key(Action::SelectCommercial),
+        i = key(Action::SelectIndustry),
+        b = key(Action::SelectDecorative),
+        o = key(Action::SelectRoad),
+        e = key(Action::SelectSchool),
+        g = key(Action::SelectStadium),
+        rotate = key(Action::Rotate),
+        next = key(Action::NextVariant),
+        previous = key(Action::PreviousVariant),
+        t = key(Action::SelectBusStop),
+        l = key(Action::EditBusLine),
+        finish = key(Action::FinishBusLine),
+        demolish = key(Action::Demolish),
+        bulldoze = key(Action::Bulldoze),
+        refund_hours = REFUND_WINDOW_HOURS,
+        cancel = key(Action::Cancel),
+        log = key(Action::ToggleEventLog),
+        milestones = key(Action::ToggleMilestones),
+        up = key(Action::CameraUp),
+        left = key(Action::CameraLeft),
+        down = key(Action::CameraDown),
+        right = key(Action::CameraRight),
+        zoom_in = key(Action::ZoomIn),
+        zoom_out = key(Action::ZoomOut),
+        pause = key(Action::TogglePause),
+        normal = key(Action::SpeedNormal),
+        fast = key(Action::SpeedFast),
+        ultra = key(Action::SpeedUltraFast),
+    )
+}
+
 pub fn setup_time_display(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut help_state: ResMut<HelpOverlayState>,
+    bindings: Res<KeyBindings>,
 ) {
     let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
 
@@ -83,7 +153,7 @@ pub fn setup_time_display(
                 });
         });
 
-    // Central help overlay, initially visible on first load
+    // Central help overlay, shown while the game is paused on it
     commands
         .spawn((
             Node {
@@ -94,7 +164,7 @@ pub fn setup_time_display(
                 bottom: Val::Px(0.0),
                 ..default()
             },
-            Visibility::Visible,
+            Visibility::Hidden,
             HelpOverlay,
         ))
         .with_children(|parent| {
@@ -214,23 +284,7 @@ Your town will grow if people are happy.",
                     ))
                     .with_children(|card| {
                         card.spawn((
-                            Text::new(
-                                "You start in the middle of the map and only tiles near the center are\n\
-placeable at the beginning.\n\
-\n\
-Press R/C/I/B/O or use the buttons to select Residential, Commercial,\n\
-Industry, Decorative or Roads.\n\
-\n\
-Use ',' and '.' to change building or road variants.\n\
-\n\
-Use Click+Shift to demolish the building.\n\
-\n\
-W,A,S,D to move, +- to zoom.\n\
-\n\
-Space pauses time, 1/2/3 change game speed.\n\
-\n\
-If you want to see this window again, press the \"?\" button.",
-                            ),
+                            Text::new(controls_help(&bindings)),
                             TextFont {
                                 font: font.clone(),
                                 font_size: 16.0,
@@ -265,14 +319,12 @@ If you want to see this window again, press the \"?\" button.",
                     });
                 });
         });
-    help_state.active = true;
-    help_state.previous_speed = None;
-    help_state.page = HelpPage::First;
 }
 
 pub fn update_time_display(
     clock: Res<GameClock>,
     time: Res<GameTime>,
+    weather: Res<WeatherState>,
     mut query: Query<(&mut Text, &mut TextColor), With<TimeDisplayText>>,
 ) {
     let Ok((mut text, mut text_color)) = query.single_mut() else {
@@ -287,8 +339,15 @@ pub fn update_time_display(
     };
 
     text.0 = format!(
-        "Day {}, {:02}:{:02}:{:02} [{}]",
-        clock.day, clock.hour, clock.minute, clock.second, speed_str
+        "Day {}, {:02}:{:02}:{:02} [{}]\n{}, {} - {}",
+        clock.day,
+        clock.hour,
+        clock.minute,
+        clock.second,
+        speed_str,
+        clock.date_label(),
+        clock.season().label(),
+        weather.today.label()
     );
 
     text_color.0 = if time.speed == TimeSpeed::Paused {
@@ -321,84 +380,71 @@ type HelpButtonQuery<'w, 's> = Query<
     (Changed<Interaction>, With<Button>),
 >;
 
+/// A fresh city starts paused on the help overlay; a loaded one carries on
+pub fn open_help_on_start(
+    loaded: Option<Res<crate::save::LoadedCity>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if loaded.is_none() {
+        next_state.set(GameState::Paused);
+    }
+}
+
+pub fn show_help_overlay(
+    mut vis_q: HelpVisibilityQuery<'_, '_>,
+    mut help_state: ResMut<HelpOverlayState>,
+    mut current_tile_type: ResMut<crate::map::CurrentTileType>,
+) {
+    help_state.page = HelpPage::First;
+    current_tile_type.texture_index = 0;
+
+    for (mut vis, overlay, page_one, page_two) in vis_q.iter_mut() {
+        if overlay.is_some() || page_one.is_some() {
+            *vis = Visibility::Visible;
+        } else if page_two.is_some() {
+            *vis = Visibility::Hidden;
+        }
+    }
+}
+
+pub fn hide_help_overlay(mut vis_q: HelpVisibilityQuery<'_, '_>) {
+    for (mut vis, overlay, page_one, page_two) in vis_q.iter_mut() {
+        if overlay.is_some() || page_one.is_some() || page_two.is_some() {
+            *vis = Visibility::Hidden;
+        }
+    }
+}
+
+/// "?" pauses on the help overlay, its buttons page through it and resume the game
 pub fn handle_help_ui(
     mut vis_q: HelpVisibilityQuery<'_, '_>,
     mut button_q: HelpButtonQuery<'_, '_>,
-    mut game_time: ResMut<GameTime>,
     mut help_state: ResMut<HelpOverlayState>,
-    mut current_tile_type: Option<ResMut<crate::map::CurrentTileType>>,
-    mut ui_click_blocker: Option<ResMut<crate::map::UiClickBlocker>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut ui_click_blocker: ResMut<crate::map::UiClickBlocker>,
 ) {
-    let mut show_first_page = false;
-    let mut show_second_page = false;
-    let mut hide_overlay = false;
-
     for (interaction, is_help_button, is_next_button, is_close_button) in button_q.iter_mut() {
         if *interaction != Interaction::Pressed {
             continue;
         }
+        ui_click_blocker.just_clicked_ui = true;
 
         if is_help_button.is_some() {
-            if let Some(ref mut blocker) = ui_click_blocker {
-                blocker.just_clicked_ui = true;
-            }
-            if !help_state.active {
-                help_state.previous_speed = Some(game_time.speed);
-                help_state.active = true;
-            }
-            help_state.page = HelpPage::First;
-            game_time.speed = TimeSpeed::Paused;
-            show_first_page = true;
-
-            if let Some(mut current) = current_tile_type.take() {
-                current.texture_index = 0;
+            if *state.get() == GameState::InGame {
+                next_state.set(GameState::Paused);
             }
         } else if is_next_button.is_some() {
-            if let Some(ref mut blocker) = ui_click_blocker {
-                blocker.just_clicked_ui = true;
-            }
-
             help_state.page = HelpPage::Second;
-            show_second_page = true;
-        } else if is_close_button.is_some() {
-            if let Some(ref mut blocker) = ui_click_blocker {
-                blocker.just_clicked_ui = true;
-            }
-
-            help_state.active = false;
-            if let Some(prev) = help_state.previous_speed.take() {
-                game_time.speed = prev;
-            }
-            hide_overlay = true;
-        }
-    }
-
-    // Apply visibility changes based on which button was pressed this frame.
-    if show_first_page {
-        for (mut vis, overlay, page_one, page_two) in vis_q.iter_mut() {
-            if overlay.is_some() || page_one.is_some() {
-                *vis = Visibility::Visible;
-            } else if page_two.is_some() {
-                *vis = Visibility::Hidden;
-            }
-        }
-    }
-
-    if show_second_page {
-        for (mut vis, _overlay, page_one, page_two) in vis_q.iter_mut() {
-            if page_one.is_some() {
-                *vis = Visibility::Hidden;
-            } else if page_two.is_some() {
-                *vis = Visibility::Visible;
-            }
-        }
-    }
-
-    if hide_overlay {
-        for (mut vis, overlay, page_one, page_two) in vis_q.iter_mut() {
-            if overlay.is_some() || page_one.is_some() || page_two.is_some() {
-                *vis = Visibility::Hidden;
+            for (mut vis, _overlay, page_one, page_two) in vis_q.iter_mut() {
+                if page_one.is_some() {
+                    *vis = Visibility::Hidden;
+                } else if page_two.is_some() {
+                    *vis = Visibility::Visible;
+                }
             }
+        } else if is_close_button.is_some() {
+            next_state.set(GameState::InGame);
         }
     }
 }
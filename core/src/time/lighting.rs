@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use super::day_cycle::{darkness, shops_open};
+use super::resources::GameClock;
+use crate::budget::BuildingType;
+use crate::camera::CameraController;
+use crate::map::{Derelict, PlacedBuilding};
+
+/// Above every building sprite, below the lit windows
+const NIGHT_OVERLAY_Z: f32 = 20.0;
+/// Big enough to cover the view at the furthest zoom
+const NIGHT_OVERLAY_SIZE: f32 = 20_000.0;
+const NIGHT_TINT: Color = Color::srgb(0.04, 0.06, 0.22);
+const DUSK_TINT: Color = Color::srgb(0.95, 0.45, 0.2);
+/// Opacity of the overlay in the middle of the night
+const NIGHT_TINT_ALPHA: f32 = 0.55;
+/// Extra opacity of the warm tint halfway through dusk and dawn
+const TWILIGHT_TINT_ALPHA: f32 = 0.12;
+
+const WINDOW_COLOR: Color = Color::srgb(1.0, 0.85, 0.45);
+const WINDOW_SIZE: Vec2 = Vec2::new(8.0, 10.0);
+/// Local z that puts windows over the night overlay whatever the building's depth
+const WINDOW_Z: f32 = NIGHT_OVERLAY_Z - 5.0;
+
+/// Screen-wide tint that darkens the city as the sun goes down
+#[derive(Component)]
+pub struct NightOverlay;
+
+/// A window on a building that lights up after dark
+#[derive(Component)]
+pub struct WindowLight {
+    building_type: BuildingType,
+    /// How dark it has to get before this window is switched on, so a street
+    /// doesn't light up all at once
+    threshold: f32,
+}
+
+pub fn setup_night_overlay(mut commands: Commands) {
+    commands.spawn((
+        Sprite::from_color(Color::NONE, Vec2::splat(NIGHT_OVERLAY_SIZE)),
+        Transform::from_xyz(0.0, 0.0, NIGHT_OVERLAY_Z),
+        NightOverlay,
+    ));
+}
+
+/// Tint the map for the time of day and keep the tint in front of the camera
+pub fn update_night_overlay(
+    clock: Res<GameClock>,
+    camera_q: Query<&Transform, (With<CameraController>, Without<NightOverlay>)>,
+    mut overlay_q: Query<(&mut Transform, &mut Sprite), With<NightOverlay>>,
+) {
+    let Ok(camera) = camera_q.single() else {
+        return;
+    };
+
+    let dark = darkness(clock.hour_of_day());
+    // strongest halfway through dusk and dawn, gone at noon and midnight
+    let twilight = 4.0 * dark * (1.0 - dark);
+    let night = NIGHT_TINT.to_srgba();
+    let dusk = DUSK_TINT.to_srgba();
+    let warmth = twilight * 0.5;
+    let tint = Color::srgba(
+        night.red + (dusk.red - night.red) * warmth,
+        night.green + (dusk.green - night.green) * warmth,
+        night.blue + (dusk.blue - night.blue) * warmth,
+        dark * NIGHT_TINT_ALPHA + twilight * TWILIGHT_TINT_ALPHA,
+    );
+
+    for (mut transform, mut sprite) in overlay_q.iter_mut() {
+        transform.translation.x = camera.translation.x;
+        transform.translation.y = camera.translation.y;
+        sprite.color = tint;
+    }
+}
+
+/// Where the windows sit on each kind of building, relative to its center
+const HOUSE_WINDOWS: [Vec2; 2] = [Vec2::new(-14.0, 6.0), Vec2::new(12.0, -8.0)];
+const SHOP_WINDOWS: [Vec2; 3] = [
+    Vec2::new(-20.0, -6.0),
+    Vec2::new(0.0, -6.0),
+    Vec2::new(20.0, -6.0),
+];
+const FACTORY_WINDOWS: [Vec2; 2] = [Vec2::new(-18.0, 10.0), Vec2::new(16.0, 10.0)];
+
+fn window_offsets(building_type: BuildingType) -> &'static [Vec2] {
+    match building_type {
+        BuildingType::Residential => &HOUSE_WINDOWS,
+        BuildingType::Commercial => &SHOP_WINDOWS,
+        BuildingType::Industry => &FACTORY_WINDOWS,
+        _ => &[],
+    }
+}
+
+/// Give every new house, shop and factory its windows, dark until night falls
+pub fn spawn_window_lights(
+    mut commands: Commands,
+    added_q: Query<(Entity, &PlacedBuilding), Added<PlacedBuilding>>,
+) {
+    for (entity, building) in added_q.iter() {
+        let offsets = window_offsets(building.building_type);
+        if offsets.is_empty() {
+            continue;
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            for (index, offset) in offsets.iter().enumerate() {
+                let seed = building.tile_pos.x * 7 + building.tile_pos.y * 13 + index as u32 * 5;
+                parent.spawn((
+                    Sprite::from_color(WINDOW_COLOR.with_alpha(0.0), WINDOW_SIZE),
+                    Transform::from_xyz(offset.x, offset.y, WINDOW_Z),
+                    WindowLight {
+                        building_type: building.building_type,
+                        threshold: 0.1 + (seed % 8) as f32 * 0.1,
+                    },
+                ));
+            }
+        });
+    }
+}
+
+/// Whether a window is lit: homes go dark in the small hours, shops once they
+/// close, and factories run a night shift
+fn window_lit(window: &WindowLight, hour: u8, dark: f32) -> bool {
+    if dark <= window.threshold {
+        return false;
+    }
+    match window.building_type {
+        // a few night owls stay up
+        BuildingType::Residential => !(1..5).contains(&hour) || window.threshold < 0.2,
+        BuildingType::Commercial => shops_open(hour),
+        _ => true,
+    }
+}
+
+pub fn update_window_lights(
+    clock: Res<GameClock>,
+    buildings_q: Query<(), With<Derelict>>,
+    mut windows_q: Query<(&WindowLight, &ChildOf, &mut Sprite)>,
+) {
+    let dark = darkness(clock.hour_of_day());
+
+    for (window, child_of, mut sprite) in windows_q.iter_mut() {
+        // nobody lives in a derelict lot
+        let lit = window_lit(window, clock.hour, dark) && !buildings_q.contains(child_of.parent());
+        let alpha = if lit { dark } else { 0.0 };
+        if sprite.color.alpha() != alpha {
+            sprite.color.set_alpha(alpha);
+        }
+    }
+}
@@ -1,9 +1,16 @@
 use bevy::prelude::*;
+
+use crate::menu::{GAME_EXIT, GAME_START, GameState, reset_resource};
+
+pub mod calendar;
+pub mod day_cycle;
 mod display;
+mod lighting;
 mod resources;
 mod systems;
+pub mod weather;
 
-pub use resources::{GameClock, HelpOverlayState};
+pub use resources::{GameClock, GameTime};
 
 pub struct GameTimePlugin;
 
@@ -12,16 +19,57 @@ impl Plugin for GameTimePlugin {
         app.init_resource::<resources::GameTime>()
             .init_resource::<resources::GameClock>()
             .init_resource::<resources::HelpOverlayState>()
-            .add_systems(Startup, display::setup_time_display)
+            .add_systems(
+                Startup,
+                (
+                    display::setup_time_display,
+                    lighting::setup_night_overlay,
+                    weather::setup_weather,
+                ),
+            )
+            .add_systems(OnEnter(GameState::Paused), display::show_help_overlay)
+            .add_systems(OnExit(GameState::Paused), display::hide_help_overlay)
             .add_systems(
                 Update,
                 (
                     systems::update_game_time,
                     systems::update_game_clock.after(systems::update_game_time),
                     systems::handle_time_speed_input,
+                    weather::update_weather_on_day_tick.after(systems::update_game_clock),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
                     display::update_time_display.after(systems::update_game_clock),
                     display::handle_help_ui,
                 ),
+            )
+            .add_systems(
+                Update,
+                (
+                    lighting::update_night_overlay,
+                    lighting::spawn_window_lights,
+                    lighting::update_window_lights,
+                )
+                    .chain()
+                    .after(systems::update_game_clock),
+            );
+
+        // every new city opens on the help overlay
+        for start in GAME_START {
+            app.add_systems(start, display::open_help_on_start);
+        }
+        for exit in GAME_EXIT {
+            app.add_systems(
+                exit,
+                (
+                    reset_resource::<resources::GameTime>,
+                    reset_resource::<resources::GameClock>,
+                    weather::setup_weather,
+                ),
             );
+        }
     }
 }
@@ -15,6 +15,9 @@ pub enum TimeSpeed {
     UltraFast,
 }
 
+/// A new city wakes up at eight in the morning
+const START_HOUR: f64 = 8.0;
+
 #[derive(Resource)]
 pub struct GameTime {
     pub elapsed_seconds: f64,
@@ -24,13 +27,13 @@ pub struct GameTime {
 impl Default for GameTime {
     fn default() -> Self {
         Self {
-            elapsed_seconds: 0.0,
+            elapsed_seconds: START_HOUR * 3600.0,
             speed: TimeSpeed::Paused,
         }
     }
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct GameClock {
     pub day: u32,
     pub hour: u8,   // 0-23
@@ -38,18 +41,39 @@ pub struct GameClock {
     pub second: u8, // 0-59
 }
 
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            day: 1,
+            hour: START_HOUR as u8,
+            minute: 0,
+            second: 0,
+        }
+    }
+}
+
+impl GameClock {
+    /// What the clock reads `elapsed_seconds` into the game
+    pub fn at(elapsed_seconds: f64) -> Self {
+        let total_seconds = elapsed_seconds as u64;
+        Self {
+            day: (total_seconds / 86400) as u32 + 1,
+            hour: ((total_seconds / 3600) % 24) as u8,
+            minute: ((total_seconds / 60) % 60) as u8,
+            second: (total_seconds % 60) as u8,
+        }
+    }
+}
+
+/// Which help page is showing while the game is paused on the help overlay
 #[derive(Resource)]
 pub struct HelpOverlayState {
-    pub active: bool,
-    pub previous_speed: Option<TimeSpeed>,
     pub page: HelpPage,
 }
 
 impl Default for HelpOverlayState {
     fn default() -> Self {
         Self {
-            active: false,
-            previous_speed: None,
             page: HelpPage::First,
         }
     }
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use super::resources::*;
+use crate::settings::{Action, ActionInput};
 
 // Update game time based on speed mode
 //
@@ -21,25 +22,11 @@ pub fn update_game_time(time: Res<Time>, mut game_time: ResMut<GameTime>) {
 }
 
 pub fn update_game_clock(game_time: Res<GameTime>, mut clock: ResMut<GameClock>) {
-    let total_seconds = game_time.elapsed_seconds as u64;
-    clock.second = (total_seconds % 60) as u8;
-    clock.minute = ((total_seconds / 60) % 60) as u8;
-    clock.hour = ((total_seconds / 3600) % 24) as u8;
-    clock.day = (total_seconds / 86400) as u32 + 1;
+    *clock = GameClock::at(game_time.elapsed_seconds);
 }
 
-pub fn handle_time_speed_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut game_time: ResMut<GameTime>,
-    help_state: Option<Res<HelpOverlayState>>,
-) {
-    if let Some(state) = help_state
-        && state.active
-    {
-        return;
-    }
-
-    if keyboard.just_pressed(KeyCode::Space) {
+pub fn handle_time_speed_input(actions: ActionInput, mut game_time: ResMut<GameTime>) {
+    if actions.just_pressed(Action::TogglePause) {
         game_time.speed = if game_time.speed == TimeSpeed::Paused {
             TimeSpeed::Normal
         } else {
@@ -47,13 +34,13 @@ pub fn handle_time_speed_input(
         };
     }
 
-    if keyboard.just_pressed(KeyCode::Digit1) {
+    if actions.just_pressed(Action::SpeedNormal) {
         game_time.speed = TimeSpeed::Normal;
     }
-    if keyboard.just_pressed(KeyCode::Digit2) {
+    if actions.just_pressed(Action::SpeedFast) {
         game_time.speed = TimeSpeed::Fast;
     }
-    if keyboard.just_pressed(KeyCode::Digit3) {
+    if actions.just_pressed(Action::SpeedUltraFast) {
         game_time.speed = TimeSpeed::UltraFast;
     }
 }
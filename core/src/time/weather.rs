@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::calendar::Season;
+use super::resources::GameClock;
+use crate::budget::BuildingType;
+use crate::city::resources::CityPopulation;
+use crate::notifications::Notify;
+use crate::settings::GameSettings;
+
+/// Keeps the weather from following the same rolls as the city event scheduler
+const WEATHER_SEED_SALT: u64 = 0x5EA5_0415;
+/// Chance that today's weather carries on into tomorrow
+const WEATHER_PERSIST_CHANCE: f64 = 0.5;
+/// Extra daily upkeep for every road tile while snow has to be cleared
+const SNOW_CLEARING_PER_ROAD_TILE: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Snow,
+    Heatwave,
+}
+
+impl Weather {
+    pub const ALL: [Weather; 4] = [
+        Weather::Clear,
+        Weather::Rain,
+        Weather::Snow,
+        Weather::Heatwave,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Rain => "Rain",
+            Weather::Snow => "Snow",
+            Weather::Heatwave => "Heatwave",
+        }
+    }
+
+    /// How likely this weather is in `season`, relative to the others
+    fn weight(&self, season: Season) -> u32 {
+        match (self, season) {
+            (Weather::Clear, Season::Winter) => 50,
+            (Weather::Clear, Season::Autumn) => 55,
+            (Weather::Clear, _) => 65,
+            (Weather::Rain, Season::Winter) => 20,
+            (Weather::Rain, Season::Summer) => 15,
+            (Weather::Rain, Season::Spring) => 35,
+            (Weather::Rain, Season::Autumn) => 45,
+            (Weather::Snow, Season::Winter) => 30,
+            (Weather::Heatwave, Season::Summer) => 20,
+            (Weather::Snow | Weather::Heatwave, _) => 0,
+        }
+    }
+
+    /// Daily change in happiness while this weather lasts
+    pub fn happiness_per_day(&self) -> f32 {
+        match self {
+            Weather::Clear => 0.0,
+            Weather::Rain => -0.003,
+            Weather::Snow => -0.004,
+            Weather::Heatwave => -0.01,
+        }
+    }
+
+    /// Extra daily upkeep per road tile
+    pub fn road_upkeep_per_tile(&self) -> i64 {
+        match self {
+            Weather::Snow => SNOW_CLEARING_PER_ROAD_TILE,
+            _ => 0,
+        }
+    }
+}
+
+/// Weighted pick of a day's weather for `season`
+pub fn pick_weather(rng: &mut StdRng, season: Season) -> Weather {
+    let total: u32 = Weather::ALL
+        .iter()
+        .map(|weather| weather.weight(season))
+        .sum();
+    let mut roll = rng.gen_range(0..total);
+    for weather in Weather::ALL {
+        if roll < weather.weight(season) {
+            return weather;
+        }
+        roll -= weather.weight(season);
+    }
+    Weather::Clear
+}
+
+/// Share of its entertainment an outdoor attraction still provides: parks empty out
+/// in winter and in bad weather, and stadium crowds thin out in rain, snow and heat
+pub fn outdoor_entertainment_factor(
+    building_type: BuildingType,
+    season: Season,
+    weather: Weather,
+) -> f32 {
+    match building_type {
+        BuildingType::Decorative => {
+            let season_factor = if season == Season::Winter { 0.5 } else { 1.0 };
+            let weather_factor = match weather {
+                Weather::Rain | Weather::Snow => 0.5,
+                Weather::Clear | Weather::Heatwave => 1.0,
+            };
+            season_factor * weather_factor
+        }
+        BuildingType::Stadium => match weather {
+            Weather::Clear => 1.0,
+            Weather::Rain | Weather::Heatwave => 0.75,
+            Weather::Snow => 0.5,
+        },
+        _ => 1.0,
+    }
+}
+
+/// Today's season and weather, rolled once a day from a seeded generator
+#[derive(Resource)]
+pub struct WeatherState {
+    rng: StdRng,
+    pub season: Season,
+    pub today: Weather,
+}
+
+impl WeatherState {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed ^ WEATHER_SEED_SALT),
+            season: Season::Spring,
+            today: Weather::Clear,
+        }
+    }
+
+    /// Move on to the next day's weather; it often stays the same, and anything
+    /// out of season gives way
+    pub fn advance(&mut self, season: Season) -> Weather {
+        self.season = season;
+        let lingers = self.today.weight(season) > 0 && self.rng.gen_bool(WEATHER_PERSIST_CHANCE);
+        if !lingers {
+            self.today = pick_weather(&mut self.rng, season);
+        }
+        self.today
+    }
+
+    pub fn entertainment_factor(&self, building_type: BuildingType) -> f32 {
+        outdoor_entertainment_factor(building_type, self.season, self.today)
+    }
+}
+
+pub fn setup_weather(mut commands: Commands, settings: Res<GameSettings>) {
+    commands.insert_resource(WeatherState::from_seed(settings.event_seed));
+}
+
+/// Once per in‑game day roll the weather, announce a change and let it weigh on
+/// the citizens' mood
+pub fn update_weather_on_day_tick(
+    clock: Res<GameClock>,
+    mut weather: ResMut<WeatherState>,
+    mut population: ResMut<CityPopulation>,
+    mut notify_writer: MessageWriter<Notify>,
+    mut last_weather_day: Local<u32>,
+) {
+    if *last_weather_day == clock.day {
+        return;
+    }
+    *last_weather_day = clock.day;
+
+    let yesterday = weather.today;
+    let season_changed = weather.season != clock.season();
+    let today = weather.advance(clock.season());
+
+    if season_changed {
+        notify_writer.write(Notify::info(format!(
+            "{} has come to the city",
+            clock.season().label()
+        )));
+    }
+    if today != yesterday {
+        let message = match today {
+            Weather::Clear => "The skies have cleared".to_string(),
+            Weather::Rain => "Rain: parks and stadiums are emptier".to_string(),
+            Weather::Snow => "Snow: clearing the roads adds to their upkeep".to_string(),
+            Weather::Heatwave => "Heatwave: citizens are getting grumpy".to_string(),
+        };
+        notify_writer.write(match today {
+            Weather::Clear => Notify::info(message),
+            _ => Notify::warning(message),
+        });
+    }
+
+    let delta = today.happiness_per_day();
+    if delta != 0.0 {
+        population.happiness = (population.happiness + delta).clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snow_only_falls_in_winter_and_heatwaves_only_come_in_summer() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..500 {
+            let spring = pick_weather(&mut rng, Season::Spring);
+            assert!(!matches!(spring, Weather::Snow | Weather::Heatwave));
+            assert_ne!(pick_weather(&mut rng, Season::Summer), Weather::Snow);
+            assert_ne!(pick_weather(&mut rng, Season::Winter), Weather::Heatwave);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_brings_the_same_weather() {
+        let mut a = WeatherState::from_seed(42);
+        let mut b = WeatherState::from_seed(42);
+        for season in [
+            Season::Spring,
+            Season::Summer,
+            Season::Autumn,
+            Season::Winter,
+        ] {
+            for _ in 0..10 {
+                assert_eq!(a.advance(season), b.advance(season));
+            }
+        }
+    }
+
+    #[test]
+    fn winter_and_bad_weather_empty_the_parks() {
+        let park = BuildingType::Decorative;
+        assert_eq!(
+            outdoor_entertainment_factor(park, Season::Summer, Weather::Clear),
+            1.0
+        );
+        assert_eq!(
+            outdoor_entertainment_factor(park, Season::Winter, Weather::Snow),
+            0.25
+        );
+        assert_eq!(
+            outdoor_entertainment_factor(BuildingType::Commercial, Season::Winter, Weather::Snow),
+            1.0
+        );
+    }
+}
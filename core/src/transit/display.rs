@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::map::tile_center_to_world;
+use crate::time::GameClock;
+use crate::time::day_cycle::is_rush_hour;
+
+use super::resources::{LineEditor, RoadTraffic, TransitNetwork, TransitStats};
+
+const LINE_COLORS: [Color; 4] = [
+    Color::srgb(0.95, 0.35, 0.3),
+    Color::srgb(0.3, 0.6, 0.95),
+    Color::srgb(0.4, 0.85, 0.4),
+    Color::srgb(0.8, 0.45, 0.9),
+];
+
+const PENDING_LINE_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+
+#[derive(Component)]
+pub struct TransitDisplayText;
+
+pub fn setup_transit_display(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load("fonts/Silkscreen/Silkscreen-Regular.ttf");
+
+    commands.spawn((
+        Text::new("Transit: no lines"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(56.0),
+            right: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        TextFont {
+            font,
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        TransitDisplayText,
+    ));
+}
+
+pub fn update_transit_display(
+    network: Res<TransitNetwork>,
+    stats: Res<TransitStats>,
+    traffic: Res<RoadTraffic>,
+    editor: Res<LineEditor>,
+    clock: Res<GameClock>,
+    mut query: Query<&mut Text, With<TransitDisplayText>>,
+    mut shown_hour: Local<Option<u8>>,
+) {
+    if !network.is_changed()
+        && !stats.is_changed()
+        && !traffic.is_changed()
+        && !editor.is_changed()
+        && *shown_hour == Some(clock.hour)
+    {
+        return;
+    }
+    *shown_hour = Some(clock.hour);
+
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+
+    let mut label = if network.lines.is_empty() {
+        "Transit: no lines".to_string()
+    } else {
+        format!(
+            "Transit: {} lines, {}/{} commuters by bus, {} by car\nFares ${}/day, upkeep ${}/day",
+            network.lines.len(),
+            stats.riders,
+            stats.commuters,
+            stats.road_commuters(),
+            stats.fare_income,
+            stats.upkeep
+        )
+    };
+
+    // highway trucks and migrants share the roads with commuting cars
+    label.push_str(&format!(
+        "\nRoad traffic: {} trips/day ({} via highways)",
+        traffic.daily_trips(),
+        traffic.highway_trips
+    ));
+    label.push_str(&format!(
+        "\nRoads now: {:.0}/{:.0} trips/hour{}",
+        traffic.trips_in_hour(clock.hour),
+        traffic.hourly_capacity(),
+        if is_rush_hour(clock.hour) {
+            " (rush hour)"
+        } else {
+            ""
+        }
+    ));
+    if traffic.delayed_share() > 0.0 {
+        label.push_str(&format!(
+            "\nTraffic jams: {:.0}% of trips delayed",
+            traffic.delayed_share() * 100.0
+        ));
+    }
+
+    if editor.active {
+        label.push_str(&format!(
+            "\nEditing line: {} stops (Enter to finish, Esc to discard)",
+            editor.pending_stops.len()
+        ));
+    }
+
+    text.0 = label;
+}
+
+/// Draw every bus line as a closed loop through its stops, plus the line being edited
+pub fn draw_transit_lines(
+    mut gizmos: Gizmos,
+    network: Res<TransitNetwork>,
+    editor: Res<LineEditor>,
+    map_q: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &Transform)>,
+) {
+    let Some((map_size, grid_size, map_type, map_transform)) = map_q.iter().next() else {
+        return;
+    };
+
+    let to_world = |pos: &TilePos| {
+        tile_center_to_world(pos, map_size, grid_size, map_type, map_transform).truncate()
+    };
+
+    for (index, line) in network.lines.iter().enumerate() {
+        let color = LINE_COLORS[index % LINE_COLORS.len()];
+        let points: Vec<Vec2> = line.stops.iter().map(to_world).collect();
+
+        for pair in points.windows(2) {
+            gizmos.line_2d(pair[0], pair[1], color);
+        }
+        if let (Some(first), Some(last)) = (points.first(), points.last()) {
+            gizmos.line_2d(*last, *first, color);
+        }
+        for point in &points {
+            gizmos.circle_2d(*point, 12.0, color);
+        }
+    }
+
+    if editor.active {
+        let points: Vec<Vec2> = editor.pending_stops.iter().map(to_world).collect();
+        for pair in points.windows(2) {
+            gizmos.line_2d(pair[0], pair[1], PENDING_LINE_COLOR);
+        }
+        for point in &points {
+            gizmos.circle_2d(*point, 16.0, PENDING_LINE_COLOR);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use crate::menu::{GAME_EXIT, GameState, reset_resource};
+
+mod display;
+pub mod resources;
+mod systems;
+
+pub use resources::{LineEditor, RoadTraffic, TransitNetwork, TransitStats};
+
+pub struct TransitPlugin;
+
+impl Plugin for TransitPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TransitNetwork>()
+            .init_resource::<LineEditor>()
+            .init_resource::<TransitStats>()
+            .init_resource::<RoadTraffic>()
+            .add_systems(Startup, display::setup_transit_display)
+            .add_systems(
+                Update,
+                (
+                    systems::toggle_line_editor,
+                    systems::pick_line_stop_on_click,
+                    systems::remove_demolished_stops,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::update_transit_on_day_tick,
+                    systems::collect_fares_hourly,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (display::update_transit_display, display::draw_transit_lines),
+            );
+
+        for exit in GAME_EXIT {
+            app.add_systems(
+                exit,
+                (
+                    reset_resource::<TransitNetwork>,
+                    reset_resource::<LineEditor>,
+                    reset_resource::<TransitStats>,
+                    reset_resource::<RoadTraffic>,
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::TilePos;
+
+use crate::time::day_cycle::commute_share;
+
+/// A bus route that visits its stops in order and loops back to the first one
+#[derive(Debug, Clone)]
+pub struct TransitLine {
+    pub id: u32,
+    pub stops: Vec<TilePos>,
+}
+
+/// All committed bus lines in the city
+#[derive(Resource, Default)]
+pub struct TransitNetwork {
+    pub lines: Vec<TransitLine>,
+    next_id: u32,
+}
+
+impl TransitNetwork {
+    pub fn add_line(&mut self, stops: Vec<TilePos>) -> u32 {
+        self.next_id += 1;
+        self.lines.push(TransitLine {
+            id: self.next_id,
+            stops,
+        });
+        self.next_id
+    }
+
+    /// Drop a demolished stop from every line; lines left with fewer than two stops are closed
+    pub fn remove_stop(&mut self, pos: &TilePos) -> Vec<u32> {
+        for line in self.lines.iter_mut() {
+            line.stops.retain(|stop| stop != pos);
+        }
+
+        let closed: Vec<u32> = self
+            .lines
+            .iter()
+            .filter(|line| line.stops.len() < 2)
+            .map(|line| line.id)
+            .collect();
+        self.lines.retain(|line| line.stops.len() >= 2);
+
+        closed
+    }
+
+    pub fn stop_count(&self) -> usize {
+        self.lines.iter().map(|line| line.stops.len()).sum()
+    }
+}
+
+/// Stops picked so far for the line currently being drawn
+#[derive(Resource, Default)]
+pub struct LineEditor {
+    pub active: bool,
+    pub pending_stops: Vec<TilePos>,
+}
+
+/// Commute figures from the last daily transit tick
+#[derive(Resource, Default, Debug)]
+pub struct TransitStats {
+    /// Employed citizens travelling to work every day
+    pub commuters: i64,
+    /// Commuters taking a bus instead of driving
+    pub riders: i64,
+    pub fare_income: i64,
+    pub upkeep: i64,
+}
+
+impl TransitStats {
+    /// Commuters that are left on the road network
+    pub fn road_commuters(&self) -> i64 {
+        (self.commuters - self.riders).max(0)
+    }
+}
+
+/// Car trips a road tile carries in an hour before traffic starts to back up
+pub const ROAD_TILE_TRIPS_PER_HOUR: f32 = 4.0;
+
+/// Load on the road network from the last daily transit tick. Bus riders are
+/// left out: every commuter who takes the bus is a car off the road.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct RoadTraffic {
+    /// Commuters who drive to work and back
+    pub car_commuters: i64,
+    /// Goods trucks and migrants using the highways
+    pub highway_trips: i64,
+    pub road_tiles: i64,
+}
+
+impl RoadTraffic {
+    pub fn daily_trips(&self) -> i64 {
+        self.car_commuters * 2 + self.highway_trips
+    }
+
+    pub fn hourly_capacity(&self) -> f32 {
+        self.road_tiles.max(0) as f32 * ROAD_TILE_TRIPS_PER_HOUR
+    }
+
+    /// Trips on the roads in `hour`: commuters crowd into the rush hours while
+    /// highway traffic is spread over the whole day
+    pub fn trips_in_hour(&self, hour: u8) -> f32 {
+        self.car_commuters.max(0) as f32 * 2.0 * commute_share(hour)
+            + self.highway_trips.max(0) as f32 / 24.0
+    }
+
+    fn delayed_in_hour(&self, hour: u8) -> f32 {
+        (self.trips_in_hour(hour) - self.hourly_capacity()).max(0.0)
+    }
+
+    /// Share of the day's trips stuck in jams because the roads can't carry them
+    /// in the hour they are made, so roads that cope on average still jam at rush hour
+    pub fn delayed_share(&self) -> f32 {
+        let trips = self.daily_trips();
+        if trips <= 0 {
+            return 0.0;
+        }
+        let delayed: f32 = (0..24).map(|hour| self.delayed_in_hour(hour)).sum();
+        (delayed / trips as f32).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(x: u32, y: u32) -> TilePos {
+        TilePos { x, y }
+    }
+
+    #[test]
+    fn removing_a_stop_shortens_lines_and_closes_stubs() {
+        let mut network = TransitNetwork::default();
+        let long = network.add_line(vec![tile(1, 1), tile(2, 2), tile(3, 3)]);
+        let short = network.add_line(vec![tile(1, 1), tile(4, 4)]);
+
+        let closed = network.remove_stop(&tile(1, 1));
+
+        assert_eq!(closed, vec![short]);
+        assert_eq!(network.lines.len(), 1);
+        assert_eq!(network.lines[0].id, long);
+        assert_eq!(network.lines[0].stops, vec![tile(2, 2), tile(3, 3)]);
+    }
+
+    #[test]
+    fn road_commuters_never_go_negative() {
+        let stats = TransitStats {
+            commuters: 10,
+            riders: 12,
+            ..default()
+        };
+
+        assert_eq!(stats.road_commuters(), 0);
+    }
+
+    #[test]
+    fn bus_riders_clear_the_jams() {
+        let stats = TransitStats {
+            commuters: 100,
+            riders: 40,
+            ..default()
+        };
+        let traffic = |car_commuters| RoadTraffic {
+            car_commuters,
+            highway_trips: 20,
+            road_tiles: 4,
+        };
+
+        let all_driving = traffic(stats.commuters);
+        let with_buses = traffic(stats.road_commuters());
+
+        assert_eq!(all_driving.daily_trips(), 220);
+        assert!(all_driving.delayed_share() > with_buses.delayed_share());
+        assert_eq!(with_buses.delayed_share(), 0.0);
+    }
+
+    #[test]
+    fn rush_hour_jams_roads_that_cope_on_average() {
+        let traffic = RoadTraffic {
+            car_commuters: 100,
+            highway_trips: 24,
+            road_tiles: 4,
+        };
+
+        assert!((traffic.daily_trips() as f32) < traffic.hourly_capacity() * 24.0);
+        assert!(traffic.trips_in_hour(8) > traffic.hourly_capacity());
+        assert!(traffic.trips_in_hour(3) < traffic.hourly_capacity());
+        assert!(traffic.delayed_share() > 0.0);
+    }
+}
@@ -0,0 +1,288 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use std::collections::HashSet;
+
+use crate::budget::{Budget, BudgetLedger, BuildingDemolished, BuildingType, LedgerCategory};
+use crate::city::OutsideConnections;
+use crate::city::resources::{
+    CityInfrastructure, CityServices, Demographics, building_contribution,
+};
+use crate::map::{CurrentTileType, CursorWorldPos, UiClickBlocker, cursor_to_map_pos};
+use crate::notifications::Notify;
+use crate::settings::{Action, ActionInput};
+use crate::spatial::SpatialGrid;
+use crate::time::GameClock;
+use crate::time::day_cycle::{fares_in_hour, hours_since};
+
+use super::resources::{LineEditor, RoadTraffic, TransitLine, TransitNetwork, TransitStats};
+
+/// Radius (in tiles) around a stop from which citizens walk to the bus
+const TRANSIT_CATCHMENT_RADIUS: i32 = 3;
+
+/// Share of the commuters living and working near a line that take the bus
+const TRANSIT_MODE_SHARE: f32 = 0.6;
+
+const FARE_PER_RIDER: i64 = 2;
+const LINE_UPKEEP_PER_DAY: i64 = 20;
+const STOP_UPKEEP_PER_DAY: i64 = 5;
+
+pub fn toggle_line_editor(
+    actions: ActionInput,
+    mut editor: ResMut<LineEditor>,
+    mut network: ResMut<TransitNetwork>,
+    mut current_tile_type: ResMut<CurrentTileType>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    // Picking a building tool leaves the editor without committing anything
+    if editor.active && current_tile_type.is_changed() && current_tile_type.texture_index != 0 {
+        editor.active = false;
+        editor.pending_stops.clear();
+        info!("Line editor closed");
+        return;
+    }
+
+    if actions.just_pressed(Action::EditBusLine) {
+        editor.active = !editor.active;
+        editor.pending_stops.clear();
+
+        if editor.active {
+            current_tile_type.texture_index = 0;
+            info!("Line editor opened: click bus stops in order, Enter to finish");
+        } else {
+            info!("Line editor closed");
+        }
+        return;
+    }
+
+    if !editor.active {
+        return;
+    }
+
+    if actions.just_pressed(Action::Cancel) {
+        editor.pending_stops.clear();
+        info!("Discarded pending bus line");
+    } else if actions.just_pressed(Action::FinishBusLine) {
+        if editor.pending_stops.len() < 2 {
+            warn!("A bus line needs at least two stops!");
+            return;
+        }
+
+        let stops = std::mem::take(&mut editor.pending_stops);
+        let stop_count = stops.len();
+        let id = network.add_line(stops);
+        info!("Opened bus line {} with {} stops", id, stop_count);
+        notify_writer.write(Notify::info(format!(
+            "Bus line {} opened with {} stops",
+            id, stop_count
+        )));
+    }
+}
+
+pub fn pick_line_stop_on_click(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
+    cursor_pos: Res<CursorWorldPos>,
+    ui_click_blocker: Res<UiClickBlocker>,
+    spatial_grid: Res<SpatialGrid>,
+    mut editor: ResMut<LineEditor>,
+    tilemap_q: Query<(
+        &TilemapSize,
+        &TilemapGridSize,
+        &TilemapTileSize,
+        &TilemapType,
+        &Transform,
+        &TilemapAnchor,
+    )>,
+) {
+    if !editor.active || !mouse_button.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    if ui_click_blocker.just_clicked_ui {
+        return;
+    }
+
+    if actions.pressed(Action::Demolish) {
+        return;
+    }
+
+    for (map_size, grid_size, tile_size, map_type, map_transform, anchor) in tilemap_q.iter() {
+        let cursor_in_map_pos = cursor_to_map_pos(cursor_pos.0, map_transform);
+
+        let Some(tile_pos) = TilePos::from_world_pos(
+            &cursor_in_map_pos,
+            map_size,
+            grid_size,
+            tile_size,
+            map_type,
+            anchor,
+        ) else {
+            continue;
+        };
+
+        if !spatial_grid.has_bus_stop_at(&tile_pos) {
+            warn!("Bus lines can only link bus stops!");
+            continue;
+        }
+
+        if editor.pending_stops.last() == Some(&tile_pos) {
+            continue;
+        }
+
+        editor.pending_stops.push(tile_pos);
+        info!(
+            "Added stop {:?} to pending line ({} stops)",
+            tile_pos,
+            editor.pending_stops.len()
+        );
+    }
+}
+
+pub fn remove_demolished_stops(
+    mut network: ResMut<TransitNetwork>,
+    mut editor: ResMut<LineEditor>,
+    mut demolished_reader: MessageReader<BuildingDemolished>,
+    mut notify_writer: MessageWriter<Notify>,
+) {
+    for event in demolished_reader.read() {
+        if event.building_type != BuildingType::BusStop {
+            continue;
+        }
+
+        editor.pending_stops.retain(|stop| *stop != event.tile_pos);
+
+        for id in network.remove_stop(&event.tile_pos) {
+            info!("Bus line {} closed after losing its stops", id);
+            notify_writer.write(
+                Notify::warning(format!("Bus line {} closed after losing its stops", id))
+                    .at(event.tile_pos),
+            );
+        }
+    }
+}
+
+/// Commuters whose home and workplace are both inside the line's catchment area
+fn line_ridership(
+    line: &TransitLine,
+    spatial_grid: &SpatialGrid,
+    services: &CityServices,
+    commuters: i64,
+) -> i64 {
+    if services.housing_capacity <= 0 || services.job_capacity <= 0 {
+        return 0;
+    }
+
+    let mut homes = HashSet::new();
+    let mut workplaces = HashSet::new();
+
+    for stop in &line.stops {
+        homes.extend(spatial_grid.buildings_of_type_in_radius(
+            stop,
+            TRANSIT_CATCHMENT_RADIUS,
+            BuildingType::Residential,
+        ));
+
+        for building_type in [BuildingType::Commercial, BuildingType::Industry] {
+            workplaces.extend(
+                spatial_grid
+                    .buildings_of_type_in_radius(stop, TRANSIT_CATCHMENT_RADIUS, building_type)
+                    .into_iter()
+                    .map(|pos| (pos, building_type)),
+            );
+        }
+    }
+
+    let covered_housing =
+        homes.len() as i64 * building_contribution(BuildingType::Residential).housing;
+    let covered_jobs: i64 = workplaces
+        .iter()
+        .map(|(_, building_type)| building_contribution(*building_type).jobs)
+        .sum();
+
+    let resident_share = (covered_housing as f32 / services.housing_capacity as f32).min(1.0);
+    let job_share = (covered_jobs as f32 / services.job_capacity as f32).min(1.0);
+
+    (commuters as f32 * resident_share * job_share * TRANSIT_MODE_SHARE).round() as i64
+}
+
+#[derive(SystemParam)]
+pub struct TransitTickInputs<'w> {
+    clock: Res<'w, GameClock>,
+    demographics: Res<'w, Demographics>,
+    services: Res<'w, CityServices>,
+    spatial_grid: Res<'w, SpatialGrid>,
+    network: Res<'w, TransitNetwork>,
+    connections: Res<'w, OutsideConnections>,
+    infra: Res<'w, CityInfrastructure>,
+    stats: ResMut<'w, TransitStats>,
+    traffic: ResMut<'w, RoadTraffic>,
+    budget: ResMut<'w, Budget>,
+    ledger: ResMut<'w, BudgetLedger>,
+}
+
+/// Once per in‑game day work out how many commuters take the bus, how much traffic the
+/// rest put on the roads, and pay line upkeep. The fares come in over the day, see
+/// `collect_fares_hourly`.
+pub fn update_transit_on_day_tick(mut inputs: TransitTickInputs, mut last_transit_day: Local<u32>) {
+    let day = inputs.clock.day;
+    if *last_transit_day == day {
+        return;
+    }
+    *last_transit_day = day;
+
+    let commuters = inputs
+        .demographics
+        .workforce()
+        .min(inputs.services.job_capacity.max(0));
+
+    let riders: i64 = inputs
+        .network
+        .lines
+        .iter()
+        .map(|line| line_ridership(line, &inputs.spatial_grid, &inputs.services, commuters))
+        .sum::<i64>()
+        .min(commuters);
+
+    let fare_income = riders * FARE_PER_RIDER;
+    let upkeep = inputs.network.lines.len() as i64 * LINE_UPKEEP_PER_DAY
+        + inputs.network.stop_count() as i64 * STOP_UPKEEP_PER_DAY;
+
+    if !inputs.network.lines.is_empty() {
+        let TransitTickInputs { budget, ledger, .. } = &mut inputs;
+        ledger.book(budget, day, LedgerCategory::TransitUpkeep, -upkeep);
+
+        info!(
+            "Transit: {} of {} commuters rode the bus (fares ${}, upkeep ${})",
+            riders, commuters, fare_income, upkeep
+        );
+    }
+
+    *inputs.stats = TransitStats {
+        commuters,
+        riders,
+        fare_income,
+        upkeep,
+    };
+    *inputs.traffic = RoadTraffic {
+        car_commuters: inputs.stats.road_commuters(),
+        highway_trips: inputs.connections.highway_trips(),
+        road_tiles: inputs.infra.road_count,
+    };
+}
+
+/// Riders pay as they board, so most of the day's fares arrive in the commute peaks
+pub fn collect_fares_hourly(
+    clock: Res<GameClock>,
+    stats: Res<TransitStats>,
+    mut budget: ResMut<Budget>,
+    mut ledger: ResMut<BudgetLedger>,
+    mut last_hour: Local<Option<u64>>,
+) {
+    for hour in hours_since(&mut last_hour, &clock) {
+        let fares = fares_in_hour(stats.fare_income, hour);
+        if fares != 0 {
+            ledger.book(&mut budget, clock.day, LedgerCategory::TransitFares, fares);
+        }
+    }
+}